@@ -0,0 +1,35 @@
+# souko shell integration for bash
+# Add the following line to ~/.bashrc:
+#     eval "$(souko shell-init bash)"
+# Customizable env vars (set before eval):
+#     SOUKO_KEY_CD_REPO : key sequence bound to `souko-cd` (default: '\C-g', empty to disable)
+
+# Select a repository in souko and change the current directory to it.
+souko-cd() {
+    local repo_path
+    repo_path="$(command souko pick --query "$*")" || return
+    [[ -n "${repo_path}" ]] || return
+    builtin cd -- "${repo_path}"
+}
+
+# Record a visit to the repository containing the current directory.
+# The visit is recorded in the background so that it does not delay the prompt.
+__souko_record_visit() {
+    local status=$?
+    if [[ "${__souko_last_pwd-}" != "${PWD}" ]]; then
+        __souko_last_pwd="${PWD}"
+        (command souko visit -- "${PWD}" >/dev/null 2>&1 &)
+    fi
+    return "${status}"
+}
+
+if [[ ";${PROMPT_COMMAND-};" != *";__souko_record_visit;"* ]]; then
+    PROMPT_COMMAND="__souko_record_visit${PROMPT_COMMAND:+;${PROMPT_COMMAND}}"
+fi
+
+if [[ $- == *i* ]]; then
+    : "${SOUKO_KEY_CD_REPO=\C-g}"
+    if [[ -n "${SOUKO_KEY_CD_REPO}" ]]; then
+        bind -x "\"${SOUKO_KEY_CD_REPO}\": souko-cd"
+    fi
+fi
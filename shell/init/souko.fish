@@ -0,0 +1,33 @@
+# souko shell integration for fish
+# Add the following line to ~/.config/fish/config.fish:
+#     souko shell-init fish | source
+# Customizable env vars (set before source):
+#     SOUKO_KEY_CD_REPO : key bound to `souko-cd` (default: \cg = Ctrl-g, empty to disable)
+
+function souko-cd --description 'Select a repository in souko and change the current directory to it'
+    set -l repo_path (command souko pick --query "$argv")
+    or return
+    test -n "$repo_path"
+    or return
+    builtin cd -- $repo_path
+end
+
+function __souko_record_visit --on-variable PWD --description 'Record a visit to the repository containing the current directory'
+    # Record the visit in the background so that it does not delay the prompt.
+    command souko visit -- $PWD >/dev/null 2>&1 &
+    disown
+end
+
+function __souko_cd_widget
+    souko-cd
+    commandline -f repaint
+end
+
+if status is-interactive
+    set -q SOUKO_KEY_CD_REPO
+    or set -l SOUKO_KEY_CD_REPO \cg
+    if test -n "$SOUKO_KEY_CD_REPO"
+        bind $SOUKO_KEY_CD_REPO __souko_cd_widget
+        bind -M insert $SOUKO_KEY_CD_REPO __souko_cd_widget
+    end
+end
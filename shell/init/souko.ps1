@@ -0,0 +1,37 @@
+# souko shell integration for PowerShell
+# Add the following line to your profile ($PROFILE):
+#     Invoke-Expression (& { (souko shell-init powershell | Out-String) })
+
+# Select a repository in souko and change the current directory to it.
+function global:souko-cd {
+    $repoPath = & souko pick --query ($args -join ' ')
+    if ($LASTEXITCODE -eq 0 -and $repoPath) {
+        Set-Location -LiteralPath $repoPath
+    }
+}
+
+# Record a visit to the repository containing the current directory.
+function global:__souko_record_visit {
+    $location = $ExecutionContext.SessionState.Path.CurrentFileSystemLocation.ProviderPath
+    if ($location -ne $global:__souko_last_location) {
+        $global:__souko_last_location = $location
+        $lastExitCode = $global:LASTEXITCODE
+        $null = & souko visit -- $location 2>$null
+        $global:LASTEXITCODE = $lastExitCode
+    }
+}
+
+if (-not $global:__souko_prompt) {
+    $global:__souko_prompt = $function:prompt
+    function global:prompt {
+        __souko_record_visit
+        & $global:__souko_prompt
+    }
+}
+
+if (Get-Module -Name PSReadLine) {
+    Set-PSReadLineKeyHandler -Chord Ctrl+g -BriefDescription souko-cd -ScriptBlock {
+        souko-cd
+        [Microsoft.PowerShell.PSConsoleReadLine]::InvokePrompt()
+    }
+}
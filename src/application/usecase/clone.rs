@@ -3,11 +3,27 @@ use std::sync::Arc;
 use crate::{
     application::support::workdir_guard::WorkdirGuard,
     domain::{
-        model::{query::Query, repo::Repo, root::Root},
+        model::{
+            query::Query,
+            remote::{ORIGIN_REMOTE_NAME, Remote},
+            repo::Repo,
+            repo_url::RepoUrlTemplateContext,
+            root::Root,
+            template::Template,
+        },
         port::{Ports, clone_repo::RepoClone, dir_editor::DirEditor},
     },
 };
 
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CloneOptions {
+    pub(crate) bare: bool,
+    /// Template of the push URL set on the `origin` remote.
+    pub(crate) push_url_template: Option<Template<RepoUrlTemplateContext>>,
+    /// Remotes added in addition to `origin`, as pairs of a remote name and a query.
+    pub(crate) extra_remotes: Vec<(String, Query)>,
+}
+
 #[derive(Debug)]
 pub(crate) struct CloneUsecase {
     dir_editor: Arc<dyn DirEditor>,
@@ -26,16 +42,31 @@ impl CloneUsecase {
         &self,
         root: &Root,
         query: &Query,
-        bare: bool,
+        options: &CloneOptions,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
-        let repo = Repo::from_query(root, query, bare);
+        let repo = Repo::from_query(root, query, options.bare)?;
         let clone_path = repo.path();
 
+        let (origin, extra_remotes) = remotes(query, options);
+
         let dir_editor = Arc::clone(&self.dir_editor);
         let mut workdir = WorkdirGuard::create(dir_editor, clone_path)?;
-        self.repo_clone.clone_repo(query.url(), clone_path, bare)?;
+        self.repo_clone
+            .clone_repo(&origin, &extra_remotes, clone_path, options.bare)?;
         workdir.persist()?;
 
         Ok(())
     }
 }
+
+/// Returns the `origin` remote and the extra remotes configured on a cloned repository.
+pub(crate) fn remotes(query: &Query, options: &CloneOptions) -> (Remote, Vec<Remote>) {
+    let push_url_template = options.push_url_template.as_ref();
+    let origin = Remote::from_query(ORIGIN_REMOTE_NAME, query, push_url_template);
+    let extra_remotes = options
+        .extra_remotes
+        .iter()
+        .map(|(name, query)| Remote::from_query(name.as_str(), query, None))
+        .collect();
+    (origin, extra_remotes)
+}
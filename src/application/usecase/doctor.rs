@@ -0,0 +1,192 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::{
+    application::support::workdir_guard::WorkdirGuard,
+    domain::{
+        model::{
+            path_buf_pair::PathBufPair,
+            root::{CanonicalRoot, Root},
+        },
+        port::{
+            Ports,
+            dir_editor::DirEditor,
+            dir_walker::DirWalker,
+            git_backend::{GitBackend, GitBackendFeatures, SshAgentStatus},
+            path_canonicalizer::{PathCanonicalizer, PathCanonicalizerError},
+            repo_cache::RepoCache,
+            visit_store::VisitStore,
+        },
+    },
+};
+
+/// State of the directory of a root.
+#[derive(Debug)]
+pub(crate) enum RootStatus {
+    Ok(CanonicalRoot),
+    Missing,
+    Unreadable(Box<dyn std::error::Error>),
+}
+
+/// Two roots whose directories are the same or one contains the other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RootOverlap {
+    pub(crate) outer: String,
+    pub(crate) inner: String,
+    /// `true` if both roots are the same directory.
+    pub(crate) same: bool,
+}
+
+/// Checks the environment souko runs in.
+#[derive(Debug)]
+pub(crate) struct DoctorUsecase {
+    path_canonicalizer: Arc<dyn PathCanonicalizer>,
+    dir_walker: Arc<dyn DirWalker>,
+    dir_editor: Arc<dyn DirEditor>,
+    repo_cache: Arc<dyn RepoCache>,
+    visit_store: Arc<dyn VisitStore>,
+    git_backend: Arc<dyn GitBackend>,
+}
+
+impl DoctorUsecase {
+    pub(crate) fn new(ports: &Ports) -> Self {
+        Self {
+            path_canonicalizer: Arc::clone(&ports.path_canonicalizer),
+            dir_walker: Arc::clone(&ports.dir_walker),
+            dir_editor: Arc::clone(&ports.dir_editor),
+            repo_cache: Arc::clone(&ports.repo_cache),
+            visit_store: Arc::clone(&ports.visit_store),
+            git_backend: Arc::clone(&ports.git_backend),
+        }
+    }
+
+    /// Checks that the directory of `root` exists and can be listed.
+    pub(crate) fn check_root(&self, root: &Root) -> RootStatus {
+        let canonical_path = match self.path_canonicalizer.canonicalize(root.path()) {
+            Ok(path) => path,
+            Err(PathCanonicalizerError::PathNotFound { .. }) => return RootStatus::Missing,
+            Err(PathCanonicalizerError::Backend(e)) => return RootStatus::Unreadable(e),
+        };
+        let root = CanonicalRoot::new(root.clone(), canonical_path);
+        let first_entry = self
+            .dir_walker
+            .entries(&root, false)
+            .and_then(|mut entries| entries.next().transpose());
+        match first_entry {
+            Ok(_) => RootStatus::Ok(root),
+            Err(e) => RootStatus::Unreadable(e),
+        }
+    }
+
+    /// Creates the directory of `root` with its missing ancestors.
+    pub(crate) fn create_root(
+        &self,
+        root: &Root,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let mut workdir = WorkdirGuard::create(Arc::clone(&self.dir_editor), root.path())?;
+        workdir.persist()
+    }
+
+    /// Checks that the repository cache can be read.
+    pub(crate) fn check_repo_cache(
+        &self,
+        path: &PathBufPair,
+        now: DateTime<Utc>,
+        expire_duration: TimeDelta,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        self.repo_cache.load(path, now, expire_duration)
+    }
+
+    /// Replaces the repository cache with an empty one.
+    ///
+    /// The cache is rebuilt by the next `souko list`.
+    pub(crate) fn reset_repo_cache(
+        &self,
+        path: &PathBufPair,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        self.repo_cache.clear();
+        self.repo_cache.persist(path)
+    }
+
+    /// Checks that the visit history can be read.
+    pub(crate) fn check_visit_history(
+        &self,
+        path: &PathBufPair,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        self.visit_store.load(path)
+    }
+
+    pub(crate) fn git_backend_features(&self) -> GitBackendFeatures {
+        self.git_backend.features()
+    }
+
+    pub(crate) fn ssh_agent(&self) -> SshAgentStatus {
+        self.git_backend.ssh_agent()
+    }
+}
+
+/// Returns the pairs of roots whose directories are the same or nested.
+pub(crate) fn root_overlaps(roots: &[CanonicalRoot]) -> Vec<RootOverlap> {
+    let mut overlaps = vec![];
+    for (i, a) in roots.iter().enumerate() {
+        for b in &roots[i + 1..] {
+            let (a_path, b_path) = (a.canonical_path(), b.canonical_path());
+            let overlap = if a_path == b_path {
+                Some((a, b, true))
+            } else if b_path.starts_with(a_path) {
+                Some((a, b, false))
+            } else if a_path.starts_with(b_path) {
+                Some((b, a, false))
+            } else {
+                None
+            };
+            if let Some((outer, inner, same)) = overlap {
+                overlaps.push(RootOverlap {
+                    outer: outer.name().to_owned(),
+                    inner: inner.name().to_owned(),
+                    same,
+                });
+            }
+        }
+    }
+    overlaps
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn root(name: &str, path: &str) -> CanonicalRoot {
+        let root = Root::new(name.to_owned(), PathBufPair::new(PathBuf::from(path)));
+        CanonicalRoot::new(root, PathBuf::from(path))
+    }
+
+    #[test]
+    fn root_overlaps_reports_same_and_nested_roots() {
+        let roots = [
+            root("a", "/src"),
+            root("b", "/src/work"),
+            root("c", "/other"),
+            root("d", "/other"),
+            root("e", "/src-2"),
+        ];
+        assert_eq!(
+            root_overlaps(&roots),
+            [
+                RootOverlap {
+                    outer: "a".to_owned(),
+                    inner: "b".to_owned(),
+                    same: false,
+                },
+                RootOverlap {
+                    outer: "c".to_owned(),
+                    inner: "d".to_owned(),
+                    same: true,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use regex::Regex;
+
+use crate::domain::{
+    model::{grep::FileMatches, path_like::PathLike},
+    port::{
+        Ports,
+        repo_files::{FileSource, RepoFiles},
+    },
+};
+
+/// Searches files in repositories.
+///
+/// This can be shared between threads to search several repositories in parallel.
+#[derive(Debug)]
+pub(crate) struct GrepUsecase {
+    repo_files: Arc<dyn RepoFiles>,
+}
+
+impl GrepUsecase {
+    pub(crate) fn new(ports: &Ports) -> Self {
+        Self {
+            repo_files: Arc::clone(&ports.repo_files),
+        }
+    }
+
+    /// Returns the files in `source` of the repository matching `pattern`, in path order.
+    pub(crate) fn search_repo(
+        &self,
+        repo_path: &dyn PathLike,
+        source: FileSource,
+        pattern: &Regex,
+    ) -> Result<Vec<FileMatches>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let mut files = vec![];
+        self.repo_files
+            .for_each_file(repo_path, source, &mut |path, content| {
+                files.extend(FileMatches::search(path, content, pattern));
+            })?;
+        files.sort_by(|a, b| a.path().cmp(b.path()));
+        Ok(files)
+    }
+}
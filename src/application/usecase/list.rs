@@ -1,4 +1,8 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
 
 use chrono::{DateTime, Duration, TimeDelta, Utc};
 use color_eyre::eyre::eyre;
@@ -10,6 +14,7 @@ use crate::{
             path_like::PathLike,
             repo::CanonicalRepo,
             root::{CanonicalRoot, Root},
+            visit::FrecencyScores,
         },
         port::{
             Ports,
@@ -24,16 +29,31 @@ use crate::{
 #[derive(Debug, Clone)]
 pub(crate) struct ListOptions {
     pub(crate) cache_expire_duration: TimeDelta,
+    pub(crate) duplicate_repo_policy: DuplicateRepoPolicy,
 }
 
 impl Default for ListOptions {
     fn default() -> Self {
         Self {
             cache_expire_duration: Duration::try_days(3).unwrap(),
+            duplicate_repo_policy: DuplicateRepoPolicy::default(),
         }
     }
 }
 
+/// How to handle repositories whose canonical path has already been listed.
+///
+/// The same repository can be reached more than once when roots overlap or
+/// when symbolic links are followed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum DuplicateRepoPolicy {
+    /// Skip every occurrence but the first one and report it as an error.
+    #[default]
+    Warn,
+    /// Yield every occurrence.
+    Show,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct ListContext {
     pub(crate) now: DateTime<Utc>,
@@ -46,6 +66,8 @@ pub(crate) struct ListRootInput {
     pub(crate) visit_hidden_dirs: bool,
     pub(crate) visit_repo_subdirs: bool,
     pub(crate) include_bare_repo: bool,
+    pub(crate) include_linked_worktrees: bool,
+    pub(crate) follow_symlinks: bool,
     pub(crate) root: Root,
 }
 
@@ -59,6 +81,17 @@ pub(crate) enum ListUsecaseError {
         #[source]
         source: PathCanonicalizerError,
     },
+    #[error(
+        "skipping duplicate repository {} in root `{root_name}` (already listed as {} in root `{first_root_name}`)",
+        path.display(),
+        first_path.display()
+    )]
+    DuplicateRepo {
+        root_name: String,
+        path: PathBuf,
+        first_root_name: String,
+        first_path: PathBuf,
+    },
     #[error(transparent)]
     Backend(#[from] Box<dyn std::error::Error>),
 }
@@ -94,6 +127,7 @@ impl ListUsecase {
             path_canonicalizer: Arc::clone(&self.path_canonicalizer),
             repo_cache: Arc::clone(&self.repo_cache),
             repo_scan_service: self.repo_scan_service.clone(),
+            seen_repos: SeenRepos::new(options.duplicate_repo_policy),
             context,
             roots: roots.into_iter(),
         }
@@ -131,6 +165,7 @@ pub(crate) struct ListRoots<'a, I> {
     path_canonicalizer: Arc<dyn PathCanonicalizer>,
     repo_cache: Arc<dyn RepoCache>,
     repo_scan_service: RepoScanService,
+    seen_repos: SeenRepos,
     context: ListContext,
     roots: I,
 }
@@ -179,6 +214,9 @@ where
                     visit_hidden_dirs: input.visit_hidden_dirs,
                     visit_repo_subdirs: input.visit_repo_subdirs,
                     include_bare_repo: input.include_bare_repo,
+                    include_linked_worktrees: input.include_linked_worktrees,
+                    follow_symlinks: input.follow_symlinks,
+                    seen_repos: self.seen_repos.clone(),
                     root,
                 })),
                 Err(ListUsecaseError::RootNotExist { .. }) if input.allow_missing_root => continue,
@@ -195,6 +233,9 @@ pub(crate) struct ListRoot {
     visit_hidden_dirs: bool,
     visit_repo_subdirs: bool,
     include_bare_repo: bool,
+    include_linked_worktrees: bool,
+    follow_symlinks: bool,
+    seen_repos: SeenRepos,
     root: CanonicalRoot,
 }
 
@@ -204,7 +245,7 @@ impl ListRoot {
     }
 
     pub(crate) fn repos(&self) -> Result<ListRepos, ListUsecaseError> {
-        let mut repos = self.repo_scan.repos(&self.root)?;
+        let mut repos = self.repo_scan.repos(&self.root, self.follow_symlinks)?;
         if !self.visit_hidden_dirs {
             repos.filter_entry(|e| !e.is_hidden());
         }
@@ -212,9 +253,66 @@ impl ListRoot {
             repo_cache: Arc::clone(&self.repo_cache),
             visit_repo_subdirs: self.visit_repo_subdirs,
             include_bare_repo: self.include_bare_repo,
+            include_linked_worktrees: self.include_linked_worktrees,
+            seen_repos: self.seen_repos.clone(),
+            root_name: self.root.name().to_owned(),
             repos,
         })
     }
+
+    /// Returns the repositories of the root recorded in the repository cache,
+    /// without walking the root.
+    ///
+    /// Repositories added or removed since the root was last listed are not
+    /// reflected.
+    pub(crate) fn cached_repos(&self) -> Vec<CanonicalRepo> {
+        let mut repos = self.repo_cache.repos(&self.root);
+        repos.retain(|repo| {
+            (self.include_bare_repo || !repo.bare())
+                && (self.include_linked_worktrees || !repo.kind().is_linked_worktree())
+        });
+        repos
+    }
+}
+
+/// Canonical paths of the repositories yielded so far, shared by all roots of a
+/// single listing.
+#[derive(Debug, Clone)]
+struct SeenRepos {
+    policy: DuplicateRepoPolicy,
+    repos: Arc<Mutex<HashMap<PathBuf, (String, PathBuf)>>>,
+}
+
+impl SeenRepos {
+    fn new(policy: DuplicateRepoPolicy) -> Self {
+        Self {
+            policy,
+            repos: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    fn check(&self, root_name: &str, repo: &CanonicalRepo) -> Result<(), ListUsecaseError> {
+        if self.policy == DuplicateRepoPolicy::Show {
+            return Ok(());
+        }
+        let mut repos = self.repos.lock().unwrap();
+        if let Some((first_root_name, first_path)) = repos.get(repo.canonical_path()) {
+            return Err(ListUsecaseError::DuplicateRepo {
+                root_name: root_name.to_owned(),
+                path: repo.path().as_display_path().to_owned(),
+                first_root_name: first_root_name.clone(),
+                first_path: first_path.clone(),
+            });
+        }
+        repos.insert(
+            repo.canonical_path().to_owned(),
+            (
+                root_name.to_owned(),
+                repo.path().as_display_path().to_owned(),
+            ),
+        );
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -222,6 +320,9 @@ pub(crate) struct ListRepos {
     repo_cache: Arc<dyn RepoCache>,
     visit_repo_subdirs: bool,
     include_bare_repo: bool,
+    include_linked_worktrees: bool,
+    seen_repos: SeenRepos,
+    root_name: String,
     repos: Repos,
 }
 
@@ -260,7 +361,165 @@ impl Iterator for ListRepos {
                 tracing::trace!("skipping bare repo: {}", repo.path().display());
                 continue;
             }
+            if !self.include_linked_worktrees && repo.kind().is_linked_worktree() {
+                tracing::trace!("skipping linked worktree: {}", repo.path().display());
+                continue;
+            }
+            itry!(self.seen_repos.check(&self.root_name, &repo));
             return Some(Ok(repo));
         }
     }
 }
+
+/// Reorders repositories so that each linked worktree directly follows its main
+/// repository.
+///
+/// Linked worktrees whose main repository is not contained in `repos` keep
+/// their position.
+pub(crate) fn group_linked_worktrees<I>(repos: I) -> Vec<CanonicalRepo>
+where
+    I: IntoIterator<Item = CanonicalRepo>,
+{
+    let repos = repos.into_iter().collect::<Vec<_>>();
+    let mut main_repos = HashMap::new();
+    for (idx, repo) in repos.iter().enumerate() {
+        if !repo.kind().is_linked_worktree() {
+            main_repos
+                .entry(repo.common_dir().to_owned())
+                .or_insert(idx);
+        }
+    }
+
+    let mut worktrees = HashMap::<usize, Vec<CanonicalRepo>>::new();
+    let mut top_level = vec![];
+    for (idx, repo) in repos.into_iter().enumerate() {
+        match main_repos.get(repo.common_dir()) {
+            Some(&main_idx) if repo.kind().is_linked_worktree() => {
+                worktrees.entry(main_idx).or_default().push(repo);
+            }
+            _ => top_level.push((idx, repo)),
+        }
+    }
+
+    let mut grouped = vec![];
+    for (idx, repo) in top_level {
+        grouped.push(repo);
+        grouped.extend(worktrees.remove(&idx).unwrap_or_default());
+    }
+    grouped
+}
+
+/// Sorts repositories of every root by descending frecency score.
+///
+/// Repositories with the same score keep their order. Consecutive repositories
+/// in the same root are grouped together, so a root can appear more than once.
+pub(crate) fn sort_by_frecency<I, R>(
+    roots: I,
+    scores: &FrecencyScores,
+) -> Vec<(CanonicalRoot, Vec<CanonicalRepo>)>
+where
+    I: IntoIterator<Item = (CanonicalRoot, R)>,
+    R: IntoIterator<Item = CanonicalRepo>,
+{
+    let mut repos = roots
+        .into_iter()
+        .flat_map(|(root, repos)| repos.into_iter().map(move |repo| (root.clone(), repo)))
+        .map(|(root, repo)| (scores.get(repo.canonical_path()), root, repo))
+        .collect::<Vec<_>>();
+    repos.sort_by(|(a, ..), (b, ..)| b.total_cmp(a));
+
+    let mut sorted: Vec<(CanonicalRoot, Vec<CanonicalRepo>)> = vec![];
+    for (_score, root, repo) in repos {
+        match sorted.last_mut() {
+            Some((last_root, last_repos)) if last_root.name() == root.name() => {
+                last_repos.push(repo);
+            }
+            _ => sorted.push((root, vec![repo])),
+        }
+    }
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::domain::model::{
+        repo::{Repo, RepoKind},
+        visit::RepoVisits,
+    };
+
+    fn repo(root: &Root, path: &str, kind: RepoKind, common_dir: &str) -> CanonicalRepo {
+        let repo = Repo::from_relative_path(root, path.into(), kind);
+        let canonical_path = repo.path().as_real_path().to_owned();
+        CanonicalRepo::new(repo, canonical_path, common_dir.into())
+    }
+
+    #[test]
+    fn group_linked_worktrees_moves_worktrees_after_main_repo() {
+        let root = Root::new("test".into(), PathBufPair::new(Path::new("/root")));
+        let repos = [
+            repo(&root, "a@feature", RepoKind::LinkedWorktree, "/root/a/.git"),
+            repo(&root, "a", RepoKind::Normal, "/root/a/.git"),
+            repo(&root, "b", RepoKind::Normal, "/root/b/.git"),
+            repo(&root, "orphan", RepoKind::LinkedWorktree, "/elsewhere/.git"),
+            repo(&root, "z@fix", RepoKind::LinkedWorktree, "/root/b/.git"),
+        ];
+
+        let grouped = group_linked_worktrees(repos);
+        let paths = grouped
+            .iter()
+            .map(|repo| repo.relative_path().to_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(paths, ["a", "a@feature", "b", "z@fix", "orphan"]);
+    }
+
+    #[test]
+    fn sort_by_frecency_orders_repos_across_roots() {
+        let root_a = Root::new("a".into(), PathBufPair::new(Path::new("/a")));
+        let root_b = Root::new("b".into(), PathBufPair::new(Path::new("/b")));
+        let roots = [
+            (
+                CanonicalRoot::new(root_a.clone(), "/a".into()),
+                vec![
+                    repo(&root_a, "x", RepoKind::Normal, "/a/x/.git"),
+                    repo(&root_a, "y", RepoKind::Normal, "/a/y/.git"),
+                    repo(&root_a, "z", RepoKind::Normal, "/a/z/.git"),
+                ],
+            ),
+            (
+                CanonicalRoot::new(root_b.clone(), "/b".into()),
+                vec![repo(&root_b, "w", RepoKind::Normal, "/b/w/.git")],
+            ),
+        ];
+        let now = Utc::now();
+        let scores = FrecencyScores::new(
+            &[
+                RepoVisits::new("/a/z".into(), 1, now),
+                RepoVisits::new("/b/w".into(), 2, now),
+                RepoVisits::new("/a/y".into(), 3, now),
+            ],
+            now,
+        );
+
+        let sorted = sort_by_frecency(roots, &scores)
+            .into_iter()
+            .map(|(root, repos)| {
+                let paths = repos
+                    .iter()
+                    .map(|repo| repo.relative_path().to_str().unwrap().to_owned())
+                    .collect::<Vec<_>>();
+                (root.name().to_owned(), paths)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sorted,
+            [
+                ("a".to_owned(), vec!["y".to_owned()]),
+                ("b".to_owned(), vec!["w".to_owned()]),
+                ("a".to_owned(), vec!["z".to_owned(), "x".to_owned()]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use std::{error::Error, sync::Arc};
+
+use crate::{
+    application::support::workdir_guard::WorkdirGuard,
+    domain::{
+        model::{
+            layout::RepoLayoutError,
+            path_buf_pair::PathBufPair,
+            path_like::PathLike as _,
+            query::{ParseError, ParseOption, Query},
+            repo::{CanonicalRepo, Repo, RepoKind},
+            root::{CanonicalRoot, Root},
+        },
+        port::{
+            Ports, dir_editor::DirEditor, repo_remote::RepoRemote, repo_worktree::RepoWorktree,
+        },
+    },
+};
+
+const ORIGIN: &str = "origin";
+
+/// A repository whose path does not match the path its origin URL implies.
+#[derive(Debug)]
+pub(crate) struct MisplacedRepo {
+    pub(crate) repo: CanonicalRepo,
+    pub(crate) origin_url: String,
+    /// The root the repository would be cloned into from its origin URL.
+    pub(crate) expected_root: Root,
+    /// The repository placed where it would be cloned from its origin URL.
+    pub(crate) expected: Repo,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum MisplacedUsecaseError {
+    #[error("failed to read the origin remote of {}", path.display())]
+    ReadOrigin {
+        path: PathBufPair,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+    #[error("failed to parse the origin URL of {}", path.display())]
+    ParseOrigin {
+        path: PathBufPair,
+        #[source]
+        source: Box<ParseError>,
+    },
+    #[error("failed to select the root for the origin URL of {}", path.display())]
+    SelectRoot {
+        path: PathBufPair,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+    #[error("failed to compute the path of the origin URL of {}", path.display())]
+    Layout {
+        path: PathBufPair,
+        #[source]
+        source: RepoLayoutError,
+    },
+    #[error("failed to list linked worktrees of {}", path.display())]
+    ListWorktrees {
+        path: PathBufPair,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+    #[error("{} has linked worktrees, which would be broken by moving it", path.display())]
+    HasLinkedWorktrees { path: PathBufPair },
+    #[error("failed to move {} to {}", from.display(), to.display())]
+    Move {
+        from: PathBufPair,
+        to: PathBufPair,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+    #[error(
+        "{} belongs to root `{root}` by its origin URL, which updating the remote cannot fix",
+        path.display()
+    )]
+    OtherRoot { path: PathBufPair, root: String },
+    #[error("cannot derive an origin URL matching the path of {} from `{url}`", path.display())]
+    DeriveUrl { path: PathBufPair, url: String },
+    #[error("failed to update the origin remote of {}", path.display())]
+    WriteOrigin {
+        path: PathBufPair,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+}
+
+#[derive(Debug)]
+pub(crate) struct MisplacedUsecase {
+    dir_editor: Arc<dyn DirEditor>,
+    repo_remote: Arc<dyn RepoRemote>,
+    repo_worktree: Arc<dyn RepoWorktree>,
+}
+
+impl MisplacedUsecase {
+    pub(crate) fn new(ports: &Ports) -> Self {
+        Self {
+            dir_editor: Arc::clone(&ports.dir_editor),
+            repo_remote: Arc::clone(&ports.repo_remote),
+            repo_worktree: Arc::clone(&ports.repo_worktree),
+        }
+    }
+
+    /// Compares the path of `repo` in `root` with the path its origin URL implies.
+    ///
+    /// `route_root` returns the root that a route selects for the origin URL,
+    /// which may differ from `root`. If no route matches, the repository is
+    /// expected in `root`, so that repositories are never moved out of roots
+    /// other than the default one just because nothing routes them there.
+    ///
+    /// Returns `None` if the paths match, or if the repository has no origin
+    /// remote. Linked worktrees and submodules are never misplaced because
+    /// their paths are not derived from the origin URL.
+    pub(crate) fn check<E>(
+        &self,
+        root: &CanonicalRoot,
+        repo: &CanonicalRepo,
+        parse_option: &ParseOption,
+        route_root: impl FnOnce(&Query) -> Result<Option<Root>, E>,
+    ) -> Result<Option<MisplacedRepo>, MisplacedUsecaseError>
+    where
+        E: Into<Box<dyn Error + Send + Sync + 'static>>,
+    {
+        if !matches!(repo.kind(), RepoKind::Normal | RepoKind::Bare) {
+            return Ok(None);
+        }
+        let path = || PathBufPair::new(repo.path());
+        let Some(origin_url) =
+            self.repo_remote
+                .remote_url(repo.path(), ORIGIN)
+                .map_err(|source| MisplacedUsecaseError::ReadOrigin {
+                    path: path(),
+                    source,
+                })?
+        else {
+            return Ok(None);
+        };
+        let query = Query::parse(&origin_url, parse_option).map_err(|source| {
+            MisplacedUsecaseError::ParseOrigin {
+                path: path(),
+                source: Box::new(source),
+            }
+        })?;
+        let expected_root = route_root(&query)
+            .map_err(|source| MisplacedUsecaseError::SelectRoot {
+                path: path(),
+                source: source.into(),
+            })?
+            .unwrap_or_else(|| root.as_root().clone());
+        let expected = Repo::from_query(&expected_root, &query, repo.bare()).map_err(|source| {
+            MisplacedUsecaseError::Layout {
+                path: path(),
+                source,
+            }
+        })?;
+        if expected_root.name() == root.name() && expected.relative_path() == repo.relative_path() {
+            return Ok(None);
+        }
+        Ok(Some(MisplacedRepo {
+            repo: repo.clone(),
+            origin_url,
+            expected_root,
+            expected,
+        }))
+    }
+
+    /// Moves the misplaced repository to the path its origin URL implies,
+    /// which may be in another root.
+    ///
+    /// Directories left empty by the move are removed up to the root.
+    /// Repositories with linked worktrees are not moved, because the worktrees
+    /// refer to the repository by its path.
+    pub(crate) fn move_repo(
+        &self,
+        root: &CanonicalRoot,
+        misplaced: &MisplacedRepo,
+    ) -> Result<(), MisplacedUsecaseError> {
+        let repo = &misplaced.repo;
+        let path = || PathBufPair::new(repo.path());
+        if !repo.bare() {
+            let worktrees = self
+                .repo_worktree
+                .list_worktrees(repo.path())
+                .map_err(|source| MisplacedUsecaseError::ListWorktrees {
+                    path: path(),
+                    source,
+                })?;
+            if !worktrees.is_empty() {
+                return Err(MisplacedUsecaseError::HasLinkedWorktrees { path: path() });
+            }
+        }
+
+        let from = repo.path().as_real_path();
+        let to = misplaced.expected.path().as_real_path();
+        let move_err = |source| MisplacedUsecaseError::Move {
+            from: path(),
+            to: PathBufPair::new(misplaced.expected.path()),
+            source,
+        };
+        let parent = to
+            .parent()
+            .expect("repository path is a non-empty path under the root");
+        let mut workdir =
+            WorkdirGuard::create(Arc::clone(&self.dir_editor), &parent).map_err(move_err)?;
+        self.dir_editor.move_dir(from, to).map_err(move_err)?;
+        workdir.persist().map_err(move_err)?;
+
+        let root_path = root.path().as_real_path();
+        let mut dir = from.parent();
+        while let Some(d) = dir.filter(|d| *d != root_path && d.starts_with(root_path)) {
+            if !matches!(self.dir_editor.ensure_dir_removed(d), Ok(true)) {
+                break;
+            }
+            dir = d.parent();
+        }
+        Ok(())
+    }
+
+    /// Returns an origin URL that implies the current path of the misplaced repository.
+    ///
+    /// The URL is derived by replacing the path segments of the current origin
+    /// URL that differ between the expected and the current paths. A
+    /// repository that belongs to another root cannot be fixed this way.
+    pub(crate) fn origin_url_for_path(
+        &self,
+        root: &CanonicalRoot,
+        misplaced: &MisplacedRepo,
+        parse_option: &ParseOption,
+    ) -> Result<String, MisplacedUsecaseError> {
+        let repo = &misplaced.repo;
+        if misplaced.expected_root.name() != root.name() {
+            return Err(MisplacedUsecaseError::OtherRoot {
+                path: PathBufPair::new(repo.path()),
+                root: misplaced.expected_root.name().to_owned(),
+            });
+        }
+        let derive_err = || MisplacedUsecaseError::DeriveUrl {
+            path: PathBufPair::new(repo.path()),
+            url: misplaced.origin_url.clone(),
+        };
+        let expected = misplaced.expected.relative_path().components();
+        let actual = repo.relative_path().components();
+        if expected.clone().count() != actual.clone().count() {
+            return Err(derive_err());
+        }
+
+        let mut url = misplaced.origin_url.clone();
+        for (expected, actual) in expected.zip(actual) {
+            if expected == actual {
+                continue;
+            }
+            let (Some(expected), Some(actual)) =
+                (expected.as_os_str().to_str(), actual.as_os_str().to_str())
+            else {
+                return Err(derive_err());
+            };
+            url = replace_segment(&url, expected, actual).ok_or_else(derive_err)?;
+        }
+
+        // Make sure that the derived URL really implies the current path.
+        let implied = Query::parse(&url, parse_option)
+            .ok()
+            .and_then(|query| Repo::from_query(root.as_root(), &query, repo.bare()).ok());
+        match implied {
+            Some(implied) if implied.relative_path() == repo.relative_path() => Ok(url),
+            _ => Err(derive_err()),
+        }
+    }
+
+    /// Sets the URL of the origin remote of the misplaced repository.
+    pub(crate) fn update_origin(
+        &self,
+        misplaced: &MisplacedRepo,
+        url: &str,
+    ) -> Result<(), MisplacedUsecaseError> {
+        let repo = &misplaced.repo;
+        self.repo_remote
+            .set_remote_url(repo.path(), ORIGIN, url)
+            .map_err(|source| MisplacedUsecaseError::WriteOrigin {
+                path: PathBufPair::new(repo.path()),
+                source,
+            })
+    }
+}
+
+/// Replaces the last path segment of `url` equal to `from` with `to`.
+///
+/// A segment is delimited by `/` or `:` on the left, and by `/`, `.git` or the
+/// end of the URL on the right.
+fn replace_segment(url: &str, from: &str, to: &str) -> Option<String> {
+    url.rmatch_indices(from).find_map(|(start, _)| {
+        let end = start + from.len();
+        let left_ok = url[..start].ends_with(['/', ':']);
+        let rest = &url[end..];
+        let right_ok = rest.is_empty() || rest.starts_with('/') || rest == ".git";
+        (left_ok && right_ok).then(|| format!("{}{to}{}", &url[..start], rest))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replace_segment_replaces_whole_segments_only() {
+        assert_eq!(
+            replace_segment("https://github.com/new/name.git", "new", "old").as_deref(),
+            Some("https://github.com/old/name.git")
+        );
+        assert_eq!(
+            replace_segment("git@github.com:new/name-new", "new", "old").as_deref(),
+            Some("git@github.com:old/name-new")
+        );
+        assert_eq!(
+            replace_segment("https://github.com/owner/new", "new", "old").as_deref(),
+            Some("https://github.com/owner/old")
+        );
+        assert_eq!(
+            replace_segment("https://github.com/owner/renewed", "new", "old"),
+            None
+        );
+    }
+}
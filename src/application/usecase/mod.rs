@@ -1,22 +1,44 @@
 use crate::{
-    application::usecase::{clone::CloneUsecase, list::ListUsecase},
+    application::usecase::{
+        clone::CloneUsecase, doctor::DoctorUsecase, grep::GrepUsecase, list::ListUsecase,
+        misplaced::MisplacedUsecase, query::QueryUsecase, visit::VisitUsecase,
+        worktree::WorktreeUsecase,
+    },
     domain::port::Ports,
 };
 
 pub(crate) mod clone;
+pub(crate) mod doctor;
+pub(crate) mod grep;
 pub(crate) mod list;
+pub(crate) mod misplaced;
+pub(crate) mod query;
+pub(crate) mod visit;
+pub(crate) mod worktree;
 
 #[derive(Debug)]
 pub(crate) struct Usecases {
     clone: CloneUsecase,
+    doctor: DoctorUsecase,
+    grep: GrepUsecase,
     list: ListUsecase,
+    misplaced: MisplacedUsecase,
+    query: QueryUsecase,
+    visit: VisitUsecase,
+    worktree: WorktreeUsecase,
 }
 
 impl Usecases {
     pub(crate) fn new(ports: &Ports) -> Self {
         Self {
             clone: CloneUsecase::new(ports),
+            doctor: DoctorUsecase::new(ports),
+            grep: GrepUsecase::new(ports),
             list: ListUsecase::new(ports),
+            misplaced: MisplacedUsecase::new(ports),
+            query: QueryUsecase::new(ports),
+            visit: VisitUsecase::new(ports),
+            worktree: WorktreeUsecase::new(ports),
         }
     }
 
@@ -24,7 +46,31 @@ impl Usecases {
         &self.clone
     }
 
+    pub(crate) fn doctor(&self) -> &DoctorUsecase {
+        &self.doctor
+    }
+
+    pub(crate) fn grep(&self) -> &GrepUsecase {
+        &self.grep
+    }
+
     pub(crate) fn list(&self) -> &ListUsecase {
         &self.list
     }
+
+    pub(crate) fn misplaced(&self) -> &MisplacedUsecase {
+        &self.misplaced
+    }
+
+    pub(crate) fn query(&self) -> &QueryUsecase {
+        &self.query
+    }
+
+    pub(crate) fn visit(&self) -> &VisitUsecase {
+        &self.visit
+    }
+
+    pub(crate) fn worktree(&self) -> &WorktreeUsecase {
+        &self.worktree
+    }
 }
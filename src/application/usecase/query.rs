@@ -0,0 +1,34 @@
+use std::{collections::HashMap, path::Path, sync::Arc};
+
+use crate::domain::{
+    model::url_rewrite::UrlRewriteRule,
+    port::{Ports, git_config::GitConfig, ssh_config::SshConfig},
+};
+
+#[derive(Debug)]
+pub(crate) struct QueryUsecase {
+    git_config: Arc<dyn GitConfig>,
+    ssh_config: Arc<dyn SshConfig>,
+}
+
+impl QueryUsecase {
+    pub(crate) fn new(ports: &Ports) -> Self {
+        Self {
+            git_config: Arc::clone(&ports.git_config),
+            ssh_config: Arc::clone(&ports.ssh_config),
+        }
+    }
+
+    pub(crate) fn git_url_rewrites(
+        &self,
+    ) -> Result<Vec<UrlRewriteRule>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        self.git_config.url_rewrites()
+    }
+
+    pub(crate) fn ssh_host_aliases(
+        &self,
+        ssh_config_path: &Path,
+    ) -> Result<HashMap<String, String>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        self.ssh_config.host_aliases(ssh_config_path)
+    }
+}
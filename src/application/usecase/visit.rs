@@ -0,0 +1,198 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use chrono::{DateTime, Utc};
+
+use crate::domain::{
+    model::{
+        path_buf_pair::PathBufPair,
+        path_like::PathLike,
+        repo::Repo,
+        root::{CanonicalRoot, Root},
+        visit::{FrecencyScores, VISIT_RETENTION},
+    },
+    port::{
+        Ports,
+        path_canonicalizer::{PathCanonicalizer, PathCanonicalizerError},
+        repo_probe::{RepoProbe, RepoProbeError},
+        visit_store::VisitStore,
+    },
+};
+
+#[derive(Debug, Clone)]
+pub(crate) struct VisitContext {
+    pub(crate) now: DateTime<Utc>,
+    pub(crate) visit_history_path: PathBufPair,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum VisitUsecaseError {
+    #[error("repository not found: {}", path.display())]
+    RepoNotFound { path: PathBufPair },
+    #[error("failed to get canonical path of {}", path.display())]
+    CanonicalizePath {
+        path: PathBufPair,
+        #[source]
+        source: PathCanonicalizerError,
+    },
+    #[error("failed to inspect directory: {}", path.display())]
+    ProbeRepo {
+        path: PathBuf,
+        #[source]
+        source: RepoProbeError,
+    },
+    #[error(transparent)]
+    Backend(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
+}
+
+/// Repository that a visited directory belongs to.
+#[derive(Debug)]
+pub(crate) struct VisitedRepo {
+    pub(crate) root: CanonicalRoot,
+    pub(crate) canonical_path: PathBuf,
+}
+
+#[derive(Debug)]
+pub(crate) struct VisitUsecase {
+    path_canonicalizer: Arc<dyn PathCanonicalizer>,
+    repo_probe: Arc<dyn RepoProbe>,
+    visit_store: Arc<dyn VisitStore>,
+}
+
+impl VisitUsecase {
+    pub(crate) fn new(ports: &Ports) -> Self {
+        Self {
+            path_canonicalizer: Arc::clone(&ports.path_canonicalizer),
+            repo_probe: Arc::clone(&ports.repo_probe),
+            visit_store: Arc::clone(&ports.visit_store),
+        }
+    }
+
+    /// Records a visit to the repository containing `dir`.
+    ///
+    /// Directories outside the repositories under `roots` are ignored and
+    /// `None` is returned.
+    pub(crate) fn record_visit<I>(
+        &self,
+        roots: I,
+        dir: &dyn PathLike,
+        context: &VisitContext,
+    ) -> Result<Option<VisitedRepo>, VisitUsecaseError>
+    where
+        I: IntoIterator<Item = Root>,
+    {
+        let dir = self
+            .path_canonicalizer
+            .canonicalize(dir)
+            .map_err(|source| VisitUsecaseError::CanonicalizePath {
+                path: PathBufPair::new(dir),
+                source,
+            })?;
+        let Some(visited) = self.find_repo(roots, &dir)? else {
+            return Ok(None);
+        };
+
+        self.record(&visited.canonical_path, context)?;
+        Ok(Some(visited))
+    }
+
+    /// Records a visit to `repo` and returns its canonical path.
+    pub(crate) fn record_repo_visit(
+        &self,
+        repo: &Repo,
+        context: &VisitContext,
+    ) -> Result<PathBuf, VisitUsecaseError> {
+        let canonical_path =
+            self.path_canonicalizer
+                .canonicalize(repo.path())
+                .map_err(|source| match source {
+                    PathCanonicalizerError::PathNotFound { path } => {
+                        VisitUsecaseError::RepoNotFound { path }
+                    }
+                    source => VisitUsecaseError::CanonicalizePath {
+                        path: repo.path().clone(),
+                        source,
+                    },
+                })?;
+        self.repo_probe
+            .probe(&canonical_path)
+            .map_err(|source| match source {
+                RepoProbeError::NotARepo { .. } => VisitUsecaseError::RepoNotFound {
+                    path: repo.path().clone(),
+                },
+                source => VisitUsecaseError::ProbeRepo {
+                    path: canonical_path.clone(),
+                    source,
+                },
+            })?;
+
+        self.record(&canonical_path, context)?;
+        Ok(canonical_path)
+    }
+
+    /// Returns the frecency scores of the visited repositories.
+    pub(crate) fn frecency_scores(
+        &self,
+        context: &VisitContext,
+    ) -> Result<FrecencyScores, VisitUsecaseError> {
+        self.visit_store.load(&context.visit_history_path)?;
+        Ok(FrecencyScores::new(&self.visit_store.visits(), context.now))
+    }
+
+    fn record(
+        &self,
+        canonical_path: &Path,
+        context: &VisitContext,
+    ) -> Result<(), VisitUsecaseError> {
+        self.visit_store.record(
+            &context.visit_history_path,
+            canonical_path,
+            context.now,
+            context.now - VISIT_RETENTION,
+        )?;
+        Ok(())
+    }
+
+    /// Finds the repository containing `dir` under the innermost root.
+    fn find_repo<I>(&self, roots: I, dir: &Path) -> Result<Option<VisitedRepo>, VisitUsecaseError>
+    where
+        I: IntoIterator<Item = Root>,
+    {
+        let root = roots
+            .into_iter()
+            .filter_map(|root| {
+                // Missing roots cannot contain the directory.
+                let canonical_path = self.path_canonicalizer.canonicalize(root.path()).ok()?;
+                dir.starts_with(&canonical_path)
+                    .then(|| CanonicalRoot::new(root, canonical_path))
+            })
+            .max_by_key(|root| root.canonical_path().components().count());
+        let Some(root) = root else {
+            return Ok(None);
+        };
+
+        for path in dir.ancestors() {
+            if path == root.canonical_path() {
+                break;
+            }
+            match self.repo_probe.probe(&path.to_owned()) {
+                Ok(_) => {
+                    return Ok(Some(VisitedRepo {
+                        root,
+                        canonical_path: path.to_owned(),
+                    }));
+                }
+                Err(RepoProbeError::NotARepo { .. }) => {}
+                Err(source) => {
+                    return Err(VisitUsecaseError::ProbeRepo {
+                        path: path.to_owned(),
+                        source,
+                    });
+                }
+            }
+        }
+        Ok(None)
+    }
+}
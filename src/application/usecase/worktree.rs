@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use crate::{
+    application::support::workdir_guard::WorkdirGuard,
+    domain::{
+        model::{
+            path_like::PathLike as _,
+            query::Query,
+            repo::Repo,
+            root::Root,
+            template::Template,
+            worktree::{Worktree, WorktreePathTemplateContext},
+        },
+        port::{
+            Ports,
+            dir_editor::DirEditor,
+            repo_worktree::{RemoveWorktreeError, RepoWorktree, WorktreeInfo},
+        },
+    },
+};
+
+#[derive(Debug)]
+pub(crate) struct WorktreeUsecase {
+    dir_editor: Arc<dyn DirEditor>,
+    repo_worktree: Arc<dyn RepoWorktree>,
+}
+
+impl WorktreeUsecase {
+    pub(crate) fn new(ports: &Ports) -> Self {
+        Self {
+            dir_editor: Arc::clone(&ports.dir_editor),
+            repo_worktree: Arc::clone(&ports.repo_worktree),
+        }
+    }
+
+    /// Adds a linked worktree for `branch` of the repository specified by `query`.
+    ///
+    /// The worktree is placed under `root` at the path computed from `path_template`.
+    pub(crate) fn add_worktree(
+        &self,
+        root: &Root,
+        query: &Query,
+        branch: &str,
+        path_template: &Template<WorktreePathTemplateContext>,
+    ) -> Result<Worktree, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let repo = Repo::from_query(root, query, false)?;
+        let existing = self.repo_worktree.list_worktrees(repo.path())?;
+        let name = Worktree::unique_name_for_branch(
+            branch,
+            existing.iter().map(|worktree| worktree.name.as_str()),
+        );
+        let worktree = Worktree::from_template(root, &repo, branch, &name, path_template)?;
+        let worktree_path = worktree.path();
+
+        // git refuses to add a worktree to an existing directory, so only its
+        // parent is prepared here and removed again if adding fails.
+        let parent_path = worktree_path
+            .as_real_path()
+            .parent()
+            .expect("worktree path is a non-empty path under the root");
+        let dir_editor = Arc::clone(&self.dir_editor);
+        let mut workdir = WorkdirGuard::create(dir_editor, &parent_path)?;
+        self.repo_worktree
+            .add_worktree(repo.path(), worktree.name(), branch, worktree_path)?;
+        workdir.persist()?;
+
+        Ok(worktree)
+    }
+
+    /// Returns the linked worktrees of the repository specified by `query`.
+    pub(crate) fn list_worktrees(
+        &self,
+        root: &Root,
+        query: &Query,
+    ) -> Result<Vec<WorktreeInfo>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let repo = Repo::from_query(root, query, false)?;
+        self.repo_worktree.list_worktrees(repo.path())
+    }
+
+    /// Removes the linked worktree for `branch` of the repository specified by `query`.
+    pub(crate) fn remove_worktree(
+        &self,
+        root: &Root,
+        query: &Query,
+        branch: &str,
+        force: bool,
+    ) -> Result<(), RemoveWorktreeError> {
+        let repo = Repo::from_query(root, query, false)
+            .map_err(|err| RemoveWorktreeError::Backend(err.into()))?;
+        // The name may have a suffix added to avoid a collision, so the
+        // worktree is looked up by its branch.
+        let existing = self
+            .repo_worktree
+            .list_worktrees(repo.path())
+            .map_err(RemoveWorktreeError::Backend)?;
+        let name = existing
+            .into_iter()
+            .find(|worktree| worktree.branch.as_deref() == Some(branch))
+            .map_or_else(
+                || Worktree::name_for_branch(branch),
+                |worktree| worktree.name,
+            );
+        self.repo_worktree
+            .remove_worktree(repo.path(), &name, force)
+    }
+}
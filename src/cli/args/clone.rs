@@ -1,9 +1,31 @@
+use clap_complete::engine::{ArgValueCandidates, ArgValueCompleter};
+
+use crate::cli::complete;
+
 #[derive(Debug, Clone, Default, clap::Args)]
 pub(in crate::cli) struct CloneArgs {
     /// Name of the root under which the repository will be cloned
-    #[arg(long = "root")]
+    #[arg(long = "root", add = ArgValueCandidates::new(complete::root_names))]
     root_name: Option<String>,
 
+    /// Show which root the repository would be cloned into and why, without cloning
+    #[arg(long)]
+    explain: bool,
+
+    /// Add an `upstream` remote pointing to the given repository
+    ///
+    /// The repository is specified in the same format as the query.
+    /// The remote is configured but not fetched.
+    #[arg(long, value_name = "QUERY")]
+    upstream: Option<String>,
+
+    /// Add a remote pointing to the given repository (can be specified multiple times)
+    ///
+    /// The repository is specified in the same format as the query.
+    /// The remote is configured but not fetched.
+    #[arg(long = "remote", value_name = "NAME=QUERY")]
+    remotes: Vec<String>,
+
     /// Git repository to clone repository from
     ///
     /// Following formats are supported:
@@ -11,11 +33,16 @@ pub(in crate::cli) struct CloneArgs {
     /// * GitHub repository: `gifnksm/souko`
     /// * Abbreviated GitHub URL: `gh:gifnksm/souko`
     /// * Abbreviated GitLab URL: `gl:gifnksm/souko`
+    /// * Other forges: `cb:owner/repo` (Codeberg), `sr:~user/repo` (SourceHut),
+    ///   `bb:owner/repo` (Bitbucket), `azure:org/project/repo` (Azure DevOps)
+    /// * Self-hosted instances: `gitea+git.example.com:owner/repo`,
+    ///   `forgejo+git.example.com:owner/repo`, `gl+gitlab.example.com:group/repo`
     /// * HTTP(S) URL: `https://github.com/gifnksm/souko.git`
     /// * SSH URL: `ssh://git@github.com/gifnksm/souko.git`
     /// * Git URL: `git://github.com/gifnksm/souko.git`
     /// * scp-like: `git@github.com:gifnksm/souko.git`
-    #[arg(verbatim_doc_comment)]
+    /// * Local path: `/srv/git/souko.git`, `./souko`, `file:///srv/git/souko.git`
+    #[arg(verbatim_doc_comment, add = ArgValueCompleter::new(complete::queries))]
     query: String,
 }
 
@@ -24,6 +51,18 @@ impl CloneArgs {
         self.root_name.as_deref()
     }
 
+    pub(in crate::cli) fn explain(&self) -> bool {
+        self.explain
+    }
+
+    pub(in crate::cli) fn upstream(&self) -> Option<&str> {
+        self.upstream.as_deref()
+    }
+
+    pub(in crate::cli) fn remotes(&self) -> &[String] {
+        &self.remotes
+    }
+
     pub(in crate::cli) fn query(&self) -> &str {
         &self.query
     }
@@ -0,0 +1,135 @@
+use clap_complete::engine::ArgValueCandidates;
+
+use crate::cli::{complete, input::unresolved_path::UnresolvedPath};
+
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct ConfigArgs {
+    #[command(subcommand)]
+    subcommand: ConfigSubcommand,
+}
+
+impl ConfigArgs {
+    pub(in crate::cli) fn subcommand(&self) -> &ConfigSubcommand {
+        &self.subcommand
+    }
+}
+
+#[derive(Debug, Clone, clap::Subcommand)]
+pub(in crate::cli) enum ConfigSubcommand {
+    /// Write a configuration file with every setting commented out
+    Init(ConfigInitArgs),
+    /// Print the effective configuration and where each value comes from
+    Show,
+    /// Print the effective value of a key, such as `query.default_scheme`
+    Get(ConfigGetArgs),
+    /// Set the value of a key in the configuration file
+    ///
+    /// Comments and formatting of the file are kept.
+    Set(ConfigSetArgs),
+    /// Add or remove roots in the configuration file
+    Root(ConfigRootArgs),
+    /// Print the path of the configuration file
+    Path,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct ConfigInitArgs {
+    /// Overwrite the configuration file if it already exists
+    #[arg(long)]
+    force: bool,
+}
+
+impl ConfigInitArgs {
+    pub(in crate::cli) fn force(&self) -> bool {
+        self.force
+    }
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct ConfigGetArgs {
+    /// Dotted key, such as `root[0].path` or `query.scheme_alias.gh`
+    key: String,
+}
+
+impl ConfigGetArgs {
+    pub(in crate::cli) fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct ConfigSetArgs {
+    /// Dotted key, such as `root[0].path` or `query.scheme_alias.gh`
+    key: String,
+
+    /// New value
+    ///
+    /// The value is parsed as TOML (for example, `true` or `["a", "b"]`), and
+    /// used as a string if it is not valid TOML.
+    value: String,
+}
+
+impl ConfigSetArgs {
+    pub(in crate::cli) fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub(in crate::cli) fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct ConfigRootArgs {
+    #[command(subcommand)]
+    subcommand: ConfigRootSubcommand,
+}
+
+impl ConfigRootArgs {
+    pub(in crate::cli) fn subcommand(&self) -> &ConfigRootSubcommand {
+        &self.subcommand
+    }
+}
+
+#[derive(Debug, Clone, clap::Subcommand)]
+pub(in crate::cli) enum ConfigRootSubcommand {
+    /// Add a root to the configuration file
+    Add(ConfigRootAddArgs),
+    /// Remove a root from the configuration file
+    ///
+    /// Repositories under the root are left untouched.
+    Remove(ConfigRootRemoveArgs),
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct ConfigRootAddArgs {
+    /// Name of the root
+    name: String,
+
+    /// Path of the root directory
+    #[arg(value_hint = clap::ValueHint::DirPath)]
+    path: UnresolvedPath,
+}
+
+impl ConfigRootAddArgs {
+    pub(in crate::cli) fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(in crate::cli) fn path(&self) -> &UnresolvedPath {
+        &self.path
+    }
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct ConfigRootRemoveArgs {
+    /// Name of the root
+    #[arg(add = ArgValueCandidates::new(complete::root_names))]
+    name: String,
+}
+
+impl ConfigRootRemoveArgs {
+    pub(in crate::cli) fn name(&self) -> &str {
+        &self.name
+    }
+}
@@ -0,0 +1,15 @@
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct DoctorArgs {
+    /// Fix the problems that can be fixed automatically
+    ///
+    /// Missing root directories are created, and an unreadable repository
+    /// cache is reset. The configuration file is never modified.
+    #[arg(long)]
+    fix: bool,
+}
+
+impl DoctorArgs {
+    pub(in crate::cli) fn fix(&self) -> bool {
+        self.fix
+    }
+}
@@ -0,0 +1,71 @@
+use std::{ffi::OsString, num::NonZeroUsize};
+
+use clap_complete::engine::ArgValueCandidates;
+
+use crate::cli::{args::list::Duplicates, complete};
+
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct ExecArgs {
+    /// Run the command only in repositories under the specified root (repeatable)
+    #[arg(long = "root", add = ArgValueCandidates::new(complete::root_names))]
+    root_name: Option<Vec<String>>,
+
+    /// How to handle the same repository reached from more than one place
+    #[arg(long, value_enum, default_value_t)]
+    duplicates: Duplicates,
+
+    /// Number of commands to run in parallel
+    #[arg(long, short = 'j', default_value = "1")]
+    jobs: NonZeroUsize,
+
+    /// How to print the output of the commands
+    #[arg(long, value_enum, default_value_t)]
+    output: ExecOutput,
+
+    /// Stop starting commands after the first failure
+    #[arg(long)]
+    fail_fast: bool,
+
+    /// Command to run in each repository, with its arguments
+    ///
+    /// The command runs with the repository as the working directory.
+    /// Template variables of `souko list --template` are exported as
+    /// environment variables such as `SOUKO_REPO_RELATIVE_PATH`.
+    #[arg(required = true, trailing_var_arg = true, value_name = "COMMAND")]
+    command: Vec<OsString>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub(in crate::cli) enum ExecOutput {
+    /// Print each line as soon as it is output, prefixed with the repository
+    #[default]
+    Prefix,
+    /// Print the whole output of each repository together after a header
+    Group,
+}
+
+impl ExecArgs {
+    pub(in crate::cli) fn root_name(&self) -> Option<&[String]> {
+        self.root_name.as_deref()
+    }
+
+    pub(in crate::cli) fn duplicates(&self) -> Duplicates {
+        self.duplicates
+    }
+
+    pub(in crate::cli) fn jobs(&self) -> NonZeroUsize {
+        self.jobs
+    }
+
+    pub(in crate::cli) fn output(&self) -> ExecOutput {
+        self.output
+    }
+
+    pub(in crate::cli) fn fail_fast(&self) -> bool {
+        self.fail_fast
+    }
+
+    pub(in crate::cli) fn command(&self) -> &[OsString] {
+        &self.command
+    }
+}
@@ -0,0 +1,94 @@
+use std::num::NonZeroUsize;
+
+use clap_complete::engine::ArgValueCandidates;
+
+use crate::cli::{args::list::Duplicates, complete};
+
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct GrepArgs {
+    /// Search repositories only under the specified root (repeatable)
+    #[arg(long = "root", add = ArgValueCandidates::new(complete::root_names))]
+    root_name: Option<Vec<String>>,
+
+    /// How to handle the same repository reached from more than one place
+    #[arg(long, value_enum, default_value_t)]
+    duplicates: Duplicates,
+
+    /// Search files committed at HEAD instead of the working tree
+    #[arg(long)]
+    head: bool,
+
+    /// Also search untracked files not ignored by `.gitignore`
+    #[arg(long, conflicts_with = "head")]
+    untracked: bool,
+
+    /// Search case-insensitively
+    #[arg(long, short = 'i')]
+    ignore_case: bool,
+
+    /// Number of repositories searched in parallel [default: number of CPUs]
+    #[arg(long, short = 'j')]
+    jobs: Option<NonZeroUsize>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t, group = "output_format")]
+    format: GrepFormat,
+
+    /// Output matches as JSON (same as `--format json`)
+    #[arg(long, group = "output_format")]
+    json: bool,
+
+    /// Regular expression to search for
+    ///
+    /// The syntax is that of the `regex` crate.
+    pattern: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub(in crate::cli) enum GrepFormat {
+    /// One `path:line:text` line per match
+    #[default]
+    Line,
+    /// Matching lines grouped under the path of each file
+    Group,
+    /// A JSON document with every repository, file and matching line
+    Json,
+}
+
+impl GrepArgs {
+    pub(in crate::cli) fn root_name(&self) -> Option<&[String]> {
+        self.root_name.as_deref()
+    }
+
+    pub(in crate::cli) fn duplicates(&self) -> Duplicates {
+        self.duplicates
+    }
+
+    pub(in crate::cli) fn head(&self) -> bool {
+        self.head
+    }
+
+    pub(in crate::cli) fn untracked(&self) -> bool {
+        self.untracked
+    }
+
+    pub(in crate::cli) fn ignore_case(&self) -> bool {
+        self.ignore_case
+    }
+
+    pub(in crate::cli) fn jobs(&self) -> Option<NonZeroUsize> {
+        self.jobs
+    }
+
+    pub(in crate::cli) fn format(&self) -> GrepFormat {
+        if self.json {
+            GrepFormat::Json
+        } else {
+            self.format
+        }
+    }
+
+    pub(in crate::cli) fn pattern(&self) -> &str {
+        &self.pattern
+    }
+}
@@ -0,0 +1,25 @@
+use clap_complete::engine::ArgValueCandidates;
+
+use crate::cli::complete;
+
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct JumpArgs {
+    /// Jump only to repositories under the specified root (repeatable)
+    #[arg(long = "root", add = ArgValueCandidates::new(complete::root_names))]
+    root_name: Option<Vec<String>>,
+
+    /// Keywords that must appear in the repository path in order
+    ///
+    /// The last keyword must appear in the last component of the path.
+    keywords: Vec<String>,
+}
+
+impl JumpArgs {
+    pub(in crate::cli) fn root_name(&self) -> Option<&[String]> {
+        self.root_name.as_deref()
+    }
+
+    pub(in crate::cli) fn keywords(&self) -> &[String] {
+        &self.keywords
+    }
+}
@@ -1,47 +1,236 @@
-use color_eyre::eyre::Result;
+use clap_complete::engine::ArgValueCandidates;
+use color_eyre::eyre::{Result, bail};
 
-use crate::{cli::render::list::RepoListTemplateContext, domain::model::template::Template};
+use crate::{
+    cli::{complete, render::list::RepoListTemplateContext},
+    domain::model::template::Template,
+};
 
 #[derive(Debug, Clone, Default, clap::Args)]
 pub(in crate::cli) struct ListArgs {
     /// List repositories only under the specified root (repeatable)
-    #[arg(long = "root")]
+    #[arg(long = "root", add = ArgValueCandidates::new(complete::root_names))]
     root_name: Option<Vec<String>>,
 
+    /// How to handle the same repository reached from more than one place
+    #[arg(long, value_enum, default_value_t)]
+    duplicates: Duplicates,
+
+    /// Show linked worktrees right after their main repository
+    #[arg(long)]
+    group_worktrees: bool,
+
+    /// Order of the listed repositories
+    #[arg(long, value_enum, default_value_t)]
+    sort: Sort,
+
     #[command(flatten)]
     format: FormatArgs,
 }
 
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub(in crate::cli) enum Duplicates {
+    /// Show only the first occurrence and warn about the others
+    #[default]
+    Warn,
+    /// Show every occurrence
+    Show,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub(in crate::cli) enum Sort {
+    /// Sort by path in each root
+    #[default]
+    Path,
+    /// Sort by how frequently and recently repositories were visited
+    Frecency,
+}
+
 #[derive(Debug, Clone, Default, clap::Args)]
-#[group(id = "format", multiple = false)]
 pub(in crate::cli) struct FormatArgs {
-    /// Output repositories as JSON
-    #[arg(long)]
+    /// Output format
+    #[arg(long, value_enum, group = "output_format")]
+    format: Option<FormatKind>,
+    /// Output repositories as JSON (same as `--format json`)
+    #[arg(long, group = "output_format")]
     json: bool,
     /// Output each repository using a template string
-    #[arg(long)]
+    #[arg(long, group = "output_format")]
     template: Option<Template<RepoListTemplateContext>>,
+    /// Columns of `--format table|csv|tsv|ndjson` (comma-separated)
+    #[arg(long, value_enum, value_delimiter = ',')]
+    columns: Option<Vec<Column>>,
+    /// Do not print the header line of `--format table|csv|tsv`
+    #[arg(long)]
+    no_header: bool,
+    /// Terminate each repository with NUL instead of newline (default and `--template` output)
+    #[arg(long, short = '0')]
+    null: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(in crate::cli) enum FormatKind {
+    /// One repository path per line
+    Default,
+    /// A JSON document with every root and repository
+    Json,
+    /// One JSON object per line for each repository
+    Ndjson,
+    /// Aligned columns
+    Table,
+    /// Comma-separated values (RFC 4180, with CRLF line breaks)
+    Csv,
+    /// Tab-separated values, with `\t`, `\n`, `\r` and `\\` escaped
+    Tsv,
+}
+
+/// A field of a repository in the list output.
+///
+/// The names are the same as the `--template` variables, and are shared by the
+/// table, CSV, TSV, NDJSON and JSON output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[value(rename_all = "snake_case")]
+pub(in crate::cli) enum Column {
+    RootName,
+    RootDisplayPath,
+    RootRealPath,
+    RootCanonicalPath,
+    RepoRelativePath,
+    RepoDisplayPath,
+    RepoRealPath,
+    RepoCanonicalPath,
+    RepoKind,
+    RepoCommonDir,
+}
+
+impl Column {
+    const DEFAULT_TABLE_COLUMNS: &[Self] =
+        &[Self::RootName, Self::RepoRelativePath, Self::RepoKind];
+    pub(in crate::cli) const ALL: &[Self] = &[
+        Self::RootName,
+        Self::RootDisplayPath,
+        Self::RootRealPath,
+        Self::RootCanonicalPath,
+        Self::RepoRelativePath,
+        Self::RepoDisplayPath,
+        Self::RepoRealPath,
+        Self::RepoCanonicalPath,
+        Self::RepoKind,
+        Self::RepoCommonDir,
+    ];
+
+    /// Returns `true` if the field belongs to the root rather than the repository.
+    pub(in crate::cli) fn is_root(self) -> bool {
+        matches!(
+            self,
+            Self::RootName | Self::RootDisplayPath | Self::RootRealPath | Self::RootCanonicalPath
+        )
+    }
+
+    pub(in crate::cli) fn name(self) -> &'static str {
+        match self {
+            Self::RootName => "root_name",
+            Self::RootDisplayPath => "root_display_path",
+            Self::RootRealPath => "root_real_path",
+            Self::RootCanonicalPath => "root_canonical_path",
+            Self::RepoRelativePath => "repo_relative_path",
+            Self::RepoDisplayPath => "repo_display_path",
+            Self::RepoRealPath => "repo_real_path",
+            Self::RepoCanonicalPath => "repo_canonical_path",
+            Self::RepoKind => "repo_kind",
+            Self::RepoCommonDir => "repo_common_dir",
+        }
+    }
 }
 
 impl FormatArgs {
     fn validate(&self) -> Result<Format> {
-        let FormatArgs { json, template } = self;
-        if *json {
-            Ok(Format::Json)
-        } else if let Some(template) = template {
-            Ok(Format::Template(template.clone()))
+        let FormatArgs {
+            format,
+            json,
+            template,
+            columns,
+            no_header,
+            null,
+        } = self;
+        let terminator = if *null {
+            Terminator::Nul
+        } else {
+            Terminator::Newline
+        };
+        let table = |default_columns: &[Column]| TableFormat {
+            columns: columns.clone().unwrap_or_else(|| default_columns.to_vec()),
+            header: !no_header,
+        };
+        let kind = if *json {
+            FormatKind::Json
+        } else if template.is_some() {
+            FormatKind::Default
         } else {
-            Ok(Format::Default)
+            format.unwrap_or(FormatKind::Default)
+        };
+
+        if columns.is_some()
+            && !matches!(
+                kind,
+                FormatKind::Table | FormatKind::Csv | FormatKind::Tsv | FormatKind::Ndjson
+            )
+        {
+            bail!("`--columns` can only be used with `--format table|csv|tsv|ndjson`");
+        }
+        if *no_header && !matches!(kind, FormatKind::Table | FormatKind::Csv | FormatKind::Tsv) {
+            bail!("`--no-header` can only be used with `--format table|csv|tsv`");
+        }
+        if *null && kind != FormatKind::Default {
+            bail!("`--null` can only be used with the default format or `--template`");
         }
+
+        let format = match kind {
+            FormatKind::Default => match template {
+                Some(template) => Format::Template(template.clone(), terminator),
+                None => Format::Default(terminator),
+            },
+            FormatKind::Json => Format::Json,
+            FormatKind::Ndjson => Format::Ndjson(table(Column::ALL).columns),
+            FormatKind::Table => Format::Table(table(Column::DEFAULT_TABLE_COLUMNS)),
+            FormatKind::Csv => Format::Csv(table(Column::ALL)),
+            FormatKind::Tsv => Format::Tsv(table(Column::ALL)),
+        };
+        Ok(format)
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub(in crate::cli) enum Format {
-    #[default]
-    Default,
+    Default(Terminator),
     Json,
-    Template(Template<RepoListTemplateContext>),
+    Ndjson(Vec<Column>),
+    Template(Template<RepoListTemplateContext>, Terminator),
+    Table(TableFormat),
+    Csv(TableFormat),
+    Tsv(TableFormat),
+}
+
+/// The character written after each repository in line-oriented output.
+#[derive(Debug, Clone, Copy)]
+pub(in crate::cli) enum Terminator {
+    Newline,
+    Nul,
+}
+
+impl Terminator {
+    pub(in crate::cli) fn as_str(self) -> &'static str {
+        match self {
+            Self::Newline => "\n",
+            Self::Nul => "\0",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(in crate::cli) struct TableFormat {
+    pub(in crate::cli) columns: Vec<Column>,
+    pub(in crate::cli) header: bool,
 }
 
 impl ListArgs {
@@ -49,6 +238,21 @@ impl ListArgs {
         self.root_name.as_deref()
     }
 
+    pub(in crate::cli) fn duplicates(&self) -> Duplicates {
+        self.duplicates
+    }
+
+    pub(in crate::cli) fn group_worktrees(&self) -> bool {
+        self.group_worktrees
+    }
+
+    pub(in crate::cli) fn sort(&self) -> Result<Sort> {
+        if self.group_worktrees && self.sort == Sort::Frecency {
+            bail!("`--group-worktrees` cannot be used with `--sort frecency`");
+        }
+        Ok(self.sort)
+    }
+
     pub(in crate::cli) fn format(&self) -> Result<Format> {
         self.format.validate()
     }
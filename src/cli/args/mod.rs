@@ -5,14 +5,32 @@ use super::input::{app_param::AppParam, unresolved_path::UnresolvedPath};
 use crate::{
     app_dirs::AppDirs,
     cli::{
-        args::{clone::CloneArgs, list::ListArgs},
+        args::{
+            clone::CloneArgs, config::ConfigArgs, doctor::DoctorArgs, exec::ExecArgs,
+            grep::GrepArgs, jump::JumpArgs, list::ListArgs, path::PathArgs, pick::PickArgs,
+            query::QueryArgs, relocate::RelocateArgs, shell_init::ShellInitArgs, visit::VisitArgs,
+            worktree::WorktreeArgs,
+        },
+        color::ColorChoice,
         input::app_param::AppParamSource,
     },
 };
 
 pub(in crate::cli) mod clone;
+pub(in crate::cli) mod config;
+pub(in crate::cli) mod doctor;
+pub(in crate::cli) mod exec;
+pub(in crate::cli) mod grep;
+pub(in crate::cli) mod jump;
 pub(in crate::cli) mod list;
+pub(in crate::cli) mod path;
+pub(in crate::cli) mod pick;
+pub(in crate::cli) mod query;
+pub(in crate::cli) mod relocate;
+pub(in crate::cli) mod shell_init;
 mod verbosity;
+pub(in crate::cli) mod visit;
+pub(in crate::cli) mod worktree;
 
 #[derive(Debug, Clone, Default, clap::Parser)]
 #[command(author, version, about)]
@@ -47,6 +65,14 @@ pub(crate) struct GlobalArgs {
     /// Path to souko repository cache directory
     #[arg(long = "repo-cache", env = "SOUKO_REPO_CACHE")]
     repo_cache_path: Option<UnresolvedPath>,
+
+    /// Path to souko visit history file
+    #[arg(long = "visit-history", env = "SOUKO_VISIT_HISTORY")]
+    visit_history_path: Option<UnresolvedPath>,
+
+    /// When to use colors in the output
+    #[arg(long, value_enum, default_value_t, global = true)]
+    color: ColorChoice,
 }
 
 impl GlobalArgs {
@@ -54,6 +80,10 @@ impl GlobalArgs {
         self.verbosity.verbosity()
     }
 
+    pub(in crate::cli) fn color(&self) -> ColorChoice {
+        self.color
+    }
+
     pub(in crate::cli) fn config_path(&self, app_dirs: &AppDirs) -> AppParam<UnresolvedPath> {
         let (source, value) = self
             .config_path
@@ -81,12 +111,72 @@ impl GlobalArgs {
             });
         AppParam::new(source, value)
     }
+
+    pub(in crate::cli) fn visit_history_path(
+        &self,
+        app_dirs: &AppDirs,
+    ) -> AppParam<UnresolvedPath> {
+        let (source, value) = self
+            .visit_history_path
+            .as_ref()
+            .map(|path| (AppParamSource::CommandLineArgument, path.clone()))
+            .unwrap_or_else(|| {
+                (
+                    AppParamSource::ImplicitDefault,
+                    UnresolvedPath::new(app_dirs.data_local_dir().join("visits.json")),
+                )
+            });
+        AppParam::new(source, value)
+    }
 }
 
 #[derive(Debug, Clone, clap::Subcommand)]
 pub(in crate::cli) enum Subcommand {
     /// Clone remote repositories and put them into souko
     Clone(CloneArgs),
+    /// Inspect and edit the configuration file
+    Config(ConfigArgs),
+    /// Diagnose problems in the configuration and the environment
+    ///
+    /// Every problem found is reported at once, and `--fix` fixes the ones that
+    /// can be fixed automatically.
+    Doctor(DoctorArgs),
+    /// Run a command in every repository in souko
+    ///
+    /// Repositories are selected with the same options as `souko list`.
+    #[command(visible_alias = "foreach")]
+    Exec(ExecArgs),
+    /// Search files of every repository in souko for a regular expression
+    ///
+    /// Tracked files in the working tree are searched by default.
+    Grep(GrepArgs),
+    /// Print the path of the best-matching repository in souko by keywords
+    ///
+    /// Repositories are ranked by how frequently and recently they were visited.
+    Jump(JumpArgs),
     /// List repositories in souko
     List(ListArgs),
+    /// Print the path of a repository in souko and record a visit to it
+    Path(PathArgs),
+    /// Select a repository in souko interactively and print its path
+    Pick(PickArgs),
+    /// Inspect how queries are interpreted
+    Query(QueryArgs),
+    /// Move repositories in souko to the paths their origin remotes imply
+    ///
+    /// Misplaced repositories are only reported unless `--apply` is given.
+    /// With `--update-remote`, origin remotes are updated to match the current
+    /// paths instead.
+    Relocate(RelocateArgs),
+    /// Print shell integration code
+    ///
+    /// The code defines a `souko-cd` function, binds it to Ctrl-G, and records
+    /// visits to repositories in souko when changing directories.
+    ShellInit(ShellInitArgs),
+    /// Record a visit to the repository containing a directory
+    ///
+    /// Directories outside the repositories in souko are ignored.
+    Visit(VisitArgs),
+    /// Manage worktrees of repositories in souko
+    Worktree(WorktreeArgs),
 }
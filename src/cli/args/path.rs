@@ -0,0 +1,24 @@
+use clap_complete::engine::{ArgValueCandidates, ArgValueCompleter};
+
+use crate::cli::complete;
+
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct PathArgs {
+    /// Name of the root containing the repository
+    #[arg(long = "root", add = ArgValueCandidates::new(complete::root_names))]
+    root_name: Option<String>,
+
+    /// Repository to print the path of, in the same format as `souko clone`
+    #[arg(add = ArgValueCompleter::new(complete::queries))]
+    query: String,
+}
+
+impl PathArgs {
+    pub(in crate::cli) fn root_name(&self) -> Option<&str> {
+        self.root_name.as_deref()
+    }
+
+    pub(in crate::cli) fn query(&self) -> &str {
+        &self.query
+    }
+}
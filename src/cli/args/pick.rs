@@ -0,0 +1,35 @@
+use clap_complete::engine::ArgValueCandidates;
+
+use crate::{
+    cli::{complete, render::list::RepoListTemplateContext},
+    domain::model::template::Template,
+};
+
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct PickArgs {
+    /// Pick repositories only under the specified root (repeatable)
+    #[arg(long = "root", add = ArgValueCandidates::new(complete::root_names))]
+    root_name: Option<Vec<String>>,
+
+    /// Label of each repository to match against, in the same format as `souko list --template`
+    #[arg(long, default_value = "{root_name} {repo_relative_path}")]
+    template: Template<RepoListTemplateContext>,
+
+    /// Initial search query
+    #[arg(long, default_value = "")]
+    query: String,
+}
+
+impl PickArgs {
+    pub(in crate::cli) fn root_name(&self) -> Option<&[String]> {
+        self.root_name.as_deref()
+    }
+
+    pub(in crate::cli) fn template(&self) -> &Template<RepoListTemplateContext> {
+        &self.template
+    }
+
+    pub(in crate::cli) fn query(&self) -> &str {
+        &self.query
+    }
+}
@@ -0,0 +1,42 @@
+use clap_complete::engine::ArgValueCompleter;
+
+use crate::cli::complete;
+
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct QueryArgs {
+    #[command(subcommand)]
+    subcommand: QuerySubcommand,
+}
+
+impl QueryArgs {
+    pub(in crate::cli) fn subcommand(&self) -> &QuerySubcommand {
+        &self.subcommand
+    }
+}
+
+#[derive(Debug, Clone, clap::Subcommand)]
+pub(in crate::cli) enum QuerySubcommand {
+    /// Show how a query is expanded into a URL and where it is placed in each root
+    Explain(QueryExplainArgs),
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct QueryExplainArgs {
+    /// Query to explain, in the same format as `souko clone`
+    #[arg(add = ArgValueCompleter::new(complete::queries))]
+    query: String,
+
+    /// Output the explanation as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+impl QueryExplainArgs {
+    pub(in crate::cli) fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub(in crate::cli) fn json(&self) -> bool {
+        self.json
+    }
+}
@@ -0,0 +1,34 @@
+use clap_complete::engine::ArgValueCandidates;
+
+use crate::cli::complete;
+
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct RelocateArgs {
+    /// Check repositories only under the specified root (repeatable)
+    #[arg(long = "root", add = ArgValueCandidates::new(complete::root_names))]
+    root_name: Option<Vec<String>>,
+
+    /// Update the origin remote to match the path instead of moving the repository
+    #[arg(long)]
+    update_remote: bool,
+
+    /// Actually move repositories or update remotes
+    ///
+    /// Without this option, misplaced repositories are only reported.
+    #[arg(long)]
+    apply: bool,
+}
+
+impl RelocateArgs {
+    pub(in crate::cli) fn root_name(&self) -> Option<&[String]> {
+        self.root_name.as_deref()
+    }
+
+    pub(in crate::cli) fn update_remote(&self) -> bool {
+        self.update_remote
+    }
+
+    pub(in crate::cli) fn apply(&self) -> bool {
+        self.apply
+    }
+}
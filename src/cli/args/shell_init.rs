@@ -0,0 +1,21 @@
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct ShellInitArgs {
+    /// Shell to print the integration code for
+    shell: Shell,
+}
+
+impl ShellInitArgs {
+    pub(in crate::cli) fn shell(&self) -> Shell {
+        self.shell
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(in crate::cli) enum Shell {
+    Bash,
+    Elvish,
+    Fish,
+    Nushell,
+    Powershell,
+    Zsh,
+}
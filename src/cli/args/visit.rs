@@ -0,0 +1,14 @@
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct VisitArgs {
+    /// Directory to record a visit to
+    #[arg(default_value = ".")]
+    path: PathBuf,
+}
+
+impl VisitArgs {
+    pub(in crate::cli) fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
@@ -0,0 +1,87 @@
+use clap_complete::engine::{ArgValueCandidates, ArgValueCompleter};
+
+use crate::cli::complete;
+
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct WorktreeArgs {
+    #[command(subcommand)]
+    subcommand: WorktreeSubcommand,
+}
+
+impl WorktreeArgs {
+    pub(in crate::cli) fn subcommand(&self) -> &WorktreeSubcommand {
+        &self.subcommand
+    }
+}
+
+#[derive(Debug, Clone, clap::Subcommand)]
+pub(in crate::cli) enum WorktreeSubcommand {
+    /// Create a worktree checking out the branch
+    ///
+    /// The branch is created from `origin/<BRANCH>` or `HEAD` if it does not exist.
+    Add(WorktreeBranchArgs),
+    /// List worktrees of the repository
+    List(WorktreeRepoArgs),
+    /// Remove the worktree of the branch
+    Remove(WorktreeRemoveArgs),
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct WorktreeRepoArgs {
+    /// Name of the root containing the repository
+    #[arg(long = "root", add = ArgValueCandidates::new(complete::root_names))]
+    root_name: Option<String>,
+
+    /// Repository to operate on, in the same format as `souko clone`
+    #[arg(add = ArgValueCompleter::new(complete::queries))]
+    query: String,
+}
+
+impl WorktreeRepoArgs {
+    pub(in crate::cli) fn root_name(&self) -> Option<&str> {
+        self.root_name.as_deref()
+    }
+
+    pub(in crate::cli) fn query(&self) -> &str {
+        &self.query
+    }
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct WorktreeBranchArgs {
+    #[command(flatten)]
+    repo: WorktreeRepoArgs,
+
+    /// Branch to check out in the worktree
+    branch: String,
+}
+
+impl WorktreeBranchArgs {
+    pub(in crate::cli) fn repo(&self) -> &WorktreeRepoArgs {
+        &self.repo
+    }
+
+    pub(in crate::cli) fn branch(&self) -> &str {
+        &self.branch
+    }
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub(in crate::cli) struct WorktreeRemoveArgs {
+    #[command(flatten)]
+    target: WorktreeBranchArgs,
+
+    /// Remove the worktree even if it is locked or has uncommitted changes
+    #[arg(long, short = 'f')]
+    force: bool,
+}
+
+impl WorktreeRemoveArgs {
+    pub(in crate::cli) fn target(&self) -> &WorktreeBranchArgs {
+        &self.target
+    }
+
+    pub(in crate::cli) fn force(&self) -> bool {
+        self.force
+    }
+}
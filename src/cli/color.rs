@@ -0,0 +1,221 @@
+use std::{
+    env,
+    ffi::OsString,
+    fmt::{self, Display},
+    io::{self, IsTerminal as _},
+    sync::OnceLock,
+};
+
+use serde::Serialize;
+
+/// When to use colors in the output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum ColorChoice {
+    /// Use colors if the output is a terminal, honoring `NO_COLOR` and `CLICOLOR_FORCE`
+    #[default]
+    Auto,
+    /// Always use colors
+    Always,
+    /// Never use colors
+    Never,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ColorConfig {
+    stdout: bool,
+    stderr: bool,
+}
+
+static COLOR_CONFIG: OnceLock<ColorConfig> = OnceLock::new();
+
+/// Decides whether stdout and stderr are colored.
+///
+/// Output is not colored until this is called.
+pub(in crate::cli) fn init(choice: ColorChoice) {
+    let config = ColorConfig {
+        stdout: resolve(choice, |name| env::var_os(name), io::stdout().is_terminal()),
+        stderr: resolve(choice, |name| env::var_os(name), io::stderr().is_terminal()),
+    };
+    let _ = COLOR_CONFIG.set(config);
+}
+
+pub(in crate::cli) fn stdout_enabled() -> bool {
+    COLOR_CONFIG.get().is_some_and(|config| config.stdout)
+}
+
+pub(in crate::cli) fn stderr_enabled() -> bool {
+    COLOR_CONFIG.get().is_some_and(|config| config.stderr)
+}
+
+/// Resolves `choice` following <https://no-color.org/> and <https://bixense.com/clicolors/>.
+fn resolve(choice: ColorChoice, var: impl Fn(&str) -> Option<OsString>, is_terminal: bool) -> bool {
+    let is_set = |name: &str| var(name).is_some_and(|value| !value.is_empty() && value != "0");
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            if var("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+                false
+            } else if is_set("CLICOLOR_FORCE") {
+                true
+            } else {
+                is_terminal && var("CLICOLOR").is_none_or(|value| value != "0")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(in crate::cli) enum Style {
+    Bold,
+    Dim,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    BoldGreen,
+    BoldYellow,
+}
+
+impl Style {
+    const RESET: &str = "\x1b[0m";
+
+    fn escape(self) -> &'static str {
+        match self {
+            Self::Bold => "\x1b[1m",
+            Self::Dim => "\x1b[2m",
+            Self::Red => "\x1b[31m",
+            Self::Green => "\x1b[32m",
+            Self::Yellow => "\x1b[33m",
+            Self::Blue => "\x1b[34m",
+            Self::Magenta => "\x1b[35m",
+            Self::Cyan => "\x1b[36m",
+            Self::BoldGreen => "\x1b[1;32m",
+            Self::BoldYellow => "\x1b[1;33m",
+        }
+    }
+
+    /// Returns `value` wrapped in this style if `enabled` is `true`.
+    pub(in crate::cli) fn paint<T>(self, enabled: bool, value: T) -> Painted<T> {
+        Painted {
+            style: enabled.then_some(self),
+            value,
+        }
+    }
+}
+
+pub(in crate::cli) struct Painted<T> {
+    style: Option<Style>,
+    value: T,
+}
+
+impl<T> Display for Painted<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.style {
+            Some(style) => write!(f, "{}{}{}", style.escape(), self.value, Style::RESET),
+            None => self.value.fmt(f),
+        }
+    }
+}
+
+/// `{color.*}` variables of templates, which expand to escape sequences when colors are enabled.
+#[derive(Debug, Clone, Default, Serialize)]
+pub(in crate::cli) struct TemplateColors {
+    #[serde(rename = "color.reset")]
+    reset: String,
+    #[serde(rename = "color.bold")]
+    bold: String,
+    #[serde(rename = "color.dim")]
+    dim: String,
+    #[serde(rename = "color.red")]
+    red: String,
+    #[serde(rename = "color.green")]
+    green: String,
+    #[serde(rename = "color.yellow")]
+    yellow: String,
+    #[serde(rename = "color.blue")]
+    blue: String,
+    #[serde(rename = "color.magenta")]
+    magenta: String,
+    #[serde(rename = "color.cyan")]
+    cyan: String,
+}
+
+impl TemplateColors {
+    pub(in crate::cli) fn new(enabled: bool) -> Self {
+        if !enabled {
+            return Self::default();
+        }
+        Self {
+            reset: Style::RESET.to_owned(),
+            bold: Style::Bold.escape().to_owned(),
+            dim: Style::Dim.escape().to_owned(),
+            red: Style::Red.escape().to_owned(),
+            green: Style::Green.escape().to_owned(),
+            yellow: Style::Yellow.escape().to_owned(),
+            blue: Style::Blue.escape().to_owned(),
+            magenta: Style::Magenta.escape().to_owned(),
+            cyan: Style::Cyan.escape().to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn resolve_with(choice: ColorChoice, vars: &[(&str, &str)], is_terminal: bool) -> bool {
+        let vars = vars
+            .iter()
+            .map(|(name, value)| (*name, OsString::from(value)))
+            .collect::<HashMap<_, _>>();
+        resolve(choice, |name| vars.get(name).cloned(), is_terminal)
+    }
+
+    #[test]
+    fn resolve_color_choice() {
+        assert!(resolve_with(ColorChoice::Auto, &[], true));
+        assert!(!resolve_with(ColorChoice::Auto, &[], false));
+        assert!(!resolve_with(ColorChoice::Auto, &[("NO_COLOR", "1")], true));
+        assert!(resolve_with(ColorChoice::Auto, &[("NO_COLOR", "")], true));
+        assert!(resolve_with(
+            ColorChoice::Auto,
+            &[("CLICOLOR_FORCE", "1")],
+            false
+        ));
+        assert!(!resolve_with(
+            ColorChoice::Auto,
+            &[("CLICOLOR_FORCE", "0")],
+            false
+        ));
+        assert!(!resolve_with(
+            ColorChoice::Auto,
+            &[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")],
+            true
+        ));
+        assert!(!resolve_with(ColorChoice::Auto, &[("CLICOLOR", "0")], true));
+        assert!(resolve_with(
+            ColorChoice::Always,
+            &[("NO_COLOR", "1")],
+            false
+        ));
+        assert!(!resolve_with(
+            ColorChoice::Never,
+            &[("CLICOLOR_FORCE", "1")],
+            true
+        ));
+    }
+
+    #[test]
+    fn paint_only_when_enabled() {
+        assert_eq!(Style::Red.paint(true, "x").to_string(), "\x1b[31mx\x1b[0m");
+        assert_eq!(Style::Red.paint(false, "x").to_string(), "x");
+    }
+}
@@ -1,18 +1,46 @@
+use std::iter;
+
 use color_eyre::eyre::{Result, WrapErr as _, eyre};
 
 use crate::{
+    application::usecase::clone,
     cli::{
         context::{clone::CloneContext, global::GlobalContext},
         message,
     },
-    domain::model::path_like::PathLike as _,
+    domain::model::{path_like::PathLike as _, repo::Repo},
 };
 
 pub(super) fn dispatch(global_ctx: &GlobalContext, clone_ctx: &CloneContext) -> Result<()> {
     let root_context = clone_ctx.root_context();
     let query = clone_ctx.query();
+    let options = clone_ctx.options();
 
-    let bare = false;
+    if clone_ctx.explain() {
+        println!("query: {}", query.original_query());
+        println!("url: {}", query.url());
+        println!(
+            "root: {} ({})",
+            root_context.value().name(),
+            root_context.value().path().display()
+        );
+        println!("reason: {}", clone_ctx.selection_reason());
+        let repo = Repo::from_query(root_context.value().root(), query, options.bare)
+            .wrap_err("invalid repository path")?;
+        println!("path: {}", repo.relative_path().display());
+        let (origin, extra_remotes) = clone::remotes(query, options);
+        for remote in iter::once(&origin).chain(&extra_remotes) {
+            match remote.push_url() {
+                Some(push_url) => println!(
+                    "remote: {} {} (push: {push_url})",
+                    remote.name(),
+                    remote.url()
+                ),
+                None => println!("remote: {} {}", remote.name(), remote.url()),
+            }
+        }
+        return Ok(());
+    }
 
     message::info!(
         "cloning {} into {}",
@@ -23,7 +51,7 @@ pub(super) fn dispatch(global_ctx: &GlobalContext, clone_ctx: &CloneContext) ->
     global_ctx
         .usecases()
         .clone()
-        .clone_repo(root_context.value().root(), query, bare)
+        .clone_repo(root_context.value().root(), query, options)
         .map_err(|e| eyre!(e))
         .wrap_err("failed to clone repository")?;
 
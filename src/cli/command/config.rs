@@ -0,0 +1,669 @@
+use color_eyre::eyre::{Result, WrapErr as _, bail, eyre};
+use toml_edit::{ArrayOfTables, DocumentMut, InlineTable, Item, Table, Value};
+
+use crate::{
+    app_dirs::AppDirs,
+    cli::{
+        args::config::{
+            ConfigGetArgs, ConfigInitArgs, ConfigRootAddArgs, ConfigRootRemoveArgs,
+            ConfigRootSubcommand, ConfigSetArgs, ConfigSubcommand,
+        },
+        color::{self, Style},
+        config::{self, Config, ConfigKey, DEFAULT_ROOT_NAME, KeySegment, RootConfig},
+        context::{
+            app::AppContext,
+            config::ConfigContext,
+            root::{self, RootContextMap},
+            route::RouteContext,
+        },
+        input::{
+            app_param::{AppParam, AppParamSource},
+            unresolved_path::UnresolvedPath,
+        },
+        message,
+    },
+    domain::model::{path_buf_pair::PathBufPair, path_like::PathLike as _},
+    util::file,
+};
+
+const NAME: &str = "configuration file";
+
+/// Template written by `souko config init`.
+///
+/// Settings are commented out with `# ` so that uncommenting them gives a valid
+/// configuration, and explanations are written in sentences that do not look
+/// like settings.
+const TEMPLATE: &str = r#"# Configuration file of souko.
+#
+# Every setting below is commented out, and shows its default value or an
+# example. Run `souko config show` to see the effective configuration.
+
+# Directories to put repositories in.
+#
+# The `default` root is used unless `--root` is given or a `[[route]]` entry
+# matches. It exists even if it is not listed here.
+#
+# [[root]]
+# name = "default"
+# path = {root_path}
+#
+# Path of repositories under the root, `<host>[:<port>]/<path>` by default.
+# layout = "{owner}/{repo}"
+#
+# Directory for repositories cloned from local paths.
+# local_prefix = "local"
+#
+# Options of `souko list`.
+# visit_hidden_dirs = false
+# visit_repo_subdirs = false
+# include_bare_repo = false
+# include_linked_worktrees = true
+# follow_symlinks = false
+
+# Rules choosing the root when `--root` is not given.
+#
+# [[route]]
+# root = "default"
+# host = "github.com"
+
+# [query]
+# Scheme used for queries without a scheme, such as `owner/repo`.
+# default_scheme = "gh"
+#
+# Honor `url.<base>.insteadOf` in the git configuration.
+# use_git_url_rewrites = false
+#
+# Read host aliases from `~/.ssh/config`.
+# use_ssh_config = false
+
+# [query.scheme_alias]
+# gh = "github"
+
+# [query.custom_scheme]
+# github = "https://github.com/{path}.git"
+
+# Remotes added by a custom scheme, expanded like the custom scheme.
+#
+# [query.custom_scheme_remote.fork]
+# upstream = "gh:{path}"
+
+# [query.host_alias]
+# work-gh = "github.com"
+
+# [query.url_rewrite."ssh://git@github.com/corp/"]
+# instead_of = ["https://github.com/corp/"]
+# push_instead_of = []
+
+# [query.normalize]
+# lowercase_host = false
+# lowercase_path = false
+# strip_git_suffix = false
+# remove_userinfo = false
+
+# [clone]
+# Push URL of `origin` added by `souko clone`, the fetch URL by default.
+# push_url_template = "ssh://git@{host}/{path}.git"
+
+# [worktree]
+# Path of worktrees created by `souko worktree add`, relative to the root.
+# path_template = "{repo_relative_path}@{branch_slug}"
+"#;
+
+pub(super) fn dispatch(app_ctx: &AppContext, config_ctx: &ConfigContext) -> Result<()> {
+    let config_path = app_ctx.config_path();
+    let app_dirs = app_ctx.app_dirs();
+    match config_ctx.subcommand() {
+        ConfigSubcommand::Init(init_args) => init(config_path, init_args, app_dirs),
+        ConfigSubcommand::Show => show(app_ctx),
+        ConfigSubcommand::Get(get_args) => get(config_path, get_args, app_dirs),
+        ConfigSubcommand::Set(set_args) => set(config_path, set_args, app_dirs),
+        ConfigSubcommand::Root(root_args) => match root_args.subcommand() {
+            ConfigRootSubcommand::Add(add_args) => root_add(config_path, add_args, app_dirs),
+            ConfigRootSubcommand::Remove(remove_args) => {
+                root_remove(config_path, remove_args, app_dirs)
+            }
+        },
+        ConfigSubcommand::Path => {
+            println!("{}", config_path.value().as_real_path().display());
+            Ok(())
+        }
+    }
+}
+
+fn init(
+    config_path: &AppParam<PathBufPair>,
+    init_args: &ConfigInitArgs,
+    app_dirs: &AppDirs,
+) -> Result<()> {
+    let path = config_path.value();
+    if path.as_real_path().exists() && !init_args.force() {
+        bail!(
+            "configuration file already exists: {} (pass `--force` to overwrite it)",
+            path.display()
+        );
+    }
+    let doc = template(app_dirs).parse::<DocumentMut>()?;
+    file::store_toml_document(NAME, path, &doc)?;
+    message::info!("wrote configuration file: {}", path.display());
+    Ok(())
+}
+
+fn template(app_dirs: &AppDirs) -> String {
+    let root_path = root::default_path(app_dirs);
+    let root_path = Value::from(root_path.as_display_path().display().to_string());
+    TEMPLATE.replace("{root_path}", &root_path.to_string())
+}
+
+fn show(app_ctx: &AppContext) -> Result<()> {
+    let config_path = app_ctx.config_path();
+    let app_dirs = app_ctx.app_dirs();
+    let doc = load_document(config_path)?;
+    let effective = effective_config(config_path, doc.as_ref(), app_dirs)?;
+    let written = doc.map(document_value);
+    let color = color::stdout_enabled();
+
+    let paths = [
+        ("config", config_path),
+        ("repo-cache", app_ctx.repo_cache_path()),
+        ("visit-history", app_ctx.visit_history_path()),
+    ];
+    for (name, path) in paths {
+        let line = format!(
+            "# {name}: {} ({})",
+            path.value().display(),
+            source_label(path.source())
+        );
+        println!("{}", Style::Dim.paint(color, line));
+    }
+
+    for (key, value) in flatten(&effective, ConfigKey::default()) {
+        let source = match written.as_ref().and_then(|written| lookup(written, &key)) {
+            Some(_) => AppParamSource::ConfigurationFile {
+                path: config_path.value().clone(),
+            },
+            None => AppParamSource::ImplicitDefault,
+        };
+        let label = format!("# {}", source_label(&source));
+        println!("{key} = {value}  {}", Style::Dim.paint(color, label));
+    }
+    Ok(())
+}
+
+fn source_label(source: &AppParamSource) -> &'static str {
+    match source {
+        AppParamSource::CommandLineArgument => "command line",
+        AppParamSource::ConfigurationFile { .. } => "config file",
+        AppParamSource::ImplicitDefault => "default",
+    }
+}
+
+fn get(
+    config_path: &AppParam<PathBufPair>,
+    get_args: &ConfigGetArgs,
+    app_dirs: &AppDirs,
+) -> Result<()> {
+    let key = parse_key(get_args.key())?;
+    let doc = load_document(config_path)?;
+    let effective = effective_config(config_path, doc.as_ref(), app_dirs)?;
+    let Some(value) = lookup(&effective, &key) else {
+        bail!("`{key}` is not set");
+    };
+    match flatten(value, key.clone()).as_slice() {
+        [(leaf, Value::String(s))] if *leaf == key => println!("{}", s.value()),
+        [(leaf, value)] if *leaf == key => println!("{value}"),
+        entries => {
+            for (key, value) in entries {
+                println!("{key} = {value}");
+            }
+        }
+    }
+    Ok(())
+}
+
+fn set(
+    config_path: &AppParam<PathBufPair>,
+    set_args: &ConfigSetArgs,
+    app_dirs: &AppDirs,
+) -> Result<()> {
+    let key = parse_key(set_args.key())?;
+    let mut doc = load_document_or_default(config_path)?;
+
+    // A value that is not valid TOML, such as `~/src` or `gl`, is most likely
+    // meant to be a string.
+    let value = set_args
+        .value()
+        .parse::<Value>()
+        .unwrap_or_else(|_| Value::from(set_args.value()));
+    set_value(&mut doc, &key, value)
+        .and_then(|()| validate(config_path, &doc, app_dirs))
+        .wrap_err_with(|| format!("failed to set `{key}`"))?;
+    file::store_toml_document(NAME, config_path.value(), &doc)?;
+    Ok(())
+}
+
+fn root_add(
+    config_path: &AppParam<PathBufPair>,
+    add_args: &ConfigRootAddArgs,
+    app_dirs: &AppDirs,
+) -> Result<()> {
+    let name = add_args.name();
+    let path = add_args
+        .path()
+        .normalize(&AppParamSource::CommandLineArgument, app_dirs);
+    let mut doc = load_document_or_default(config_path)?;
+    if root_index(&doc, name).is_some() {
+        bail!("root `{name}` already exists in config file");
+    }
+
+    let mut root = InlineTable::new();
+    root.insert("name", Value::from(name));
+    root.insert(
+        "path",
+        Value::from(path.as_display_path().display().to_string()),
+    );
+    match doc
+        .entry("root")
+        .or_insert_with(|| Item::ArrayOfTables(ArrayOfTables::new()))
+    {
+        Item::ArrayOfTables(roots) => roots.push(root.into_table()),
+        Item::Value(Value::Array(roots)) => roots.push(root),
+        _ => bail!("`root` in config file is not an array"),
+    }
+    validate(config_path, &doc, app_dirs)?;
+    file::store_toml_document(NAME, config_path.value(), &doc)?;
+    message::info!("added root `{name}`: {}", path.display());
+    Ok(())
+}
+
+fn root_remove(
+    config_path: &AppParam<PathBufPair>,
+    remove_args: &ConfigRootRemoveArgs,
+    app_dirs: &AppDirs,
+) -> Result<()> {
+    let name = remove_args.name();
+    let mut doc = load_document_or_default(config_path)?;
+    let Some(index) = root_index(&doc, name) else {
+        bail!("root `{name}` not found in config file");
+    };
+    let config = load_config(config_path, Some(&doc))?;
+    if config.routes.iter().any(|route| route.root == name) {
+        bail!("root `{name}` is used by a `[[route]]` entry in config file");
+    }
+    let is_empty = match &mut doc["root"] {
+        Item::ArrayOfTables(roots) => {
+            roots.remove(index);
+            roots.is_empty()
+        }
+        Item::Value(Value::Array(roots)) => {
+            roots.remove(index);
+            roots.is_empty()
+        }
+        _ => unreachable!("root index is only found in arrays"),
+    };
+    if is_empty {
+        doc.remove("root");
+    }
+    validate(config_path, &doc, app_dirs)?;
+    file::store_toml_document(NAME, config_path.value(), &doc)?;
+    message::info!("removed root `{name}`");
+    Ok(())
+}
+
+fn root_index(doc: &DocumentMut, name: &str) -> Option<usize> {
+    let has_name =
+        |root: &dyn toml_edit::TableLike| root.get("name").and_then(Item::as_str) == Some(name);
+    match doc.get("root")? {
+        Item::ArrayOfTables(roots) => roots.iter().position(|root| has_name(root)),
+        Item::Value(Value::Array(roots)) => roots
+            .iter()
+            .position(|root| root.as_inline_table().is_some_and(|root| has_name(root))),
+        _ => None,
+    }
+}
+
+fn parse_key(key: &str) -> Result<ConfigKey> {
+    let key = key.parse::<ConfigKey>()?;
+    if !key.is_known() {
+        bail!("unknown key `{key}`");
+    }
+    Ok(key)
+}
+
+fn load_document(config_path: &AppParam<PathBufPair>) -> Result<Option<DocumentMut>> {
+    let path = config_path.value();
+    match file::load_toml_document(NAME, path)? {
+        Some(doc) => Ok(Some(doc)),
+        None if config_path.source().is_implicit_default() => Ok(None),
+        None => bail!("config file not found: {}", path.display()),
+    }
+}
+
+/// Loads the configuration document to edit, which is created if missing.
+fn load_document_or_default(config_path: &AppParam<PathBufPair>) -> Result<DocumentMut> {
+    Ok(file::load_toml_document(NAME, config_path.value())?.unwrap_or_default())
+}
+
+fn load_config(config_path: &AppParam<PathBufPair>, doc: Option<&DocumentMut>) -> Result<Config> {
+    let Some(doc) = doc else {
+        return Ok(Config::default());
+    };
+    config::from_document(doc).wrap_err_with(|| {
+        format!(
+            "failed to parse configuration file: {}",
+            config_path.value().display()
+        )
+    })
+}
+
+/// Checks that the edited configuration is loaded by souko without errors.
+fn validate(
+    config_path: &AppParam<PathBufPair>,
+    doc: &DocumentMut,
+    app_dirs: &AppDirs,
+) -> Result<()> {
+    let config = load_config(config_path, Some(doc))?;
+    let root_map = RootContextMap::new(config_path.value(), &config.roots, app_dirs);
+    RouteContext::from_config(&config.routes, &root_map)?;
+    Ok(())
+}
+
+/// Returns the effective configuration as a TOML value.
+///
+/// Default values are filled in, including the implicit `default` root and
+/// the resolved paths of the roots.
+fn effective_config(
+    config_path: &AppParam<PathBufPair>,
+    doc: Option<&DocumentMut>,
+    app_dirs: &AppDirs,
+) -> Result<Value> {
+    let mut config = load_config(config_path, doc)?;
+    let root_map = RootContextMap::new(config_path.value(), &config.roots, app_dirs);
+    if !config
+        .roots
+        .iter()
+        .any(|root| root.name == DEFAULT_ROOT_NAME)
+    {
+        config.roots.push(RootConfig::default_root());
+    }
+    for root in &mut config.roots {
+        let path = root_map
+            .root_by_name_or_err(&root.name)?
+            .value()
+            .root()
+            .path();
+        root.path = Some(UnresolvedPath::new(path.as_display_path().to_owned()));
+    }
+    let doc = toml_edit::ser::to_document(&config)
+        .map_err(|e| eyre!(e))
+        .wrap_err("failed to serialize configuration")?;
+    Ok(document_value(doc))
+}
+
+fn document_value(doc: DocumentMut) -> Value {
+    Value::InlineTable(doc.as_table().clone().into_inline_table())
+}
+
+fn lookup<'a>(value: &'a Value, key: &ConfigKey) -> Option<&'a Value> {
+    key.segments().iter().try_fold(value, |value, segment| {
+        let value = value.as_inline_table()?.get(&segment.name)?;
+        match segment.index {
+            Some(index) => value.as_array()?.get(index),
+            None => Some(value),
+        }
+    })
+}
+
+/// Lists the leaf values under `value`, with their dotted keys.
+///
+/// Empty tables and arrays, and arrays of non-table values are leaves.
+fn flatten(value: &Value, key: ConfigKey) -> Vec<(ConfigKey, Value)> {
+    match value {
+        Value::InlineTable(table) if !table.is_empty() => table
+            .iter()
+            .flat_map(|(name, value)| flatten(value, key.child(name)))
+            .collect(),
+        Value::Array(array) if !array.is_empty() && array.iter().all(Value::is_inline_table) => {
+            array
+                .iter()
+                .enumerate()
+                .flat_map(|(index, value)| flatten(value, key.element(index)))
+                .collect()
+        }
+        _ => {
+            let mut value = value.clone();
+            value.decor_mut().clear();
+            vec![(key, value)]
+        }
+    }
+}
+
+/// Mutable reference to a table in a TOML document, either standard or inline.
+enum TableMut<'a> {
+    Table(&'a mut Table),
+    Inline(&'a mut InlineTable),
+}
+
+impl<'a> TableMut<'a> {
+    /// Returns the table at `segment`, creating a missing table if the segment
+    /// is not an array element.
+    fn child(self, segment: &KeySegment) -> Option<Self> {
+        let name = segment.name.as_str();
+        match self {
+            TableMut::Table(table) => {
+                if segment.index.is_none() && !table.contains_key(name) {
+                    let mut child = Table::new();
+                    child.set_implicit(true);
+                    table.insert(name, Item::Table(child));
+                }
+                match (table.get_mut(name)?, segment.index) {
+                    (Item::Table(child), None) => Some(TableMut::Table(child)),
+                    (Item::ArrayOfTables(children), Some(index)) => {
+                        children.get_mut(index).map(TableMut::Table)
+                    }
+                    (Item::Value(value), index) => inline_child(value, index),
+                    _ => None,
+                }
+            }
+            TableMut::Inline(table) => {
+                if segment.index.is_none() && !table.contains_key(name) {
+                    table.insert(name, Value::InlineTable(InlineTable::new()));
+                }
+                inline_child(table.get_mut(name)?, segment.index)
+            }
+        }
+    }
+
+    /// Sets the value of the field `name`, keeping the comments around the
+    /// replaced value.
+    fn set(self, name: &str, mut value: Value) {
+        match self {
+            TableMut::Table(table) => match table.get_mut(name) {
+                Some(Item::Value(old)) => {
+                    *value.decor_mut() = old.decor().clone();
+                    *old = value;
+                }
+                Some(item) => *item = Item::Value(value),
+                None => {
+                    table.insert(name, Item::Value(value));
+                }
+            },
+            TableMut::Inline(table) => match table.get_mut(name) {
+                Some(old) => {
+                    *value.decor_mut() = old.decor().clone();
+                    *old = value;
+                }
+                None => {
+                    table.insert(name, value);
+                    // Keep the spacing of the previous last entry from
+                    // ending up before the separator.
+                    table.fmt();
+                }
+            },
+        }
+    }
+}
+
+fn inline_child(value: &mut Value, index: Option<usize>) -> Option<TableMut<'_>> {
+    let value = match index {
+        Some(index) => value.as_array_mut()?.get_mut(index)?,
+        None => value,
+    };
+    value.as_inline_table_mut().map(TableMut::Inline)
+}
+
+fn set_value(doc: &mut DocumentMut, key: &ConfigKey, mut value: Value) -> Result<()> {
+    let (last, parents) = key
+        .segments()
+        .split_last()
+        .expect("parsed keys have at least one segment");
+    if last.index.is_some() {
+        bail!("cannot set an array element, set the whole array instead");
+    }
+    let mut table = TableMut::Table(doc.as_table_mut());
+    let mut parent_key = ConfigKey::default();
+    for segment in parents {
+        parent_key = match segment.index {
+            Some(index) => parent_key.child(&segment.name).element(index),
+            None => parent_key.child(&segment.name),
+        };
+        table = table.child(segment).ok_or_else(|| match segment.index {
+            Some(_) => eyre!("`{parent_key}` not found in config file"),
+            None => eyre!("`{parent_key}` is not a table in config file"),
+        })?;
+    }
+    value.decor_mut().clear();
+    table.set(&last.name, value);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the settings of the template, uncommented.
+    fn uncommented_template() -> DocumentMut {
+        let template = TEMPLATE.replace("{root_path}", r#""~/src""#);
+        template
+            .lines()
+            .filter_map(|line| line.strip_prefix("# "))
+            .filter(|line| {
+                line.starts_with('[')
+                    || line.split_once(" = ").is_some_and(|(name, _)| {
+                        name.chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                    })
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn uncommented_template_is_valid() {
+        let doc = uncommented_template();
+        assert!(doc.contains_key("worktree"));
+        let (unknown_fields, result) = config::deserialize(&doc);
+        assert_eq!(unknown_fields, []);
+        result.unwrap();
+    }
+
+    #[test]
+    fn template_shows_default_values() {
+        let mut config = Config::default();
+        config.roots.push(RootConfig::default_root());
+        let defaults = document_value(toml_edit::ser::to_document(&config).unwrap());
+        let template = document_value(uncommented_template());
+        let mut compared = 0;
+        // Settings without default values, such as `layout`, are examples.
+        for (key, value) in flatten(&template, ConfigKey::default()) {
+            let Some(default) = lookup(&defaults, &key) else {
+                continue;
+            };
+            let mut default = default.clone();
+            default.decor_mut().clear();
+            assert_eq!(value.to_string(), default.to_string(), "{key}");
+            compared += 1;
+        }
+        assert!(compared > 10, "{compared}");
+    }
+
+    #[test]
+    fn set_value_keeps_comments_and_creates_tables() {
+        let mut doc = r#"
+# roots
+[[root]]
+name = "default"
+path = "~/src" # where repositories are
+
+[query]
+scheme_alias = { gh = "github" }
+"#
+        .parse::<DocumentMut>()
+        .unwrap();
+        for (key, value) in [
+            ("root[0].path", r#""~/repos""#),
+            ("query.scheme_alias.gl", r#""gitlab""#),
+            ("query.normalize.lowercase_host", "true"),
+            ("worktree.path_template", r#""{repo_relative_path}""#),
+        ] {
+            let key = key.parse::<ConfigKey>().unwrap();
+            set_value(&mut doc, &key, value.parse().unwrap()).unwrap();
+        }
+        assert_eq!(
+            doc.to_string(),
+            r#"
+# roots
+[[root]]
+name = "default"
+path = "~/repos" # where repositories are
+
+[query]
+scheme_alias = { gh = "github", gl = "gitlab" }
+
+[query.normalize]
+lowercase_host = true
+
+[worktree]
+path_template = "{repo_relative_path}"
+"#
+        );
+
+        let key = "root[1].path".parse::<ConfigKey>().unwrap();
+        assert!(set_value(&mut doc, &key, Value::from("x")).is_err());
+    }
+
+    #[test]
+    fn flatten_lists_leaves_with_dotted_keys() {
+        let doc = r#"
+[[root]]
+name = "default"
+
+[query]
+scheme_alias = {}
+
+[query.url_rewrite."git@github.com:"]
+instead_of = ["https://github.com/"]
+"#
+        .parse::<DocumentMut>()
+        .unwrap();
+        let value = document_value(doc);
+        let entries = flatten(&value, ConfigKey::default())
+            .into_iter()
+            .map(|(key, value)| format!("{key} = {value}"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entries,
+            [
+                r#"root[0].name = "default""#,
+                "query.scheme_alias = {}",
+                r#"query.url_rewrite."git@github.com:".instead_of = ["https://github.com/"]"#,
+            ]
+        );
+        let key = "root[0].name".parse::<ConfigKey>().unwrap();
+        assert_eq!(
+            lookup(&value, &key).and_then(Value::as_str),
+            Some("default")
+        );
+    }
+}
@@ -0,0 +1,439 @@
+use std::fmt::Display;
+
+use chrono::Utc;
+use color_eyre::eyre::{Result, bail};
+
+use crate::{
+    application::usecase::{
+        Usecases,
+        doctor::{RootStatus, root_overlaps},
+        list::ListOptions,
+    },
+    cli::{
+        color::{self, Style},
+        command::list::{input_roots, list_context},
+        config::{self, Config},
+        context::{app::AppContext, doctor::DoctorContext, global::GlobalContext},
+        input::app_param::AppParam,
+    },
+    domain::{
+        model::{
+            path_buf_pair::PathBufPair,
+            path_like::PathLike as _,
+            query::{ExpansionStep, Query},
+            repo::Repo,
+        },
+        port::git_backend::SshAgentStatus,
+    },
+    util::{error::FormatErrorChain as _, file},
+};
+
+/// Problems found so far, printed as they are found.
+#[derive(Debug)]
+struct Report {
+    fix: bool,
+    color: bool,
+    errors: usize,
+    warnings: usize,
+    fixed: usize,
+}
+
+impl Report {
+    fn new(fix: bool) -> Self {
+        Self {
+            fix,
+            color: color::stdout_enabled(),
+            errors: 0,
+            warnings: 0,
+            fixed: 0,
+        }
+    }
+
+    fn section(&self, title: impl Display) {
+        println!("{}", Style::Bold.paint(self.color, title));
+    }
+
+    fn ok(&self, message: impl Display) {
+        println!("  {} {message}", Style::Green.paint(self.color, "ok:"));
+    }
+
+    fn warn(&mut self, message: impl Display) {
+        self.warnings += 1;
+        println!(
+            "  {} {message}",
+            Style::Yellow.paint(self.color, "warning:")
+        );
+    }
+
+    fn error(&mut self, message: impl Display) {
+        self.errors += 1;
+        println!("  {} {message}", Style::Red.paint(self.color, "error:"));
+    }
+
+    fn fixed(&mut self, message: impl Display) {
+        self.fixed += 1;
+        println!("  {} {message}", Style::Cyan.paint(self.color, "fixed:"));
+    }
+
+    fn hint(&self, message: impl Display) {
+        println!(
+            "    {}",
+            Style::Dim.paint(self.color, format!("hint: {message}"))
+        );
+    }
+
+    fn fix_hint(&self) {
+        if !self.fix {
+            self.hint("run `souko doctor --fix` to fix it");
+        }
+    }
+}
+
+pub(super) fn dispatch(app_ctx: &AppContext, doctor_ctx: &DoctorContext) -> Result<()> {
+    let mut report = Report::new(doctor_ctx.fix());
+
+    let config = check_config(&mut report, app_ctx.config_path());
+    check_data_files(&mut report, app_ctx);
+    check_git(&mut report, app_ctx.usecases());
+
+    match config {
+        Some(config) => {
+            // The configuration file is checked above, so the context is built
+            // from the checked configuration instead of loading it again.
+            let global_ctx = GlobalContext::new(app_ctx, config.clone());
+            check_roots(&mut report, &global_ctx);
+            check_schemes(&mut report, &global_ctx, &config);
+            check_repos(&mut report, &global_ctx);
+        }
+        None => {
+            report.section("Roots and repositories");
+            report.warn("skipped because the configuration file cannot be loaded");
+        }
+    }
+
+    println!();
+    let summary = format!(
+        "{} errors, {} warnings, {} fixed",
+        report.errors, report.warnings, report.fixed
+    );
+    println!("{}", Style::Bold.paint(report.color, summary));
+    if report.errors > 0 {
+        bail!("found {} errors", report.errors);
+    }
+    Ok(())
+}
+
+/// Checks the configuration file, and returns it if the other checks can use it.
+///
+/// Unknown fields are reported, and the other checks use the configuration
+/// without them. They are never removed, because they are usually misspelled
+/// settings that the user intended.
+fn check_config(report: &mut Report, config_path: &AppParam<PathBufPair>) -> Option<Config> {
+    let path = config_path.value();
+    report.section(format!("Configuration file: {}", path.display()));
+
+    let doc = match file::load_toml_document("configuration file", path) {
+        Ok(Some(doc)) => doc,
+        Ok(None) if config_path.source().is_implicit_default() => {
+            report.ok("not found, the default configuration is used");
+            return Some(Config::default());
+        }
+        Ok(None) => {
+            report.error("not found");
+            return None;
+        }
+        Err(e) => {
+            report.error(format!("{e:#}"));
+            return None;
+        }
+    };
+
+    let (unknown_fields, result) = config::deserialize(&doc);
+    for field in &unknown_fields {
+        report.error(format!("unknown field `{field}`"));
+        match field.suggestion() {
+            Some(suggestion) => report.hint(format!("did you mean `{suggestion}`?")),
+            None => report.hint("remove it from the configuration file"),
+        }
+    }
+
+    match result {
+        Ok(config) => {
+            if unknown_fields.is_empty() {
+                report.ok("configuration file is valid");
+            }
+            Some(config)
+        }
+        Err(e) => {
+            report.error(e.to_string().trim_end());
+            None
+        }
+    }
+}
+
+fn check_data_files(report: &mut Report, app_ctx: &AppContext) {
+    let doctor = app_ctx.usecases().doctor();
+
+    let repo_cache_path = app_ctx.repo_cache_path().value();
+    report.section(format!("Repository cache: {}", repo_cache_path.display()));
+    let options = ListOptions::default();
+    match doctor.check_repo_cache(repo_cache_path, Utc::now(), options.cache_expire_duration) {
+        Ok(()) => report.ok("readable"),
+        Err(e) if report.fix => match doctor.reset_repo_cache(repo_cache_path) {
+            Ok(()) => report.fixed(format!(
+                "reset the unreadable cache ({})",
+                e.format_error_chain()
+            )),
+            Err(e) => report.error(e.format_error_chain()),
+        },
+        // `souko list` also resets an unreadable cache, so this is not an error.
+        Err(e) => {
+            report.warn(e.format_error_chain());
+            report.fix_hint();
+        }
+    }
+
+    let visit_history_path = app_ctx.visit_history_path().value();
+    report.section(format!("Visit history: {}", visit_history_path.display()));
+    match doctor.check_visit_history(visit_history_path) {
+        Ok(()) => report.ok("readable"),
+        Err(e) => {
+            report.error(e.format_error_chain());
+            report.hint("remove the file to start a new history");
+        }
+    }
+}
+
+fn check_git(report: &mut Report, usecases: &Usecases) {
+    let doctor = usecases.doctor();
+    let features = doctor.git_backend_features();
+    report.section("Git");
+    let linkage = if features.vendored {
+        "vendored"
+    } else {
+        "system library"
+    };
+    report.ok(format!("{} ({linkage})", features.version));
+    if cfg!(feature = "vendored-openssl") {
+        report.ok("OpenSSL is vendored");
+    }
+    if features.https {
+        report.ok("HTTPS is supported");
+    } else {
+        report.warn("HTTPS is not supported; cloning over https will fail");
+    }
+    if features.ssh {
+        report.ok("SSH is supported");
+    } else {
+        report.warn("SSH is not supported; cloning over ssh will fail");
+    }
+    match doctor.ssh_agent() {
+        SshAgentStatus::Available => report.ok("ssh-agent is available"),
+        SshAgentStatus::NotConfigured => {
+            report.warn("ssh-agent is not available (`SSH_AUTH_SOCK` is not set)");
+            report.hint("cloning over ssh needs ssh-agent to authenticate");
+        }
+        SshAgentStatus::MissingSocket => {
+            report.warn("ssh-agent is not available (`SSH_AUTH_SOCK` points to a missing socket)");
+            report.hint("cloning over ssh needs ssh-agent to authenticate");
+        }
+        SshAgentStatus::Unknown => {}
+    }
+}
+
+fn check_roots(report: &mut Report, global_ctx: &GlobalContext) {
+    let doctor = global_ctx.usecases().doctor();
+    report.section("Roots");
+    let mut canonical_roots = vec![];
+    for root in global_ctx.root_map().all_roots() {
+        let value = root.value().root();
+        let name = value.name();
+        let path = value.path().display();
+        match doctor.check_root(value) {
+            RootStatus::Ok(canonical_root) => {
+                report.ok(format!("`{name}`: {path}"));
+                canonical_roots.push(canonical_root);
+            }
+            // The default root is created by the first `souko clone`.
+            RootStatus::Missing if root.source().is_implicit_default() => {
+                report.ok(format!("`{name}`: {path} (not created yet)"));
+            }
+            RootStatus::Missing if report.fix => match doctor.create_root(value) {
+                Ok(()) => report.fixed(format!("`{name}`: created {path}")),
+                Err(e) => report.error(format!("`{name}`: {}", e.format_error_chain())),
+            },
+            RootStatus::Missing => {
+                report.warn(format!("`{name}`: {path} does not exist"));
+                report.fix_hint();
+            }
+            RootStatus::Unreadable(e) => {
+                report.error(format!(
+                    "`{name}`: {path} cannot be read: {}",
+                    e.format_error_chain()
+                ));
+            }
+        }
+    }
+
+    for overlap in root_overlaps(&canonical_roots) {
+        let (outer, inner) = (&overlap.outer, &overlap.inner);
+        if overlap.same {
+            report.error(format!(
+                "roots `{outer}` and `{inner}` are the same directory"
+            ));
+        } else {
+            report.warn(format!("root `{inner}` is inside root `{outer}`"));
+            report.hint(format!(
+                "repositories in `{inner}` are also listed under `{outer}`"
+            ));
+        }
+    }
+
+    // Routes are only resolved by the subcommands that select a root, so
+    // check them here.
+    if let Err(e) = global_ctx.route() {
+        report.error(format!("{e:#}"));
+    }
+}
+
+fn check_schemes(report: &mut Report, global_ctx: &GlobalContext, config: &Config) {
+    let query = &config.query;
+    let root = global_ctx.root_map().default_root().value().root();
+
+    let mut samples = vec![(
+        format!("default scheme `{}`", query.default_scheme),
+        "owner/repo".to_owned(),
+    )];
+    for (scheme, template) in &query.custom_scheme {
+        let sample = if template.uses_variable("host") {
+            format!("{scheme}+example.com:owner/repo")
+        } else {
+            format!("{scheme}:owner/repo")
+        };
+        samples.push((format!("custom scheme `{scheme}`"), sample));
+    }
+    for alias in query.scheme_alias.keys() {
+        samples.push((
+            format!("scheme alias `{alias}`"),
+            format!("{alias}:owner/repo"),
+        ));
+    }
+    samples.sort();
+
+    report.section("Schemes");
+    let parse_option = match global_ctx.query() {
+        Ok(query) => query.parse_option(),
+        Err(e) => {
+            report.error(format!("{e:#}"));
+            return;
+        }
+    };
+    for (name, sample) in samples {
+        let result = Query::parse(&sample, parse_option)
+            .map_err(|e| e.format_error_chain().to_string())
+            .and_then(|query| {
+                // A template that does not expand to a URL is taken as a
+                // query without scheme again.
+                let steps = query.steps();
+                let custom = steps
+                    .iter()
+                    .position(|step| matches!(step, ExpansionStep::CustomScheme { .. }));
+                if let Some(i) = custom
+                    && steps[i + 1..]
+                        .iter()
+                        .any(|step| matches!(step, ExpansionStep::DefaultScheme { .. }))
+                {
+                    return Err(format!("`{}` is not a URL", steps[i].result()));
+                }
+                Ok(query)
+            })
+            .and_then(|query| {
+                Repo::from_query(root, &query, false)
+                    .map_err(|e| e.format_error_chain().to_string())
+            });
+        match result {
+            Ok(repo) => report.ok(format!(
+                "{name}: `{sample}` -> {}",
+                repo.relative_path().display()
+            )),
+            Err(e) => report.error(format!("{name} fails to expand `{sample}`: {e}")),
+        }
+    }
+}
+
+fn check_repos(report: &mut Report, global_ctx: &GlobalContext) {
+    let roots = global_ctx
+        .root_map()
+        .all_roots()
+        .cloned()
+        .collect::<Vec<_>>();
+    report.section("Repositories");
+    let Ok(query) = global_ctx.query() else {
+        report.warn("skipped because the query options cannot be loaded");
+        return;
+    };
+    let parse_option = query.parse_option();
+    // An invalid route is reported by the roots check, so the repositories are
+    // checked in their own roots then.
+    let route = global_ctx.route().ok();
+
+    let mut checked = 0;
+    let list_roots = global_ctx.usecases().list().list_repos(
+        input_roots(&roots),
+        list_context(global_ctx),
+        ListOptions::default(),
+    );
+    for list_root in list_roots {
+        let repos = match list_root.and_then(|list_root| {
+            let root = list_root.root().clone();
+            list_root.repos().map(|repos| (root, repos))
+        }) {
+            Ok(repos) => repos,
+            Err(e) => {
+                report.warn(e.format_error_chain());
+                continue;
+            }
+        };
+        let (root, repos) = repos;
+        for repo in repos {
+            let repo = match repo {
+                Ok(repo) => repo,
+                Err(e) => {
+                    report.warn(e.format_error_chain());
+                    continue;
+                }
+            };
+            checked += 1;
+            let route_root = |query: &Query| match route {
+                Some(route) => route
+                    .route_root(global_ctx.root_map(), query)
+                    .map(|root| root.map(|root| root.value().root().clone())),
+                None => Ok(None),
+            };
+            match global_ctx
+                .usecases()
+                .misplaced()
+                .check(&root, &repo, parse_option, route_root)
+            {
+                Ok(None) => {}
+                Ok(Some(misplaced)) => {
+                    let mut expected =
+                        format!("`{}`", misplaced.expected.relative_path().display());
+                    if misplaced.expected_root.name() != root.name() {
+                        expected =
+                            format!("{expected} in root `{}`", misplaced.expected_root.name());
+                    }
+                    report.warn(format!(
+                        "`{}` in root `{}` does not match its origin `{}`, expected {expected}",
+                        misplaced.repo.relative_path().display(),
+                        root.name(),
+                        misplaced.origin_url,
+                    ));
+                    report.hint("run `souko relocate` to move it");
+                }
+                Err(e) => report.warn(e.format_error_chain()),
+            }
+        }
+    }
+    report.ok(format!("{checked} repositories checked"));
+}
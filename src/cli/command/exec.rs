@@ -0,0 +1,269 @@
+use std::{
+    io::{self, BufRead as _, BufReader, Read, Write as _},
+    path::PathBuf,
+    process::{Command, ExitStatus, Stdio},
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+use color_eyre::eyre::{Result, bail};
+
+use crate::{
+    application::usecase::list::{DuplicateRepoPolicy, ListOptions},
+    cli::{
+        args::{
+            exec::ExecOutput,
+            list::{Column, Duplicates},
+        },
+        color::{self, Style},
+        command::list::{WarnAndSkipErrorExt as _, input_roots, list_context},
+        context::{exec::ExecContext, global::GlobalContext},
+        message,
+        render::list::RepoListTemplateContext,
+    },
+    domain::model::{path_like::PathLike as _, repo::CanonicalRepo, root::CanonicalRoot},
+};
+
+/// A command to run in a repository, prepared on the main thread.
+///
+/// Listing repositories goes through ports that cannot be shared between
+/// threads, so only plain data is passed to the workers.
+#[derive(Debug)]
+struct Job {
+    label: String,
+    cwd: PathBuf,
+    envs: Vec<(String, String)>,
+}
+
+#[derive(Debug)]
+enum Outcome {
+    Exited(ExitStatus),
+    Io(io::Error),
+    Skipped,
+}
+
+pub(super) fn dispatch(global_ctx: &GlobalContext, exec_ctx: &ExecContext) -> Result<()> {
+    let options = ListOptions {
+        duplicate_repo_policy: match exec_ctx.duplicates() {
+            Duplicates::Warn => DuplicateRepoPolicy::Warn,
+            Duplicates::Show => DuplicateRepoPolicy::Show,
+        },
+        ..ListOptions::default()
+    };
+    let jobs = exec_ctx.jobs().get();
+    let failed = AtomicBool::new(false);
+    let results = Mutex::new(vec![]);
+    let (tx, rx) = mpsc::sync_channel::<Job>(jobs);
+    let rx = Mutex::new(rx);
+
+    thread::scope(|s| {
+        for _ in 0..jobs {
+            s.spawn(|| {
+                loop {
+                    let Ok(job) = rx.lock().unwrap().recv() else {
+                        break;
+                    };
+                    let outcome = if exec_ctx.fail_fast() && failed.load(Ordering::SeqCst) {
+                        Outcome::Skipped
+                    } else {
+                        run(exec_ctx, &job)
+                    };
+                    if !matches!(&outcome, Outcome::Exited(status) if status.success())
+                        && !matches!(outcome, Outcome::Skipped)
+                    {
+                        failed.store(true, Ordering::SeqCst);
+                    }
+                    results.lock().unwrap().push((job.label, outcome));
+                }
+            });
+        }
+
+        let repos = global_ctx
+            .usecases()
+            .list()
+            .list_repos(
+                input_roots(exec_ctx.roots()),
+                list_context(global_ctx),
+                options,
+            )
+            .warn_and_skip_errors()
+            .map(|list_root| {
+                let root = list_root.root().clone();
+                list_root.repos().map(|repos| (root, repos))
+            })
+            .warn_and_skip_errors()
+            .flat_map(|(root, repos)| {
+                repos
+                    .warn_and_skip_errors()
+                    .map(move |repo| (root.clone(), repo))
+            });
+        for (root, repo) in repos {
+            if exec_ctx.fail_fast() && failed.load(Ordering::SeqCst) {
+                break;
+            }
+            if tx.send(job(&root, &repo)).is_err() {
+                break;
+            }
+        }
+        drop(tx);
+    });
+
+    summarize(results.into_inner().unwrap())
+}
+
+fn job(root: &CanonicalRoot, repo: &CanonicalRepo) -> Job {
+    let template_ctx = RepoListTemplateContext::new(root, repo, false);
+    let envs = Column::ALL
+        .iter()
+        .map(|&column| {
+            (
+                format!("SOUKO_{}", column.name().to_uppercase()),
+                template_ctx.field(column).to_owned(),
+            )
+        })
+        .collect();
+    Job {
+        label: format!(
+            "{}:{}",
+            template_ctx.field(Column::RootName),
+            template_ctx.field(Column::RepoRelativePath)
+        ),
+        cwd: repo.path().as_real_path().to_owned(),
+        envs,
+    }
+}
+
+fn command(exec_ctx: &ExecContext, job: &Job) -> Command {
+    let mut command = Command::new(exec_ctx.program());
+    command
+        .args(exec_ctx.args())
+        .current_dir(&job.cwd)
+        .envs(job.envs.iter().map(|(k, v)| (k, v)))
+        .stdin(Stdio::null());
+    command
+}
+
+fn run(exec_ctx: &ExecContext, job: &Job) -> Outcome {
+    let result = match exec_ctx.output() {
+        ExecOutput::Prefix => run_prefixed(exec_ctx, job),
+        ExecOutput::Group => run_grouped(exec_ctx, job),
+    };
+    match result {
+        Ok(status) => Outcome::Exited(status),
+        Err(e) => Outcome::Io(e),
+    }
+}
+
+fn run_prefixed(exec_ctx: &ExecContext, job: &Job) -> io::Result<ExitStatus> {
+    let mut child = command(exec_ctx, job)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let stderr = child.stderr.take().expect("stderr is piped");
+    let (stdout_result, stderr_result) = thread::scope(|s| {
+        let stdout = s.spawn(|| {
+            let prefix = Style::Cyan
+                .paint(color::stdout_enabled(), format!("{}:", job.label))
+                .to_string();
+            copy_prefixed(stdout, &prefix, || Box::new(io::stdout().lock()))
+        });
+        let stderr = s.spawn(|| {
+            let prefix = Style::Cyan
+                .paint(color::stderr_enabled(), format!("{}:", job.label))
+                .to_string();
+            copy_prefixed(stderr, &prefix, || Box::new(io::stderr().lock()))
+        });
+        (stdout.join().unwrap(), stderr.join().unwrap())
+    });
+    let status = child.wait()?;
+    stdout_result?;
+    stderr_result?;
+    Ok(status)
+}
+
+/// Copies `input` to `output` line by line, with `prefix` at the start of each line.
+///
+/// Errors writing the output are ignored, so that the command keeps running
+/// even if the output is closed, but errors reading the output of the command
+/// are returned.
+fn copy_prefixed<'a>(
+    input: impl Read,
+    prefix: &str,
+    output: impl Fn() -> Box<dyn io::Write + 'a>,
+) -> io::Result<()> {
+    let mut input = BufReader::new(input);
+    let mut line = vec![];
+    loop {
+        line.clear();
+        match input.read_until(b'\n', &mut line) {
+            Ok(0) => return Ok(()),
+            Ok(_) => {}
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => {
+                return Err(io::Error::new(
+                    e.kind(),
+                    format!("failed to read the output of the command: {e}"),
+                ));
+            }
+        }
+        if !line.ends_with(b"\n") {
+            line.push(b'\n');
+        }
+        // Lock the output for each line so that lines from parallel commands
+        // are not interleaved in the middle.
+        let mut out = output();
+        let _ = out.write_all(prefix.as_bytes());
+        let _ = out.write_all(b" ");
+        let _ = out.write_all(&line);
+        let _ = out.flush();
+    }
+}
+
+fn run_grouped(exec_ctx: &ExecContext, job: &Job) -> io::Result<ExitStatus> {
+    let output = command(exec_ctx, job).output()?;
+    let header = Style::Bold.paint(color::stdout_enabled(), format!("==> {} <==", job.label));
+    // Hold both locks so that the output of each repository stays together.
+    let mut stdout = io::stdout().lock();
+    let mut stderr = io::stderr().lock();
+    writeln!(stdout, "{header}")?;
+    stdout.write_all(&output.stdout)?;
+    stdout.flush()?;
+    stderr.write_all(&output.stderr)?;
+    stderr.flush()?;
+    Ok(output.status)
+}
+
+fn summarize(results: Vec<(String, Outcome)>) -> Result<()> {
+    let mut succeeded = 0;
+    let mut skipped = 0;
+    let mut failed = 0;
+    for (label, outcome) in results {
+        match outcome {
+            Outcome::Exited(status) if status.success() => succeeded += 1,
+            Outcome::Exited(status) => {
+                failed += 1;
+                message::warn!("command failed in `{label}`: {status}");
+            }
+            Outcome::Io(e) => {
+                failed += 1;
+                message::warn!("failed to run command in `{label}`: {e}");
+            }
+            Outcome::Skipped => skipped += 1,
+        }
+    }
+
+    let mut summary = format!("{succeeded} succeeded, {failed} failed");
+    if skipped > 0 {
+        summary.push_str(&format!(", {skipped} skipped"));
+    }
+    message::info!("{summary}");
+    if failed > 0 {
+        bail!("command failed in {failed} repositories");
+    }
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use std::{
+    collections::BTreeMap,
+    io,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+use color_eyre::eyre::Result;
+
+use crate::{
+    application::usecase::list::{DuplicateRepoPolicy, ListOptions},
+    cli::{
+        Outcome,
+        args::list::Duplicates,
+        color,
+        command::list::{WarnAndSkipErrorExt as _, input_roots, list_context},
+        context::{global::GlobalContext, grep::GrepContext},
+        message,
+        render::grep::{Renderer, RepoMatches},
+    },
+    domain::model::{path_buf_pair::PathBufPair, path_like::PathLike as _},
+    util::error::FormatErrorChain as _,
+};
+
+/// A repository to search, prepared on the main thread.
+#[derive(Debug)]
+struct Target {
+    root_name: String,
+    relative_path: String,
+    path: PathBufPair,
+}
+
+pub(super) fn dispatch(global_ctx: &GlobalContext, grep_ctx: &GrepContext) -> Result<Outcome> {
+    let options = ListOptions {
+        duplicate_repo_policy: match grep_ctx.duplicates() {
+            Duplicates::Warn => DuplicateRepoPolicy::Warn,
+            Duplicates::Show => DuplicateRepoPolicy::Show,
+        },
+        ..ListOptions::default()
+    };
+    // Listing repositories goes through ports that cannot be shared between
+    // threads, so every repository is listed before searching.
+    let targets = global_ctx
+        .usecases()
+        .list()
+        .list_repos(
+            input_roots(grep_ctx.roots()),
+            list_context(global_ctx),
+            options,
+        )
+        .warn_and_skip_errors()
+        .map(|list_root| {
+            let root_name = list_root.root().name().to_owned();
+            list_root.repos().map(|repos| (root_name, repos))
+        })
+        .warn_and_skip_errors()
+        .flat_map(|(root_name, repos)| {
+            repos.warn_and_skip_errors().map(move |repo| Target {
+                root_name: root_name.clone(),
+                relative_path: repo.relative_path().as_real_path().display().to_string(),
+                path: repo.path().clone(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let grep = global_ctx.usecases().grep();
+    let source = grep_ctx.source();
+    let pattern = grep_ctx.pattern();
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    let mut renderer = Renderer::new(grep_ctx.format(), color::stdout_enabled());
+    let mut matched = false;
+    let mut failed = false;
+    thread::scope(|s| -> Result<()> {
+        for _ in 0..grep_ctx.jobs().get().min(targets.len()) {
+            let tx = tx.clone();
+            let (targets, next) = (&targets, &next);
+            s.spawn(move || {
+                loop {
+                    let i = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(target) = targets.get(i) else {
+                        break;
+                    };
+                    let result = grep.search_repo(&target.path, source, pattern);
+                    if tx.send((i, result)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        // Results arrive in any order, so they are buffered to keep the
+        // output in the order of the listing.
+        let mut pending = BTreeMap::new();
+        let mut next_output = 0;
+        for (i, result) in rx {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&next_output) {
+                let target = &targets[next_output];
+                next_output += 1;
+                let files = match result {
+                    Ok(files) => files,
+                    Err(e) => {
+                        failed = true;
+                        message::warn!(
+                            "failed to search `{}`: {}",
+                            target.relative_path,
+                            e.format_error_chain()
+                        );
+                        continue;
+                    }
+                };
+                if files.is_empty() {
+                    continue;
+                }
+                matched = true;
+                renderer.repo(
+                    io::stdout().lock(),
+                    RepoMatches {
+                        root_name: target.root_name.clone(),
+                        repo_relative_path: target.relative_path.clone(),
+                        repo_real_path: target.path.as_real_path().to_owned(),
+                        files,
+                    },
+                )?;
+            }
+        }
+        Ok(())
+    })?;
+    renderer.finish(io::stdout().lock())?;
+
+    Ok(if failed {
+        Outcome::SearchFailed
+    } else if matched {
+        Outcome::Success
+    } else {
+        Outcome::NoMatch
+    })
+}
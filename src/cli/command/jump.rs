@@ -0,0 +1,68 @@
+use std::{
+    cmp::Reverse,
+    io::{self, Write as _},
+};
+
+use color_eyre::eyre::{Result, bail, eyre};
+
+use crate::{
+    application::usecase::list::ListOptions,
+    cli::{
+        command::{
+            list::{WarnAndSkipErrorExt as _, input_roots, list_context},
+            visit::visit_context,
+        },
+        context::{global::GlobalContext, jump::JumpContext},
+    },
+    util::error::FormatErrorChain as _,
+};
+
+pub(super) fn dispatch(global_ctx: &GlobalContext, jump_ctx: &JumpContext) -> Result<()> {
+    let scores = global_ctx
+        .usecases()
+        .visit()
+        .frecency_scores(&visit_context(global_ctx))
+        .map_err(|e| eyre!("{}", e.format_error_chain()))?;
+    let keywords = jump_ctx.keywords();
+
+    let best = global_ctx
+        .usecases()
+        .list()
+        .list_repos(
+            input_roots(jump_ctx.roots()),
+            list_context(global_ctx),
+            ListOptions::default(),
+        )
+        .warn_and_skip_errors()
+        .map(|list_root| list_root.repos())
+        .warn_and_skip_errors()
+        .flat_map(|repos| repos.warn_and_skip_errors())
+        .filter(|repo| {
+            let relative_path = repo
+                .relative_path()
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            keywords.matches(&relative_path)
+        })
+        // Prefer the highest score, then the shortest path, then the first one listed.
+        .map(|repo| {
+            let score = scores.get(repo.canonical_path());
+            let len = repo.relative_path().as_os_str().len();
+            (score, Reverse(len), repo)
+        })
+        .reduce(|best, candidate| {
+            if (candidate.0, candidate.1) > (best.0, best.1) {
+                candidate
+            } else {
+                best
+            }
+        });
+
+    let Some((_score, _len, repo)) = best else {
+        bail!("no repository matches `{keywords}`");
+    };
+    writeln!(io::stdout().lock(), "{}", repo.canonical_path().display())?;
+    Ok(())
+}
@@ -1,19 +1,90 @@
 use chrono::Utc;
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{Result, eyre};
 
 use crate::{
-    application::usecase::list::{ListContext as ListUsecaseContext, ListOptions, ListRootInput},
+    application::usecase::list::{
+        DuplicateRepoPolicy, ListContext as ListUsecaseContext, ListOptions, ListRootInput,
+        group_linked_worktrees, sort_by_frecency,
+    },
     cli::{
-        context::{global::GlobalContext, list::ListContext},
-        message, render,
+        args::list::{Duplicates, Sort},
+        color,
+        command::visit::visit_context,
+        context::{global::GlobalContext, list::ListContext, root::RootContext},
+        input::app_param::AppParam,
+        message,
+        render::{self, list::RenderOptions},
     },
-    domain::model::path_buf_pair::PathBufPair,
+    domain::model::{path_buf_pair::PathBufPair, repo::CanonicalRepo, root::CanonicalRoot},
     util::error::FormatErrorChain as _,
 };
 
 pub(in crate::cli) fn dispatch(global_ctx: &GlobalContext, list_ctx: &ListContext) -> Result<()> {
     let format = list_ctx.format();
-    let input_roots = list_ctx.roots().iter().map(|root| ListRootInput {
+    let input_roots = input_roots(list_ctx.roots());
+
+    let context = list_context(global_ctx);
+    let options = ListOptions {
+        duplicate_repo_policy: match list_ctx.duplicates() {
+            Duplicates::Warn => DuplicateRepoPolicy::Warn,
+            Duplicates::Show => DuplicateRepoPolicy::Show,
+        },
+        ..ListOptions::default()
+    };
+
+    let group_worktrees = list_ctx.group_worktrees();
+    let roots = global_ctx
+        .usecases()
+        .list()
+        .list_repos(input_roots, context, options)
+        .map(|list_root| {
+            list_root.and_then(|root| {
+                let repos = root.repos()?.warn_and_skip_errors();
+                // Grouping needs every repository of the root at once, so
+                // streaming output is only kept when grouping is disabled.
+                let repos: Box<dyn Iterator<Item = CanonicalRepo>> = if group_worktrees {
+                    Box::new(group_linked_worktrees(repos).into_iter())
+                } else {
+                    Box::new(repos)
+                };
+                Ok((root.root().clone(), repos))
+            })
+        })
+        .warn_and_skip_errors();
+    let roots: Box<dyn Iterator<Item = (CanonicalRoot, Box<dyn Iterator<Item = CanonicalRepo>>)>> =
+        match list_ctx.sort() {
+            Sort::Path => Box::new(roots),
+            Sort::Frecency => {
+                // Sorting needs every repository of every root at once.
+                let scores = global_ctx
+                    .usecases()
+                    .visit()
+                    .frecency_scores(&visit_context(global_ctx))
+                    .map_err(|e| eyre!("{}", e.format_error_chain()))?;
+                Box::new(
+                    sort_by_frecency(roots, &scores)
+                        .into_iter()
+                        .map(|(root, repos)| {
+                            let repos: Box<dyn Iterator<Item = CanonicalRepo>> =
+                                Box::new(repos.into_iter());
+                            (root, repos)
+                        }),
+                )
+            }
+        };
+
+    let render_options = RenderOptions {
+        max_width: terminal_size::terminal_size_of(std::io::stdout())
+            .map(|(terminal_size::Width(width), _)| usize::from(width)),
+        color: color::stdout_enabled(),
+    };
+    render::list::render(std::io::stdout().lock(), format, render_options, roots)
+}
+
+pub(in crate::cli) fn input_roots(
+    roots: &[AppParam<RootContext>],
+) -> impl Iterator<Item = ListRootInput> + '_ {
+    roots.iter().map(|root| ListRootInput {
         // Only the synthesized fallback `default` root with `ImplicitDefault`
         // source is allowed to be missing silently. Roots loaded from the
         // configuration file keep `ConfigurationFile` source even when their
@@ -22,29 +93,20 @@ pub(in crate::cli) fn dispatch(global_ctx: &GlobalContext, list_ctx: &ListContex
         visit_hidden_dirs: root.value().visit_hidden_dirs(),
         visit_repo_subdirs: root.value().visit_repo_subdirs(),
         include_bare_repo: root.value().include_bare_repo(),
+        include_linked_worktrees: root.value().include_linked_worktrees(),
+        follow_symlinks: root.value().follow_symlinks(),
         root: root.value().root().clone(),
-    });
+    })
+}
 
-    let context = ListUsecaseContext {
+pub(in crate::cli) fn list_context(global_ctx: &GlobalContext) -> ListUsecaseContext {
+    ListUsecaseContext {
         now: Utc::now(),
         repo_cache_path: PathBufPair::new(global_ctx.repo_cache_path()),
-    };
-    let options = ListOptions::default();
-
-    let roots = global_ctx
-        .usecases()
-        .list()
-        .list_repos(input_roots, context, options)
-        .map(|list_root| {
-            list_root
-                .and_then(|root| Ok((root.root().clone(), root.repos()?.warn_and_skip_errors())))
-        })
-        .warn_and_skip_errors();
-
-    render::list::render(std::io::stdout().lock(), format, roots)
+    }
 }
 
-trait WarnAndSkipErrorExt<T, E>: Iterator<Item = Result<T, E>> {
+pub(super) trait WarnAndSkipErrorExt<T, E>: Iterator<Item = Result<T, E>> {
     fn warn_and_skip_errors(self) -> impl Iterator<Item = T>
     where
         Self: Sized,
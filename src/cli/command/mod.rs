@@ -1,16 +1,47 @@
 use color_eyre::eyre::Result;
 
-use crate::cli::{SubcommandContext, context::global::GlobalContext};
+use crate::cli::{Outcome, SubcommandContext, context::app::AppContext};
 
 mod clone;
-mod list;
+mod config;
+mod doctor;
+mod exec;
+mod grep;
+mod jump;
+pub(in crate::cli) mod list;
+mod path;
+mod pick;
+mod query;
+mod relocate;
+mod shell_init;
+mod visit;
+mod worktree;
 
 pub(crate) fn dispatch(
-    global_ctx: &GlobalContext,
+    app_ctx: &AppContext,
     subcommand_ctx: &SubcommandContext,
-) -> Result<()> {
+) -> Result<Outcome> {
     match subcommand_ctx {
-        SubcommandContext::Clone(clone_ctx) => clone::dispatch(global_ctx, clone_ctx),
-        SubcommandContext::List(list_ctx) => list::dispatch(global_ctx, list_ctx),
+        SubcommandContext::Clone(clone_ctx) => clone::dispatch(app_ctx.global()?, clone_ctx)?,
+        SubcommandContext::Config(config_ctx) => config::dispatch(app_ctx, config_ctx)?,
+        SubcommandContext::Doctor(doctor_ctx) => doctor::dispatch(app_ctx, doctor_ctx)?,
+        SubcommandContext::Exec(exec_ctx) => exec::dispatch(app_ctx.global()?, exec_ctx)?,
+        SubcommandContext::Grep(grep_ctx) => return grep::dispatch(app_ctx.global()?, grep_ctx),
+        SubcommandContext::Jump(jump_ctx) => jump::dispatch(app_ctx.global()?, jump_ctx)?,
+        SubcommandContext::List(list_ctx) => list::dispatch(app_ctx.global()?, list_ctx)?,
+        SubcommandContext::Path(path_ctx) => path::dispatch(app_ctx.global()?, path_ctx)?,
+        SubcommandContext::Pick(pick_ctx) => return pick::dispatch(app_ctx.global()?, pick_ctx),
+        SubcommandContext::QueryExplain(explain_ctx) => {
+            query::dispatch(app_ctx.global()?, explain_ctx)?
+        }
+        SubcommandContext::Relocate(relocate_ctx) => {
+            relocate::dispatch(app_ctx.global()?, relocate_ctx)?
+        }
+        SubcommandContext::ShellInit(shell_init_ctx) => shell_init::dispatch(shell_init_ctx)?,
+        SubcommandContext::Visit(visit_ctx) => visit::dispatch(app_ctx.global()?, visit_ctx)?,
+        SubcommandContext::Worktree(worktree_ctx) => {
+            worktree::dispatch(app_ctx.global()?, worktree_ctx)?
+        }
     }
+    Ok(Outcome::Success)
 }
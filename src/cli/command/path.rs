@@ -0,0 +1,39 @@
+use std::io::{self, Write as _};
+
+use color_eyre::eyre::{Result, eyre};
+
+use crate::{
+    application::usecase::visit::VisitUsecaseError,
+    cli::{
+        command::visit::visit_context,
+        context::{global::GlobalContext, path::PathContext},
+    },
+    domain::model::repo::Repo,
+    util::error::FormatErrorChain as _,
+};
+
+pub(super) fn dispatch(global_ctx: &GlobalContext, path_ctx: &PathContext) -> Result<()> {
+    let root = path_ctx.root_context().value().root();
+    let query = path_ctx.query();
+    let usecase = global_ctx.usecases().visit();
+    let context = visit_context(global_ctx);
+
+    // The repository may have been cloned either as a normal or a bare repository.
+    let repo = Repo::from_query(root, query, false).map_err(|e| eyre!(e))?;
+    let canonical_path = match usecase.record_repo_visit(&repo, &context) {
+        Err(VisitUsecaseError::RepoNotFound { path }) => {
+            let bare_repo = Repo::from_query(root, query, true).map_err(|e| eyre!(e))?;
+            match usecase.record_repo_visit(&bare_repo, &context) {
+                Err(VisitUsecaseError::RepoNotFound { .. }) => {
+                    Err(VisitUsecaseError::RepoNotFound { path })
+                }
+                res => res,
+            }
+        }
+        res => res,
+    }
+    .map_err(|e| eyre!("{}", e.format_error_chain()))?;
+
+    writeln!(io::stdout().lock(), "{}", canonical_path.display())?;
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use std::{
+    io::{self, IsTerminal as _},
+    time::{Duration, Instant},
+};
+
+use color_eyre::eyre::{Result, bail};
+use crossterm::{
+    event::{self, Event},
+    execute,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use crate::{
+    application::usecase::list::ListOptions,
+    cli::{
+        Outcome,
+        command::list::{input_roots, list_context},
+        context::{global::GlobalContext, pick::PickContext},
+        message,
+        render::{
+            list::RepoListTemplateContext,
+            pick::{Picker, PickerAction},
+        },
+    },
+    util::error::FormatErrorChain as _,
+};
+
+/// How long to scan between checks for key input, so that the picker stays responsive.
+const SCAN_SLICE: Duration = Duration::from_millis(30);
+
+pub(super) fn dispatch(global_ctx: &GlobalContext, pick_ctx: &PickContext) -> Result<Outcome> {
+    if !io::stderr().is_terminal() {
+        bail!("`souko pick` requires a terminal on stderr");
+    }
+
+    let roots = global_ctx.usecases().list().list_repos(
+        input_roots(pick_ctx.roots()),
+        list_context(global_ctx),
+        ListOptions::default(),
+    );
+    let mut repos = roots.flat_map(|list_root| {
+        let repos = list_root.and_then(|list_root| {
+            let root = list_root.root().clone();
+            let repos = list_root.repos()?;
+            Ok(repos.map(move |repo| repo.map(|repo| (root.clone(), repo))))
+        });
+        let (repos, error) = match repos {
+            Ok(repos) => (Some(repos), None),
+            Err(e) => (None, Some(Err(e))),
+        };
+        repos.into_iter().flatten().chain(error)
+    });
+
+    let mut picker = Picker::new(pick_ctx.query());
+    let mut warnings = vec![];
+    let mut scanning = true;
+    let action = {
+        let _terminal = TerminalGuard::enter()?;
+        let mut stderr = io::stderr();
+        loop {
+            if scanning {
+                let deadline = Instant::now() + SCAN_SLICE;
+                while Instant::now() < deadline {
+                    match repos.next() {
+                        Some(Ok((root, repo))) => {
+                            let context = RepoListTemplateContext::new(&root, &repo, false);
+                            let label = pick_ctx.template().expand(&context);
+                            picker.push(label, repo.canonical_path().to_owned());
+                        }
+                        Some(Err(e)) => warnings.push(e.format_error_chain().to_string()),
+                        None => {
+                            scanning = false;
+                            picker.finish_scan();
+                            break;
+                        }
+                    }
+                }
+            }
+            picker.draw(&mut stderr, terminal::size()?)?;
+
+            // Wait for input only after the scan, and otherwise handle pending keys.
+            let mut action = PickerAction::Continue;
+            while action == PickerAction::Continue && (!scanning || event::poll(Duration::ZERO)?) {
+                match event::read()? {
+                    Event::Key(key) => action = picker.handle_key(key),
+                    Event::Resize(..) => {}
+                    _ => continue,
+                }
+                if !scanning {
+                    break;
+                }
+            }
+            if action != PickerAction::Continue {
+                break action;
+            }
+        }
+    };
+
+    for warning in warnings {
+        message::warn!("{warning}");
+    }
+    match (action, picker.selected()) {
+        (PickerAction::Select, Some(path)) => {
+            println!("{}", path.display());
+            Ok(Outcome::Success)
+        }
+        _ => Ok(Outcome::Cancelled),
+    }
+}
+
+/// Switches the terminal to raw mode and the alternate screen until dropped.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> Result<Self> {
+        terminal::enable_raw_mode()?;
+        let guard = Self;
+        execute!(io::stderr(), EnterAlternateScreen)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stderr(), LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
@@ -0,0 +1,37 @@
+use color_eyre::eyre::Result;
+
+use crate::{
+    cli::{
+        context::{global::GlobalContext, query_explain::QueryExplainContext},
+        render::{self, query::RootPlacement},
+    },
+    domain::model::repo::Repo,
+    util::error::FormatErrorChain as _,
+};
+
+pub(super) fn dispatch(
+    global_ctx: &GlobalContext,
+    explain_ctx: &QueryExplainContext,
+) -> Result<()> {
+    let query = explain_ctx.query();
+    let placements = global_ctx
+        .root_map()
+        .all_roots()
+        .map(|root| {
+            let root = root.value();
+            RootPlacement {
+                name: root.name().to_owned(),
+                selected: root.name() == explain_ctx.selected_root(),
+                repo: Repo::from_query(root.root(), query, false)
+                    .map_err(|e| e.format_error_chain().to_string()),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    render::query::render(
+        std::io::stdout().lock(),
+        explain_ctx.json(),
+        query,
+        &placements,
+    )
+}
@@ -0,0 +1,136 @@
+use color_eyre::eyre::{Result, bail};
+
+use crate::{
+    application::usecase::{list::ListOptions, misplaced::MisplacedRepo},
+    cli::{
+        command::list::{WarnAndSkipErrorExt as _, input_roots, list_context},
+        context::{global::GlobalContext, relocate::RelocateContext},
+        message,
+    },
+    domain::model::{query::Query, root::CanonicalRoot},
+    util::error::FormatErrorChain as _,
+};
+
+pub(super) fn dispatch(global_ctx: &GlobalContext, relocate_ctx: &RelocateContext) -> Result<()> {
+    let misplaced_usecase = global_ctx.usecases().misplaced();
+    let parse_option = global_ctx.query()?.parse_option();
+    let route = global_ctx.route()?;
+    // Repositories are moved to another root only if a route selects it.
+    let route_root = |query: &Query| {
+        route
+            .route_root(global_ctx.root_map(), query)
+            .map(|root| root.map(|root| root.value().root().clone()))
+    };
+
+    // Every repository is checked before relocating any of them, so that
+    // moving directories does not disturb the walk of the roots.
+    let misplaced = global_ctx
+        .usecases()
+        .list()
+        .list_repos(
+            input_roots(relocate_ctx.roots()),
+            list_context(global_ctx),
+            ListOptions::default(),
+        )
+        .warn_and_skip_errors()
+        .map(|list_root| {
+            let root = list_root.root().clone();
+            list_root.repos().map(|repos| (root, repos))
+        })
+        .warn_and_skip_errors()
+        .flat_map(|(root, repos)| {
+            repos
+                .warn_and_skip_errors()
+                .map(move |repo| (root.clone(), repo))
+        })
+        .filter_map(|(root, repo)| {
+            misplaced_usecase
+                .check(&root, &repo, parse_option, route_root)
+                .map(|misplaced| misplaced.map(|misplaced| (root, misplaced)))
+                .transpose()
+        })
+        .warn_and_skip_errors()
+        .collect::<Vec<_>>();
+
+    if misplaced.is_empty() {
+        message::info!("no misplaced repositories found");
+        return Ok(());
+    }
+
+    let mut failed = 0;
+    for (root, misplaced) in &misplaced {
+        let result = if relocate_ctx.update_remote() {
+            update_remote(global_ctx, relocate_ctx, root, misplaced)
+        } else {
+            move_repo(global_ctx, relocate_ctx, root, misplaced)
+        };
+        if let Err(e) = result {
+            message::warn!("{}", e.format_error_chain());
+            failed += 1;
+        }
+    }
+
+    if !relocate_ctx.apply() {
+        let action = if relocate_ctx.update_remote() {
+            "update their remotes"
+        } else {
+            "move them"
+        };
+        message::info!(
+            "{} misplaced repositories found, pass `--apply` to {action}",
+            misplaced.len()
+        );
+    }
+    if failed > 0 {
+        bail!("failed to relocate {failed} repositories");
+    }
+    Ok(())
+}
+
+fn move_repo(
+    global_ctx: &GlobalContext,
+    relocate_ctx: &RelocateContext,
+    root: &CanonicalRoot,
+    misplaced: &MisplacedRepo,
+) -> Result<()> {
+    let from = misplaced.repo.relative_path().display();
+    let to = misplaced.expected.relative_path().display();
+    let expected_root = misplaced.expected_root.name();
+    if expected_root == root.name() {
+        println!("{}: {from} -> {to}", root.name());
+    } else {
+        println!("{}: {from} -> {expected_root}: {to}", root.name());
+    }
+    if relocate_ctx.apply() {
+        global_ctx
+            .usecases()
+            .misplaced()
+            .move_repo(root, misplaced)?;
+    }
+    Ok(())
+}
+
+fn update_remote(
+    global_ctx: &GlobalContext,
+    relocate_ctx: &RelocateContext,
+    root: &CanonicalRoot,
+    misplaced: &MisplacedRepo,
+) -> Result<()> {
+    let misplaced_usecase = global_ctx.usecases().misplaced();
+    let url = misplaced_usecase.origin_url_for_path(
+        root,
+        misplaced,
+        global_ctx.query()?.parse_option(),
+    )?;
+    println!(
+        "{}: {}: {} -> {}",
+        root.name(),
+        misplaced.repo.relative_path().display(),
+        misplaced.origin_url,
+        url,
+    );
+    if relocate_ctx.apply() {
+        misplaced_usecase.update_origin(misplaced, &url)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use std::io::{self, Write as _};
+
+use color_eyre::eyre::{Result, WrapErr as _};
+
+use crate::cli::{args::shell_init::Shell, context::shell_init::ShellInitContext};
+
+const BASH: &str = include_str!("../../../shell/init/souko.bash");
+const ELVISH: &str = include_str!("../../../shell/init/souko.elv");
+const FISH: &str = include_str!("../../../shell/init/souko.fish");
+const NUSHELL: &str = include_str!("../../../shell/init/souko.nu");
+const POWERSHELL: &str = include_str!("../../../shell/init/souko.ps1");
+const ZSH: &str = include_str!("../../../shell/init/souko.zsh");
+
+fn script(shell: Shell) -> &'static str {
+    match shell {
+        Shell::Bash => BASH,
+        Shell::Elvish => ELVISH,
+        Shell::Fish => FISH,
+        Shell::Nushell => NUSHELL,
+        Shell::Powershell => POWERSHELL,
+        Shell::Zsh => ZSH,
+    }
+}
+
+pub(super) fn dispatch(shell_init_ctx: &ShellInitContext) -> Result<()> {
+    io::stdout()
+        .write_all(script(shell_init_ctx.shell()).as_bytes())
+        .wrap_err("failed to write shell integration code")
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum as _;
+
+    use super::*;
+
+    #[test]
+    fn every_script_defines_cd_function_key_binding_and_visit_hook() {
+        for shell in Shell::value_variants() {
+            let script = script(*shell);
+            assert!(script.contains("souko-cd"), "{shell:?}");
+            assert!(script.contains("souko visit --"), "{shell:?}");
+            assert!(
+                ["bind", "binding", "Set-PSReadLineKeyHandler"]
+                    .iter()
+                    .any(|keyword| script.contains(keyword)),
+                "{shell:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use chrono::Utc;
+use color_eyre::eyre::{Result, eyre};
+
+use crate::{
+    application::usecase::visit::VisitContext as VisitUsecaseContext,
+    cli::context::{global::GlobalContext, visit::VisitContext},
+    domain::model::{path_buf_pair::PathBufPair, path_like::PathLike as _},
+    util::error::FormatErrorChain as _,
+};
+
+pub(super) fn dispatch(global_ctx: &GlobalContext, visit_ctx: &VisitContext) -> Result<()> {
+    let context = visit_context(global_ctx);
+    let visited = global_ctx
+        .usecases()
+        .visit()
+        .record_visit(
+            visit_ctx.roots().iter().cloned(),
+            visit_ctx.path(),
+            &context,
+        )
+        .map_err(|e| eyre!("{}", e.format_error_chain()))?;
+    match visited {
+        Some(repo) => tracing::debug!(
+            "recorded visit to {} in root `{}`",
+            repo.canonical_path.display(),
+            repo.root.name()
+        ),
+        None => tracing::debug!("not a repository in souko: {}", visit_ctx.path().display()),
+    }
+    Ok(())
+}
+
+pub(super) fn visit_context(global_ctx: &GlobalContext) -> VisitUsecaseContext {
+    VisitUsecaseContext {
+        now: Utc::now(),
+        visit_history_path: PathBufPair::new(global_ctx.visit_history_path()),
+    }
+}
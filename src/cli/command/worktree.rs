@@ -0,0 +1,69 @@
+use std::io::{self, Write as _};
+
+use color_eyre::eyre::{Result, WrapErr as _, eyre};
+
+use crate::{
+    cli::{
+        context::{
+            global::GlobalContext,
+            worktree::{WorktreeAction, WorktreeContext},
+        },
+        message,
+    },
+    domain::model::path_like::PathLike as _,
+};
+
+pub(super) fn dispatch(global_ctx: &GlobalContext, worktree_ctx: &WorktreeContext) -> Result<()> {
+    let root = worktree_ctx.root_context().value().root();
+    let query = worktree_ctx.query();
+    let usecase = global_ctx.usecases().worktree();
+
+    match worktree_ctx.action() {
+        WorktreeAction::Add { branch } => {
+            message::info!(
+                "adding worktree of {} for branch `{branch}`",
+                query.original_query()
+            );
+            let worktree = usecase
+                .add_worktree(root, query, branch, global_ctx.worktree_path_template())
+                .map_err(|e| eyre!(e))
+                .wrap_err("failed to add worktree")?;
+            message::info!("added worktree at {}", worktree.path().display());
+        }
+        WorktreeAction::List => {
+            let worktrees = usecase
+                .list_worktrees(root, query)
+                .map_err(|e| eyre!(e))
+                .wrap_err("failed to list worktrees")?;
+            let mut out = io::stdout().lock();
+            for worktree in worktrees {
+                let branch = worktree.branch.as_deref().unwrap_or("(detached)");
+                let mut notes = vec![];
+                if worktree.locked {
+                    notes.push("locked");
+                }
+                if worktree.prunable {
+                    notes.push("prunable");
+                }
+                write!(out, "{}\t{branch}", worktree.path.display())?;
+                if !notes.is_empty() {
+                    write!(out, "\t{}", notes.join(","))?;
+                }
+                writeln!(out)?;
+            }
+            out.flush()?;
+        }
+        WorktreeAction::Remove { branch, force } => {
+            usecase
+                .remove_worktree(root, query, branch, *force)
+                .map_err(|e| eyre!(e))
+                .wrap_err("failed to remove worktree")?;
+            message::info!(
+                "removed worktree of {} for branch `{branch}`",
+                query.original_query()
+            );
+        }
+    }
+
+    Ok(())
+}
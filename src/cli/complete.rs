@@ -0,0 +1,248 @@
+use std::{
+    borrow::Borrow as _,
+    collections::BTreeSet,
+    env,
+    ffi::{OsStr, OsString},
+    io, iter,
+};
+
+use clap::{CommandFactory as _, FromArgMatches as _};
+use clap_complete::{CompleteEnv, engine::CompletionCandidate};
+use color_eyre::eyre::Result;
+
+use crate::{
+    app_dirs::AppDirs,
+    application::usecase::{Usecases, list::ListOptions},
+    cli::{
+        args::{Args, GlobalArgs},
+        command::list::{input_roots, list_context},
+        context::{app::AppContext, global::GlobalContext},
+    },
+    domain::model::{
+        path_like::PathLike as _,
+        query::{ParseOption, Query},
+        repo::{CanonicalRepo, Repo},
+        root::CanonicalRoot,
+    },
+    infrastructure,
+};
+
+const BIN_NAME: &str = env!("CARGO_BIN_NAME");
+
+/// Handles a completion request from the shell if `var` is set.
+///
+/// Returns `true` if the request was handled.
+pub(crate) fn complete(bin_name: &str, var: &'static str) -> Result<bool> {
+    // Dynamic completion does not support nushell yet, so static completion
+    // is generated instead.
+    if env::var_os(var).is_some_and(|shell| shell == "nushell") {
+        clap_complete::generate(
+            clap_complete_nushell::Nushell,
+            &mut Args::command(),
+            bin_name,
+            &mut io::stdout(),
+        );
+        return Ok(true);
+    }
+
+    let current_dir = env::current_dir().ok();
+    let completed = CompleteEnv::with_factory(Args::command)
+        .var(var)
+        .bin(bin_name)
+        // Generated scripts call the binary found in `PATH`, so that they keep
+        // working when generated with `cargo run`.
+        .completer(bin_name)
+        .try_complete(env::args_os(), current_dir.as_deref())?;
+    Ok(completed)
+}
+
+/// Completes the names of the roots in the configuration file.
+pub(in crate::cli) fn root_names() -> Vec<CompletionCandidate> {
+    let Some(app_ctx) = app_context() else {
+        return vec![];
+    };
+    let Ok(global_ctx) = app_ctx.global() else {
+        return vec![];
+    };
+    global_ctx
+        .root_map()
+        .all_roots()
+        .map(|root| {
+            let path = root.value().root().path().display().to_string();
+            CompletionCandidate::new(root.value().name()).help(Some(path.into()))
+        })
+        .collect()
+}
+
+/// Completes queries of the repositories in souko.
+///
+/// Without a scheme, the scheme prefixes and the repositories reachable with
+/// the default scheme are completed. After a scheme prefix such as `gh:`,
+/// the owners and then the repositories known locally are completed.
+pub(in crate::cli) fn queries(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Some(current) = current.to_str() else {
+        return vec![];
+    };
+    let Some(app_ctx) = app_context() else {
+        return vec![];
+    };
+    let Ok(global_ctx) = app_ctx.global() else {
+        return vec![];
+    };
+    let Ok(query) = global_ctx.query() else {
+        return vec![];
+    };
+    let parse_option = query.parse_option();
+    let repos = local_repos(global_ctx);
+
+    let mut candidates = BTreeSet::new();
+    let mut scheme_help = vec![];
+    match current.split_once(':') {
+        Some((scheme, rest)) if schemes(parse_option).any(|s| s == scheme) => {
+            for (root, repo, path) in query_paths(&repos) {
+                let query = format!("{scheme}:{path}");
+                if !places_repo(&query, parse_option, root, repo) {
+                    continue;
+                }
+                match path.rsplit_once('/') {
+                    Some((owner, _)) if !rest.contains('/') => {
+                        candidates.insert(format!("{scheme}:{owner}/"));
+                    }
+                    _ => {
+                        candidates.insert(query);
+                    }
+                }
+            }
+        }
+        Some(_) => {}
+        None => {
+            scheme_help.extend(
+                parse_option
+                    .custom_scheme
+                    .iter()
+                    .map(|(scheme, template)| (format!("{scheme}:"), template.to_string())),
+            );
+            scheme_help.extend(
+                parse_option
+                    .scheme_alias
+                    .iter()
+                    .map(|(alias, scheme)| (format!("{alias}:"), format!("alias of `{scheme}:`"))),
+            );
+            for (root, repo, path) in query_paths(&repos) {
+                if places_repo(&path, parse_option, root, repo) {
+                    candidates.insert(path);
+                }
+            }
+        }
+    }
+
+    scheme_help.sort();
+    scheme_help
+        .into_iter()
+        .map(|(candidate, help)| CompletionCandidate::new(candidate).help(Some(help.into())))
+        .chain(candidates.into_iter().map(CompletionCandidate::new))
+        .filter(|candidate| {
+            candidate
+                .get_value()
+                .to_str()
+                .is_some_and(|value| value.starts_with(current))
+        })
+        .collect()
+}
+
+fn app_context() -> Option<AppContext> {
+    let args = completing_global_args()?;
+    let app_dirs = AppDirs::new(BIN_NAME).ok()?;
+    let usecases = Usecases::new(&infrastructure::ports());
+    Some(AppContext::new(&args, usecases, app_dirs))
+}
+
+/// Parses the global options, such as `--config`, of the command line being completed.
+///
+/// The shell passes the command line after `--`. It is usually incomplete, so
+/// parse errors are ignored. Options given by environment variables are also
+/// picked up.
+fn completing_global_args() -> Option<GlobalArgs> {
+    let words = env::args_os().skip_while(|arg| arg != "--").skip(1);
+    let words = iter::once(OsString::from(BIN_NAME)).chain(words.skip(1));
+    let matches = Args::command()
+        .ignore_errors(true)
+        .try_get_matches_from(words)
+        .ok()?;
+    GlobalArgs::from_arg_matches(&matches).ok()
+}
+
+/// Returns the repositories in souko.
+///
+/// Walking every root on each completion is slow, so the repositories are
+/// read from the repository cache. Only the roots without cached repositories
+/// are walked.
+fn local_repos(global_ctx: &GlobalContext) -> Vec<(CanonicalRoot, CanonicalRepo)> {
+    let roots = global_ctx
+        .root_map()
+        .all_roots()
+        .cloned()
+        .collect::<Vec<_>>();
+    global_ctx
+        .usecases()
+        .list()
+        .list_repos(
+            input_roots(&roots),
+            list_context(global_ctx),
+            ListOptions::default(),
+        )
+        .filter_map(Result::ok)
+        .filter_map(|list_root| {
+            let root = list_root.root().clone();
+            let mut repos = list_root.cached_repos();
+            if repos.is_empty() {
+                repos = list_root.repos().ok()?.filter_map(Result::ok).collect();
+            }
+            Some(repos.into_iter().map(move |repo| (root.clone(), repo)))
+        })
+        .flatten()
+        .collect()
+}
+
+fn schemes(parse_option: &ParseOption) -> impl Iterator<Item = &str> {
+    parse_option
+        .custom_scheme
+        .keys()
+        .chain(parse_option.scheme_alias.keys())
+        .map(|scheme| -> &str { scheme.borrow() })
+}
+
+/// Returns the paths that may form a query of each repository.
+///
+/// The relative path of a repository usually starts with the host, so the
+/// path without the first component is tried as well as the whole path.
+fn query_paths(
+    repos: &[(CanonicalRoot, CanonicalRepo)],
+) -> impl Iterator<Item = (&CanonicalRoot, &CanonicalRepo, String)> {
+    repos.iter().flat_map(|(root, repo)| {
+        let components = repo
+            .relative_path()
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>();
+        let without_host = (components.len() > 1).then(|| components[1..].join("/"));
+        let whole = components.join("/");
+        without_host
+            .into_iter()
+            .chain([whole])
+            .map(move |path| (root, repo, path))
+    })
+}
+
+/// Returns `true` if `query` is placed at the path of `repo` under `root`.
+fn places_repo(
+    query: &str,
+    parse_option: &ParseOption,
+    root: &CanonicalRoot,
+    repo: &CanonicalRepo,
+) -> bool {
+    Query::parse(query, parse_option)
+        .ok()
+        .and_then(|query| Repo::from_query(root.as_root(), &query, repo.bare()).ok())
+        .is_some_and(|placed| placed.relative_path() == repo.relative_path())
+}
@@ -1,35 +1,60 @@
-use std::{collections::HashMap, str::FromStr as _};
+use std::{
+    cell::Cell,
+    collections::{BTreeMap, HashMap},
+    fmt,
+    str::FromStr,
+};
 
-use serde::Deserialize;
+use color_eyre::eyre::{Report, bail};
+use serde::{
+    Deserialize, Serialize, Serializer,
+    de::{self, DeserializeSeed, IntoDeserializer as _, MapAccess, SeqAccess, Visitor},
+    forward_to_deserialize_any,
+};
+use toml_edit::{Array, DocumentMut, InlineTable, Key, Value, de::Deserializer};
 
 use super::input::unresolved_path::UnresolvedPath;
 use crate::domain::model::{
-    query::CustomSchemeTemplateContext, scheme::Scheme, template::Template,
+    layout::DEFAULT_LOCAL_PREFIX, query::CustomSchemeTemplateContext,
+    repo_url::RepoUrlTemplateContext, scheme::Scheme, template::Template,
+    worktree::WorktreePathTemplateContext,
 };
 
 pub(in crate::cli) const DEFAULT_ROOT_NAME: &str = "default";
 
-#[derive(Debug, Clone, Default, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub(in crate::cli) struct Config {
     #[serde(rename = "root", default)]
     pub(in crate::cli) roots: Vec<RootConfig>,
+    #[serde(rename = "route", default)]
+    pub(in crate::cli) routes: Vec<RouteConfig>,
     #[serde(default)]
     pub(in crate::cli) query: QueryConfig,
+    #[serde(default)]
+    pub(in crate::cli) clone: CloneConfig,
+    #[serde(default)]
+    pub(in crate::cli) worktree: WorktreeConfig,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub(in crate::cli) struct RootConfig {
     pub(in crate::cli) name: String,
     #[serde(default)]
     pub(in crate::cli) path: Option<UnresolvedPath>,
     #[serde(default)]
+    pub(in crate::cli) layout: Option<Template<RepoUrlTemplateContext>>,
+    #[serde(default = "default_local_prefix")]
+    pub(in crate::cli) local_prefix: String,
+    #[serde(default)]
     pub(in crate::cli) visit_hidden_dirs: bool,
     #[serde(default)]
     pub(in crate::cli) visit_repo_subdirs: bool,
     #[serde(default)]
     pub(in crate::cli) include_bare_repo: bool,
+    #[serde(default = "default_include_linked_worktrees")]
+    pub(in crate::cli) include_linked_worktrees: bool,
+    #[serde(default)]
+    pub(in crate::cli) follow_symlinks: bool,
 }
 
 impl RootConfig {
@@ -37,27 +62,105 @@ impl RootConfig {
         Self {
             name: DEFAULT_ROOT_NAME.to_owned(),
             path: None,
+            layout: None,
+            local_prefix: default_local_prefix(),
             visit_hidden_dirs: false,
             visit_repo_subdirs: false,
             include_bare_repo: false,
+            include_linked_worktrees: default_include_linked_worktrees(),
+            follow_symlinks: false,
         }
     }
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(in crate::cli) struct RouteConfig {
+    pub(in crate::cli) root: String,
+    #[serde(default)]
+    pub(in crate::cli) host: Option<String>,
+    #[serde(default)]
+    pub(in crate::cli) url: Option<String>,
+}
+
+/// Serializes a map in the order of its keys, so that the output is stable.
+fn serialize_sorted<S, K, V>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    K: Serialize + Ord,
+    V: Serialize,
+{
+    serializer.collect_map(map.iter().collect::<BTreeMap<_, _>>())
+}
+
+fn default_local_prefix() -> String {
+    DEFAULT_LOCAL_PREFIX.to_owned()
+}
+
+fn default_include_linked_worktrees() -> bool {
+    true
+}
+
 fn default_scheme() -> Scheme {
     Scheme::from_str("gh").unwrap()
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub(in crate::cli) struct QueryConfig {
     #[serde(default = "default_scheme")]
     pub(in crate::cli) default_scheme: Scheme,
-    #[serde(default)]
+    #[serde(default, serialize_with = "serialize_sorted")]
     pub(in crate::cli) scheme_alias: HashMap<Scheme, Scheme>,
-    #[serde(default)]
+    #[serde(default, serialize_with = "serialize_sorted")]
     pub(in crate::cli) custom_scheme:
         HashMap<Scheme, Template<CustomSchemeTemplateContext<'static>>>,
+    #[serde(default, serialize_with = "serialize_sorted")]
+    pub(in crate::cli) custom_scheme_remote:
+        HashMap<Scheme, BTreeMap<String, Template<CustomSchemeTemplateContext<'static>>>>,
+    #[serde(default)]
+    pub(in crate::cli) url_rewrite: BTreeMap<String, UrlRewriteConfig>,
+    #[serde(default)]
+    pub(in crate::cli) use_git_url_rewrites: bool,
+    #[serde(default, serialize_with = "serialize_sorted")]
+    pub(in crate::cli) host_alias: HashMap<String, String>,
+    #[serde(default)]
+    pub(in crate::cli) use_ssh_config: bool,
+    #[serde(default)]
+    pub(in crate::cli) normalize: NormalizeConfig,
+    #[serde(default, serialize_with = "serialize_sorted")]
+    pub(in crate::cli) scheme_normalize: HashMap<Scheme, SchemeNormalizeConfig>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+pub(in crate::cli) struct NormalizeConfig {
+    #[serde(default)]
+    pub(in crate::cli) lowercase_host: bool,
+    #[serde(default)]
+    pub(in crate::cli) lowercase_path: bool,
+    #[serde(default)]
+    pub(in crate::cli) strip_git_suffix: bool,
+    #[serde(default)]
+    pub(in crate::cli) remove_userinfo: bool,
+}
+
+/// Per-scheme normalization; unspecified options inherit `[query.normalize]`.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+pub(in crate::cli) struct SchemeNormalizeConfig {
+    #[serde(default)]
+    pub(in crate::cli) lowercase_host: Option<bool>,
+    #[serde(default)]
+    pub(in crate::cli) lowercase_path: Option<bool>,
+    #[serde(default)]
+    pub(in crate::cli) strip_git_suffix: Option<bool>,
+    #[serde(default)]
+    pub(in crate::cli) remove_userinfo: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(in crate::cli) struct UrlRewriteConfig {
+    #[serde(default)]
+    pub(in crate::cli) instead_of: Vec<String>,
+    #[serde(default)]
+    pub(in crate::cli) push_instead_of: Vec<String>,
 }
 
 impl Default for QueryConfig {
@@ -66,6 +169,556 @@ impl Default for QueryConfig {
             default_scheme: default_scheme(),
             scheme_alias: Default::default(),
             custom_scheme: Default::default(),
+            custom_scheme_remote: Default::default(),
+            url_rewrite: Default::default(),
+            use_git_url_rewrites: false,
+            host_alias: Default::default(),
+            use_ssh_config: false,
+            normalize: Default::default(),
+            scheme_normalize: Default::default(),
+        }
+    }
+}
+
+fn default_worktree_path_template() -> Template<WorktreePathTemplateContext> {
+    Template::from_str("{repo_relative_path}@{branch_slug}").unwrap()
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub(in crate::cli) struct CloneConfig {
+    #[serde(default)]
+    pub(in crate::cli) push_url_template: Option<Template<RepoUrlTemplateContext>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(in crate::cli) struct WorktreeConfig {
+    #[serde(default = "default_worktree_path_template")]
+    pub(in crate::cli) path_template: Template<WorktreePathTemplateContext>,
+}
+
+impl Default for WorktreeConfig {
+    fn default() -> Self {
+        Self {
+            path_template: default_worktree_path_template(),
+        }
+    }
+}
+
+/// Deserializes the configuration document, ignoring unknown fields.
+///
+/// Returns the keys of the ignored fields, such as `root[0].nmae`, with the
+/// result. If deserialization fails, only the fields ignored before the error
+/// are returned.
+pub(in crate::cli) fn deserialize(
+    doc: &DocumentMut,
+) -> (Vec<ConfigKey>, Result<Config, toml_edit::de::Error>) {
+    let mut unknown_fields = vec![];
+    let result = serde_ignored::deserialize(Deserializer::from(doc.clone()), |path| {
+        unknown_fields.push(ignored_key(&path));
+    });
+    (unknown_fields, result)
+}
+
+/// Deserializes the configuration document, failing on unknown fields.
+pub(in crate::cli) fn from_document(doc: &DocumentMut) -> Result<Config, Report> {
+    let (unknown_fields, result) = deserialize(doc);
+    if let Some(key) = unknown_fields.first() {
+        match key.suggestion() {
+            Some(suggestion) => bail!("unknown field `{key}` (did you mean `{suggestion}`?)"),
+            None => bail!("unknown field `{key}`"),
+        }
+    }
+    Ok(result?)
+}
+
+fn ignored_key(path: &serde_ignored::Path<'_>) -> ConfigKey {
+    match path {
+        serde_ignored::Path::Root => ConfigKey::default(),
+        serde_ignored::Path::Seq { parent, index } => ignored_key(parent).element(*index),
+        serde_ignored::Path::Map { parent, key } => ignored_key(parent).child(key),
+        serde_ignored::Path::Some { parent }
+        | serde_ignored::Path::NewtypeStruct { parent }
+        | serde_ignored::Path::NewtypeVariant { parent } => ignored_key(parent),
+    }
+}
+
+/// Returns the names of the fields of the table that `segments` names, taken
+/// from the schema of [`Config`].
+///
+/// Returns `None` if the segments do not name a table with fixed fields, such
+/// as `query.scheme_alias`.
+fn field_names(segments: &[KeySegment]) -> Option<&'static [&'static str]> {
+    let found = Cell::new(None);
+    let _ = Config::deserialize(FieldNamesProbe {
+        segments,
+        array: false,
+        found: &found,
+    });
+    found.get()
+}
+
+/// Deserializer that follows the segments of a key, and records the field
+/// names of the struct at the end instead of deserializing it.
+struct FieldNamesProbe<'a> {
+    /// Segments below the value being deserialized.
+    segments: &'a [KeySegment],
+    /// Whether the value is an array whose element is followed.
+    array: bool,
+    found: &'a Cell<Option<&'static [&'static str]>>,
+}
+
+impl<'a> FieldNamesProbe<'a> {
+    /// Returns a map with only the field of the next segment.
+    fn next_field(self) -> Option<ProbeMap<'a>> {
+        let [segment, rest @ ..] = self.segments else {
+            return None;
+        };
+        (!self.array).then_some(ProbeMap {
+            key: Some(&segment.name),
+            value: Some(Self {
+                segments: rest,
+                array: segment.index.is_some(),
+                found: self.found,
+            }),
+        })
+    }
+}
+
+impl<'de> de::Deserializer<'de> for FieldNamesProbe<'_> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(de::Error::custom("not a table with fixed fields"))
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if !self.array {
+            return self.deserialize_any(visitor);
+        }
+        visitor.visit_seq(ProbeSeq(Some(Self {
+            array: false,
+            ..self
+        })))
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.next_field() {
+            Some(map) => visitor.visit_map(map),
+            None => Err(de::Error::custom("not a table with fixed fields")),
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.segments.is_empty() && !self.array {
+            self.found.set(Some(fields));
+        }
+        self.deserialize_map(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct tuple tuple_struct enum identifier ignored_any
+    }
+}
+
+struct ProbeMap<'a> {
+    key: Option<&'a str>,
+    value: Option<FieldNamesProbe<'a>>,
+}
+
+impl<'de> MapAccess<'de> for ProbeMap<'_> {
+    type Error = de::value::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        self.key
+            .take()
+            .map(|key| seed.deserialize(key.into_deserializer()))
+            .transpose()
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.value.take().expect("value follows its key"))
+    }
+}
+
+struct ProbeSeq<'a>(Option<FieldNamesProbe<'a>>);
+
+impl<'de> SeqAccess<'de> for ProbeSeq<'_> {
+    type Error = de::value::Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.0
+            .take()
+            .map(|probe| seed.deserialize(probe))
+            .transpose()
+    }
+}
+
+fn field_path(parent: &str, name: &str) -> String {
+    let name = Key::new(name).display_repr().into_owned();
+    if parent.is_empty() {
+        name
+    } else {
+        format!("{parent}.{name}")
+    }
+}
+
+/// A dotted key of the configuration file, such as `root[0].path`.
+///
+/// Segments are bare or double-quoted TOML keys, each optionally followed by
+/// an array index.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(in crate::cli) struct ConfigKey {
+    segments: Vec<KeySegment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(in crate::cli) struct KeySegment {
+    pub(in crate::cli) name: String,
+    pub(in crate::cli) index: Option<usize>,
+}
+
+impl ConfigKey {
+    pub(in crate::cli) fn segments(&self) -> &[KeySegment] {
+        &self.segments
+    }
+
+    /// Returns the key of the field `name` in the table this key names.
+    pub(in crate::cli) fn child(&self, name: &str) -> Self {
+        let mut segments = self.segments.clone();
+        segments.push(KeySegment {
+            name: name.to_owned(),
+            index: None,
+        });
+        Self { segments }
+    }
+
+    /// Returns the key of the `index`-th element of the array this key names.
+    pub(in crate::cli) fn element(&self, index: usize) -> Self {
+        let mut segments = self.segments.clone();
+        if let Some(last) = segments.last_mut() {
+            last.index = Some(index);
+        }
+        Self { segments }
+    }
+
+    /// Returns `true` if the key names a field known to souko.
+    pub(in crate::cli) fn is_known(&self) -> bool {
+        // Deserializes a document that only has this key, and checks that the
+        // key is not ignored. Elements of an array share the same fields, so
+        // every index is replaced with the first one.
+        let mut value = Value::InlineTable(InlineTable::new());
+        for segment in self.segments.iter().rev() {
+            if segment.index.is_some() {
+                value = Value::Array(Array::from_iter([value]));
+            }
+            let mut table = InlineTable::new();
+            table.insert(&segment.name, value);
+            value = Value::InlineTable(table);
+        }
+        let Value::InlineTable(table) = value else {
+            unreachable!("the key has at least one segment");
+        };
+        let (unknown_fields, _) = deserialize(&DocumentMut::from(table.into_table()));
+        let segments = self
+            .segments
+            .iter()
+            .map(|segment| KeySegment {
+                name: segment.name.clone(),
+                index: segment.index.map(|_| 0),
+            })
+            .collect::<Vec<_>>();
+        !unknown_fields
+            .iter()
+            .any(|unknown| segments.starts_with(unknown.segments()))
+    }
+
+    /// Returns the known key most similar to this key, differing only in the
+    /// last segment, such as `root[0].name` for `root[0].nmae`.
+    pub(in crate::cli) fn suggestion(&self) -> Option<Self> {
+        let (last, parent) = self.segments.split_last()?;
+        let (name, _) = field_names(parent)?
+            .iter()
+            .map(|name| (name, strsim::jaro(&last.name, name)))
+            .filter(|(_, similarity)| *similarity > 0.7)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+        let mut segments = self.segments.clone();
+        segments.last_mut()?.name = (*name).to_owned();
+        Some(Self { segments })
+    }
+}
+
+impl FromStr for ConfigKey {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut segments = vec![];
+        let mut rest = s;
+        loop {
+            let name;
+            if let Some(quoted) = rest.strip_prefix('"') {
+                let Some(end) = quoted.find('"') else {
+                    bail!("invalid key `{s}`: unterminated quoted segment");
+                };
+                name = quoted[..end].to_owned();
+                rest = &quoted[end + 1..];
+            } else {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+                    .unwrap_or(rest.len());
+                if end == 0 {
+                    bail!("invalid key `{s}`: empty segment");
+                }
+                name = rest[..end].to_owned();
+                rest = &rest[end..];
+            }
+            let mut index = None;
+            if let Some(indexed) = rest.strip_prefix('[') {
+                let Some((digits, tail)) = indexed.split_once(']') else {
+                    bail!("invalid key `{s}`: unterminated index");
+                };
+                let Ok(i) = digits.parse() else {
+                    bail!("invalid key `{s}`: invalid index `{digits}`");
+                };
+                index = Some(i);
+                rest = tail;
+            }
+            segments.push(KeySegment { name, index });
+            match rest.strip_prefix('.') {
+                Some(tail) => rest = tail,
+                None if rest.is_empty() => break,
+                None => bail!("invalid key `{s}`: unexpected `{rest}`"),
+            }
+        }
+        Ok(Self { segments })
+    }
+}
+
+impl fmt::Display for ConfigKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut path = String::new();
+        for segment in &self.segments {
+            path = field_path(&path, &segment.name);
+            if let Some(index) = segment.index {
+                path = format!("{path}[{index}]");
+            }
+        }
+        f.write_str(&path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_accepts_every_known_field() {
+        let input = r#"
+            [[root]]
+            name = "default"
+            path = "~/src"
+            layout = "{host}/{path}"
+            local_prefix = "local"
+            visit_hidden_dirs = false
+            visit_repo_subdirs = false
+            include_bare_repo = false
+            include_linked_worktrees = true
+            follow_symlinks = false
+
+            [[route]]
+            root = "default"
+            host = "github.com"
+
+            [query]
+            default_scheme = "gh"
+            use_git_url_rewrites = false
+            use_ssh_config = false
+            scheme_alias = { foo = "github" }
+            custom_scheme = { bar = "https://example.com/{path}.git" }
+            host_alias = { gh-work = "github.com" }
+
+            [query.url_rewrite."git@github.com:"]
+            instead_of = ["https://github.com/"]
+            push_instead_of = []
+
+            [query.normalize]
+            lowercase_host = true
+            lowercase_path = false
+            strip_git_suffix = true
+            remove_userinfo = true
+
+            [query.scheme_normalize.github]
+            lowercase_path = true
+
+            [clone]
+            push_url_template = "git@{host}:{path}.git"
+
+            [worktree]
+            path_template = "{repo_relative_path}@{branch_slug}"
+        "#;
+        let doc = input.parse::<DocumentMut>().unwrap();
+        let (unknown_fields, result) = deserialize(&doc);
+        assert_eq!(unknown_fields, []);
+        result.unwrap();
+    }
+
+    #[test]
+    fn config_key_parses_quoted_segments_and_indices() {
+        let key =
+            ConfigKey::from_str(r#"query.url_rewrite."https://example.com/".instead_of"#).unwrap();
+        assert_eq!(
+            key.segments()
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect::<Vec<_>>(),
+            ["query", "url_rewrite", "https://example.com/", "instead_of"]
+        );
+        assert_eq!(
+            key.to_string(),
+            r#"query.url_rewrite."https://example.com/".instead_of"#
+        );
+
+        let key = ConfigKey::from_str("root[1].path").unwrap();
+        assert_eq!(key.segments()[0].index, Some(1));
+        assert_eq!(key.to_string(), "root[1].path");
+
+        for invalid in ["", "root.", "root[x]", "root[0", "\"unterminated", "a b"] {
+            assert!(ConfigKey::from_str(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn config_key_is_known_follows_the_schema() {
+        for known in [
+            "root",
+            "root[0].path",
+            "query.default_scheme",
+            "query.scheme_alias.gl",
+            r#"query.url_rewrite."git@github.com:".instead_of"#,
+            "query.scheme_normalize.github.lowercase_path",
+            "worktree.path_template",
+        ] {
+            assert!(ConfigKey::from_str(known).unwrap().is_known(), "{known}");
+        }
+        for unknown in [
+            "typo",
+            "root[0].nmae",
+            "root[3].nmae",
+            "query.normalize.lowercase",
+            "query.url_rewrite.base.instead",
+        ] {
+            assert!(
+                !ConfigKey::from_str(unknown).unwrap().is_known(),
+                "{unknown}"
+            );
+        }
+    }
+
+    #[test]
+    fn deserialize_reports_every_unknown_field() {
+        let input = r#"
+            unknown = 1
+
+            [[root]]
+            name = "default"
+            nmae = "typo"
+
+            [query]
+            scheme_normalize = { github = { lowercase = true } }
+
+            [query.url_rewrite."https://example.com/"]
+            instead = ["x"]
+
+            [worktree]
+            path_template = "{repo_relative_path}"
+            extra = true
+        "#;
+        let doc = input.parse::<DocumentMut>().unwrap();
+        let (unknown_fields, result) = deserialize(&doc);
+        assert_eq!(
+            unknown_fields
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            [
+                "unknown",
+                "root[0].nmae",
+                "query.scheme_normalize.github.lowercase",
+                "query.url_rewrite.\"https://example.com/\".instead",
+                "worktree.extra",
+            ]
+        );
+        result.unwrap();
+    }
+
+    #[test]
+    fn config_key_suggests_the_most_similar_known_key() {
+        for (unknown, suggestion) in [
+            ("roots", Some("root")),
+            ("root[2].nmae", Some("root[2].name")),
+            ("route[0].hots", Some("route[0].host")),
+            ("query.defualt_scheme", Some("query.default_scheme")),
+            (
+                "query.url_rewrite.base.instead",
+                Some("query.url_rewrite.base.instead_of"),
+            ),
+            ("worktree.path_templte", Some("worktree.path_template")),
+            ("xyz", None),
+            ("query.scheme_alias.gh.x", None),
+        ] {
+            assert_eq!(
+                ConfigKey::from_str(unknown)
+                    .unwrap()
+                    .suggestion()
+                    .map(|key| key.to_string())
+                    .as_deref(),
+                suggestion,
+                "{unknown}"
+            );
         }
     }
 }
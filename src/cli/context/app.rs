@@ -0,0 +1,98 @@
+use std::{cell::OnceCell, rc::Rc};
+
+use color_eyre::eyre::{Result, WrapErr as _, bail};
+
+use crate::{
+    app_dirs::AppDirs,
+    application::usecase::Usecases,
+    cli::{
+        args::GlobalArgs,
+        config::{self, Config},
+        context::global::GlobalContext,
+        input::app_param::AppParam,
+    },
+    domain::model::{path_buf_pair::PathBufPair, path_like::PathLike as _},
+    util::file,
+};
+
+/// Context shared by every subcommand, available without loading the
+/// configuration file.
+#[derive(Debug)]
+pub(in crate::cli) struct AppContext {
+    usecases: Rc<Usecases>,
+    app_dirs: AppDirs,
+    config_path: AppParam<PathBufPair>,
+    repo_cache_path: AppParam<PathBufPair>,
+    visit_history_path: AppParam<PathBufPair>,
+    global: OnceCell<GlobalContext>,
+}
+
+impl AppContext {
+    pub(in crate::cli) fn new(args: &GlobalArgs, usecases: Usecases, app_dirs: AppDirs) -> Self {
+        let config_path = args.config_path(&app_dirs);
+        let config_path = config_path
+            .as_ref()
+            .map(|path| path.normalize(config_path.source(), &app_dirs));
+        let repo_cache_path = args.repo_cache_path(&app_dirs);
+        let repo_cache_path = repo_cache_path
+            .as_ref()
+            .map(|path| path.normalize(repo_cache_path.source(), &app_dirs));
+        let visit_history_path = args.visit_history_path(&app_dirs);
+        let visit_history_path = visit_history_path
+            .as_ref()
+            .map(|path| path.normalize(visit_history_path.source(), &app_dirs));
+        Self {
+            usecases: Rc::new(usecases),
+            app_dirs,
+            config_path,
+            repo_cache_path,
+            visit_history_path,
+            global: OnceCell::new(),
+        }
+    }
+
+    pub(in crate::cli) fn usecases(&self) -> &Rc<Usecases> {
+        &self.usecases
+    }
+
+    pub(in crate::cli) fn app_dirs(&self) -> &AppDirs {
+        &self.app_dirs
+    }
+
+    pub(in crate::cli) fn config_path(&self) -> &AppParam<PathBufPair> {
+        &self.config_path
+    }
+
+    pub(in crate::cli) fn repo_cache_path(&self) -> &AppParam<PathBufPair> {
+        &self.repo_cache_path
+    }
+
+    pub(in crate::cli) fn visit_history_path(&self) -> &AppParam<PathBufPair> {
+        &self.visit_history_path
+    }
+
+    /// Returns the context built from the configuration file.
+    ///
+    /// The configuration file is loaded on first use, so that the subcommands
+    /// that check or edit it work even if it is broken.
+    pub(in crate::cli) fn global(&self) -> Result<&GlobalContext> {
+        if let Some(global) = self.global.get() {
+            return Ok(global);
+        }
+        let global = GlobalContext::new(self, load_config(&self.config_path)?);
+        Ok(self.global.get_or_init(|| global))
+    }
+}
+
+fn load_config(path: &AppParam<PathBufPair>) -> Result<Config> {
+    match file::load_toml_document("configuration file", path.value())? {
+        Some(doc) => config::from_document(&doc).wrap_err_with(|| {
+            format!(
+                "failed to parse configuration file: {}",
+                path.value().display()
+            )
+        }),
+        None if path.source().is_implicit_default() => Ok(Config::default()),
+        None => bail!("config file not found: {}", path.value().display()),
+    }
+}
@@ -1,34 +1,90 @@
-use color_eyre::eyre::{Result, WrapErr as _};
+use std::collections::HashSet;
+
+use color_eyre::eyre::{Result, WrapErr as _, bail};
 
 use crate::{
+    application::usecase::clone::CloneOptions,
     cli::{
         args::clone::CloneArgs,
         context::{global::GlobalContext, root::RootContext},
         input::app_param::AppParam,
     },
-    domain::model::query::Query,
+    domain::model::{
+        query::{ParseOption, Query},
+        remote::ORIGIN_REMOTE_NAME,
+    },
 };
 
 #[derive(Debug)]
 pub(in crate::cli) struct CloneContext {
     root: AppParam<RootContext>,
     query: Query,
+    options: CloneOptions,
+    selection_reason: String,
+    explain: bool,
 }
 
 impl CloneContext {
     pub(in crate::cli) fn new(global_ctx: &GlobalContext, args: &CloneArgs) -> Result<Self> {
-        let root = match args.root_name() {
-            Some(name) => global_ctx.root_map().root_by_name_or_err(name)?,
-            None => global_ctx.root_map().default_root(),
+        let query_parse_option = global_ctx.query()?.parse_option();
+        let query = parse_query(args.query(), query_parse_option)?;
+
+        let selection =
+            global_ctx
+                .route()?
+                .select_root(global_ctx.root_map(), args.root_name(), &query)?;
+        let root = selection.root.clone();
+        let selection_reason = selection.reason.to_string();
+
+        let mut extra_remotes = vec![];
+        if let Some(upstream) = args.upstream() {
+            extra_remotes.push(("upstream", upstream));
+        }
+        for remote in args.remotes() {
+            let Some((name, query)) = remote.split_once('=') else {
+                bail!("invalid remote `{remote}`: expected `NAME=QUERY`");
+            };
+            extra_remotes.push((name, query));
+        }
+        let mut remote_names = HashSet::from([ORIGIN_REMOTE_NAME]);
+        let mut extra_remotes = extra_remotes
+            .into_iter()
+            .map(|(name, query)| {
+                if name.is_empty() {
+                    bail!("remote name must not be empty");
+                }
+                if !remote_names.insert(name) {
+                    bail!("remote `{name}` is specified more than once");
+                }
+                Ok((name.to_owned(), parse_query(query, query_parse_option)?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        // remotes of the custom scheme, overridden by the command line
+        for (name, remote_query) in query.remotes() {
+            if name == ORIGIN_REMOTE_NAME {
+                bail!(
+                    "custom scheme of `{}` must not add `{name}` remote",
+                    query.original_query()
+                );
+            }
+            if remote_names.insert(name) {
+                extra_remotes.push((name.clone(), parse_query(remote_query, query_parse_option)?));
+            }
         }
-        .clone();
 
-        let query_parse_option = global_ctx.query().parse_option();
-        let query_str = args.query();
-        let query = Query::parse(query_str, query_parse_option)
-            .wrap_err_with(|| format!("invalid query: {query_str}"))?;
+        let options = CloneOptions {
+            bare: false,
+            push_url_template: global_ctx.clone_push_url_template().cloned(),
+            extra_remotes,
+        };
 
-        Ok(Self { root, query })
+        Ok(Self {
+            root,
+            query,
+            options,
+            selection_reason,
+            explain: args.explain(),
+        })
     }
 
     pub(in crate::cli) fn root_context(&self) -> &AppParam<RootContext> {
@@ -38,4 +94,20 @@ impl CloneContext {
     pub(in crate::cli) fn query(&self) -> &Query {
         &self.query
     }
+
+    pub(in crate::cli) fn options(&self) -> &CloneOptions {
+        &self.options
+    }
+
+    pub(in crate::cli) fn selection_reason(&self) -> &str {
+        &self.selection_reason
+    }
+
+    pub(in crate::cli) fn explain(&self) -> bool {
+        self.explain
+    }
+}
+
+fn parse_query(query: &str, option: &ParseOption) -> Result<Query> {
+    Query::parse(query, option).wrap_err_with(|| format!("invalid query: {query}"))
 }
@@ -0,0 +1,18 @@
+use crate::cli::args::config::{ConfigArgs, ConfigSubcommand};
+
+#[derive(Debug)]
+pub(in crate::cli) struct ConfigContext {
+    subcommand: ConfigSubcommand,
+}
+
+impl ConfigContext {
+    pub(in crate::cli) fn new(args: &ConfigArgs) -> Self {
+        Self {
+            subcommand: args.subcommand().clone(),
+        }
+    }
+
+    pub(in crate::cli) fn subcommand(&self) -> &ConfigSubcommand {
+        &self.subcommand
+    }
+}
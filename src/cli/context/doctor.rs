@@ -0,0 +1,16 @@
+use crate::cli::args::doctor::DoctorArgs;
+
+#[derive(Debug)]
+pub(in crate::cli) struct DoctorContext {
+    fix: bool,
+}
+
+impl DoctorContext {
+    pub(in crate::cli) fn new(args: &DoctorArgs) -> Self {
+        Self { fix: args.fix() }
+    }
+
+    pub(in crate::cli) fn fix(&self) -> bool {
+        self.fix
+    }
+}
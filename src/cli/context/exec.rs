@@ -0,0 +1,70 @@
+use std::{ffi::OsString, num::NonZeroUsize};
+
+use color_eyre::eyre::Result;
+
+use crate::cli::{
+    args::{
+        exec::{ExecArgs, ExecOutput},
+        list::Duplicates,
+    },
+    context::{global::GlobalContext, root::RootContext},
+    input::app_param::AppParam,
+};
+
+#[derive(Debug)]
+pub(in crate::cli) struct ExecContext {
+    roots: Vec<AppParam<RootContext>>,
+    duplicates: Duplicates,
+    jobs: NonZeroUsize,
+    output: ExecOutput,
+    fail_fast: bool,
+    program: OsString,
+    args: Vec<OsString>,
+}
+
+impl ExecContext {
+    pub(in crate::cli) fn new(global_ctx: &GlobalContext, args: &ExecArgs) -> Result<Self> {
+        let roots = global_ctx.root_map().roots_by_names(args.root_name())?;
+        let (program, command_args) = args
+            .command()
+            .split_first()
+            .expect("clap requires at least one command argument");
+        Ok(Self {
+            roots,
+            duplicates: args.duplicates(),
+            jobs: args.jobs(),
+            output: args.output(),
+            fail_fast: args.fail_fast(),
+            program: program.clone(),
+            args: command_args.to_vec(),
+        })
+    }
+
+    pub(in crate::cli) fn roots(&self) -> &[AppParam<RootContext>] {
+        &self.roots
+    }
+
+    pub(in crate::cli) fn duplicates(&self) -> Duplicates {
+        self.duplicates
+    }
+
+    pub(in crate::cli) fn jobs(&self) -> NonZeroUsize {
+        self.jobs
+    }
+
+    pub(in crate::cli) fn output(&self) -> ExecOutput {
+        self.output
+    }
+
+    pub(in crate::cli) fn fail_fast(&self) -> bool {
+        self.fail_fast
+    }
+
+    pub(in crate::cli) fn program(&self) -> &OsString {
+        &self.program
+    }
+
+    pub(in crate::cli) fn args(&self) -> &[OsString] {
+        &self.args
+    }
+}
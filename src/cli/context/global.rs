@@ -1,45 +1,61 @@
-use color_eyre::eyre::{Result, bail};
+use std::{cell::OnceCell, collections::HashMap, path::PathBuf, rc::Rc};
+
+use color_eyre::eyre::{Result, WrapErr as _, eyre};
 
 use crate::{
-    app_dirs::AppDirs,
     application::usecase::Usecases,
     cli::{
-        args::Args,
-        config::Config,
-        context::{query::QueryContext, root::RootContextMap},
-        input::app_param::AppParam,
+        config::{Config, QueryConfig, RouteConfig},
+        context::{
+            app::AppContext,
+            query::{QueryContext, QueryEnvironment},
+            root::RootContextMap,
+            route::RouteContext,
+        },
+    },
+    domain::model::{
+        path_buf_pair::PathBufPair, repo_url::RepoUrlTemplateContext, template::Template,
+        worktree::WorktreePathTemplateContext,
     },
-    domain::model::{path_buf_pair::PathBufPair, path_like::PathLike as _},
-    util::file,
 };
 
 #[derive(Debug)]
 pub(in crate::cli) struct GlobalContext {
-    usecases: Usecases,
+    usecases: Rc<Usecases>,
     root_map: RootContextMap,
-    query: QueryContext,
+    route_configs: Vec<RouteConfig>,
+    route: OnceCell<RouteContext>,
+    query_config: QueryConfig,
+    ssh_config_path: PathBuf,
+    working_dir: PathBuf,
+    query: OnceCell<QueryContext>,
+    clone_push_url_template: Option<Template<RepoUrlTemplateContext>>,
+    worktree_path_template: Template<WorktreePathTemplateContext>,
     repo_cache_path: PathBufPair,
+    visit_history_path: PathBufPair,
 }
 
 impl GlobalContext {
-    pub(in crate::cli) fn new(args: &Args, usecases: Usecases, app_dirs: AppDirs) -> Result<Self> {
-        let config_path = args.global_args().config_path(&app_dirs);
-        let config_path = config_path
-            .as_ref()
-            .map(|path| path.normalize(config_path.source(), &app_dirs));
-        let config = load_config(&config_path)?;
-        let root_map = RootContextMap::new(config_path.value(), &config.roots, &app_dirs);
-        let query = QueryContext::from_config(&config.query);
-        let repo_cache_path = args.global_args().repo_cache_path(&app_dirs);
-        let repo_cache_path = repo_cache_path
-            .value()
-            .normalize(repo_cache_path.source(), &app_dirs);
-        Ok(Self {
-            usecases,
+    pub(in crate::cli) fn new(app_ctx: &AppContext, config: Config) -> Self {
+        let app_dirs = app_ctx.app_dirs();
+        let config_path = app_ctx.config_path();
+        let root_map = RootContextMap::new(config_path.value(), &config.roots, app_dirs);
+        let clone_push_url_template = config.clone.push_url_template.clone();
+        let worktree_path_template = config.worktree.path_template.clone();
+        Self {
+            usecases: Rc::clone(app_ctx.usecases()),
             root_map,
-            query,
-            repo_cache_path,
-        })
+            route_configs: config.routes,
+            route: OnceCell::new(),
+            query_config: config.query,
+            ssh_config_path: app_dirs.home_dir().join(".ssh/config"),
+            working_dir: app_dirs.working_dir().to_owned(),
+            query: OnceCell::new(),
+            clone_push_url_template,
+            worktree_path_template,
+            repo_cache_path: app_ctx.repo_cache_path().value().clone(),
+            visit_history_path: app_ctx.visit_history_path().value().clone(),
+        }
     }
 
     pub(in crate::cli) fn usecases(&self) -> &Usecases {
@@ -50,19 +66,72 @@ impl GlobalContext {
         &self.repo_cache_path
     }
 
+    pub(in crate::cli) fn visit_history_path(&self) -> &PathBufPair {
+        &self.visit_history_path
+    }
+
     pub(in crate::cli) fn root_map(&self) -> &RootContextMap {
         &self.root_map
     }
 
-    pub(in crate::cli) fn query(&self) -> &QueryContext {
-        &self.query
+    /// Returns the rules that select the root for a repository.
+    ///
+    /// The rules are validated on first use, so that an invalid `[[route]]`
+    /// entry only breaks the subcommands that select a root.
+    pub(in crate::cli) fn route(&self) -> Result<&RouteContext> {
+        if let Some(route) = self.route.get() {
+            return Ok(route);
+        }
+        let route = RouteContext::from_config(&self.route_configs, &self.root_map)?;
+        Ok(self.route.get_or_init(|| route))
+    }
+
+    /// Returns the options to parse queries.
+    ///
+    /// The git and ssh configurations are read on first use, so that the
+    /// subcommands without queries, such as `souko visit` run on every
+    /// directory change, do not read them.
+    pub(in crate::cli) fn query(&self) -> Result<&QueryContext> {
+        if let Some(query) = self.query.get() {
+            return Ok(query);
+        }
+        let config = &self.query_config;
+        let git_url_rewrites = if config.use_git_url_rewrites {
+            self.usecases
+                .query()
+                .git_url_rewrites()
+                .map_err(|e| eyre!(e))
+                .wrap_err("failed to read URL rewrite rules from git config")?
+        } else {
+            vec![]
+        };
+        let ssh_host_aliases = if config.use_ssh_config {
+            self.usecases
+                .query()
+                .ssh_host_aliases(&self.ssh_config_path)
+                .map_err(|e| eyre!(e))
+                .wrap_err("failed to read host aliases from ssh config")?
+        } else {
+            HashMap::new()
+        };
+        let query = QueryContext::from_config(
+            config,
+            QueryEnvironment {
+                git_url_rewrites,
+                ssh_host_aliases,
+                working_dir: Some(self.working_dir.clone()),
+            },
+        );
+        Ok(self.query.get_or_init(|| query))
+    }
+
+    pub(in crate::cli) fn clone_push_url_template(
+        &self,
+    ) -> Option<&Template<RepoUrlTemplateContext>> {
+        self.clone_push_url_template.as_ref()
     }
-}
 
-fn load_config(path: &AppParam<PathBufPair>) -> Result<Config> {
-    match file::load_toml("configuration file", path.value())? {
-        Some(config) => Ok(config),
-        None if path.source().is_implicit_default() => Ok(Config::default()),
-        None => bail!("config file not found: {}", path.value().display()),
+    pub(in crate::cli) fn worktree_path_template(&self) -> &Template<WorktreePathTemplateContext> {
+        &self.worktree_path_template
     }
 }
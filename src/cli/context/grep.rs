@@ -0,0 +1,78 @@
+use std::{num::NonZeroUsize, thread};
+
+use color_eyre::eyre::{Result, WrapErr as _};
+use regex::{Regex, RegexBuilder};
+
+use crate::{
+    cli::{
+        args::{
+            grep::{GrepArgs, GrepFormat},
+            list::Duplicates,
+        },
+        context::{global::GlobalContext, root::RootContext},
+        input::app_param::AppParam,
+    },
+    domain::port::repo_files::FileSource,
+};
+
+#[derive(Debug)]
+pub(in crate::cli) struct GrepContext {
+    roots: Vec<AppParam<RootContext>>,
+    duplicates: Duplicates,
+    source: FileSource,
+    pattern: Regex,
+    jobs: NonZeroUsize,
+    format: GrepFormat,
+}
+
+impl GrepContext {
+    pub(in crate::cli) fn new(global_ctx: &GlobalContext, args: &GrepArgs) -> Result<Self> {
+        let roots = global_ctx.root_map().roots_by_names(args.root_name())?;
+        let source = if args.head() {
+            FileSource::Head
+        } else {
+            FileSource::WorkTree {
+                untracked: args.untracked(),
+            }
+        };
+        let pattern = RegexBuilder::new(args.pattern())
+            .case_insensitive(args.ignore_case())
+            .build()
+            .wrap_err_with(|| format!("invalid pattern `{}`", args.pattern()))?;
+        let jobs = args
+            .jobs()
+            .unwrap_or_else(|| thread::available_parallelism().unwrap_or(NonZeroUsize::MIN));
+        Ok(Self {
+            roots,
+            duplicates: args.duplicates(),
+            source,
+            pattern,
+            jobs,
+            format: args.format(),
+        })
+    }
+
+    pub(in crate::cli) fn roots(&self) -> &[AppParam<RootContext>] {
+        &self.roots
+    }
+
+    pub(in crate::cli) fn duplicates(&self) -> Duplicates {
+        self.duplicates
+    }
+
+    pub(in crate::cli) fn source(&self) -> FileSource {
+        self.source
+    }
+
+    pub(in crate::cli) fn pattern(&self) -> &Regex {
+        &self.pattern
+    }
+
+    pub(in crate::cli) fn jobs(&self) -> NonZeroUsize {
+        self.jobs
+    }
+
+    pub(in crate::cli) fn format(&self) -> GrepFormat {
+        self.format
+    }
+}
@@ -0,0 +1,34 @@
+use color_eyre::eyre::Result;
+
+use crate::{
+    cli::{
+        args::jump::JumpArgs,
+        context::{global::GlobalContext, root::RootContext},
+        input::app_param::AppParam,
+    },
+    domain::model::visit::JumpKeywords,
+};
+
+#[derive(Debug)]
+pub(in crate::cli) struct JumpContext {
+    roots: Vec<AppParam<RootContext>>,
+    keywords: JumpKeywords,
+}
+
+impl JumpContext {
+    pub(in crate::cli) fn new(global_ctx: &GlobalContext, args: &JumpArgs) -> Result<Self> {
+        let roots = global_ctx.root_map().roots_by_names(args.root_name())?;
+        Ok(Self {
+            roots,
+            keywords: JumpKeywords::new(args.keywords()),
+        })
+    }
+
+    pub(in crate::cli) fn roots(&self) -> &[AppParam<RootContext>] {
+        &self.roots
+    }
+
+    pub(in crate::cli) fn keywords(&self) -> &JumpKeywords {
+        &self.keywords
+    }
+}
@@ -1,7 +1,7 @@
 use color_eyre::eyre::Result;
 
 use crate::cli::{
-    args::list::{Format, ListArgs},
+    args::list::{Duplicates, Format, ListArgs, Sort},
     context::{global::GlobalContext, root::RootContext},
     input::app_param::AppParam,
 };
@@ -9,27 +9,44 @@ use crate::cli::{
 #[derive(Debug)]
 pub(in crate::cli) struct ListContext {
     roots: Vec<AppParam<RootContext>>,
+    duplicates: Duplicates,
+    group_worktrees: bool,
+    sort: Sort,
     format: Format,
 }
 
 impl ListContext {
     pub(in crate::cli) fn new(global_ctx: &GlobalContext, args: &ListArgs) -> Result<Self> {
-        let root_map = global_ctx.root_map();
-        let roots = match args.root_name() {
-            Some(names) => names
-                .iter()
-                .map(|name| root_map.root_by_name_or_err(name).cloned())
-                .collect::<Result<Vec<_>>>()?,
-            None => root_map.all_roots().cloned().collect(),
-        };
+        let roots = global_ctx.root_map().roots_by_names(args.root_name())?;
+        let duplicates = args.duplicates();
+        let group_worktrees = args.group_worktrees();
+        let sort = args.sort()?;
         let format = args.format()?;
-        Ok(Self { roots, format })
+        Ok(Self {
+            roots,
+            duplicates,
+            group_worktrees,
+            sort,
+            format,
+        })
     }
 
     pub(in crate::cli) fn roots(&self) -> &[AppParam<RootContext>] {
         &self.roots
     }
 
+    pub(in crate::cli) fn duplicates(&self) -> Duplicates {
+        self.duplicates
+    }
+
+    pub(in crate::cli) fn group_worktrees(&self) -> bool {
+        self.group_worktrees
+    }
+
+    pub(in crate::cli) fn sort(&self) -> Sort {
+        self.sort
+    }
+
     pub(in crate::cli) fn format(&self) -> &Format {
         &self.format
     }
@@ -2,28 +2,107 @@ use color_eyre::eyre::Result;
 
 use crate::cli::{
     args::Subcommand,
-    context::{clone::CloneContext, global::GlobalContext, list::ListContext},
+    context::{
+        app::AppContext, clone::CloneContext, config::ConfigContext, doctor::DoctorContext,
+        exec::ExecContext, grep::GrepContext, jump::JumpContext, list::ListContext,
+        path::PathContext, pick::PickContext, query_explain::QueryExplainContext,
+        relocate::RelocateContext, shell_init::ShellInitContext, visit::VisitContext,
+        worktree::WorktreeContext,
+    },
 };
 
+pub(in crate::cli) mod app;
 pub(in crate::cli) mod clone;
+pub(in crate::cli) mod config;
+pub(in crate::cli) mod doctor;
+pub(in crate::cli) mod exec;
 pub(crate) mod global;
+pub(in crate::cli) mod grep;
+pub(in crate::cli) mod jump;
 pub(in crate::cli) mod list;
+pub(in crate::cli) mod path;
+pub(in crate::cli) mod pick;
 pub(in crate::cli) mod query;
+pub(in crate::cli) mod query_explain;
+pub(in crate::cli) mod relocate;
 pub(in crate::cli) mod root;
+pub(in crate::cli) mod route;
+pub(in crate::cli) mod shell_init;
+pub(in crate::cli) mod visit;
+pub(in crate::cli) mod worktree;
 
 #[derive(Debug)]
 pub(in crate::cli) enum SubcommandContext {
     Clone(Box<CloneContext>),
+    Config(Box<ConfigContext>),
+    Doctor(Box<DoctorContext>),
+    Exec(Box<ExecContext>),
+    Grep(Box<GrepContext>),
+    Jump(Box<JumpContext>),
     List(Box<ListContext>),
+    Path(Box<PathContext>),
+    Pick(Box<PickContext>),
+    QueryExplain(Box<QueryExplainContext>),
+    Relocate(Box<RelocateContext>),
+    ShellInit(Box<ShellInitContext>),
+    Visit(Box<VisitContext>),
+    Worktree(Box<WorktreeContext>),
 }
 
 impl SubcommandContext {
-    pub(in crate::cli) fn new(global_ctx: &GlobalContext, subcommand: &Subcommand) -> Result<Self> {
+    pub(in crate::cli) fn new(app_ctx: &AppContext, subcommand: &Subcommand) -> Result<Self> {
+        // The configuration file is loaded by the subcommands that use it, so
+        // that `souko config` and `souko doctor` work even if it is broken.
         match subcommand {
-            Subcommand::Clone(args) => {
-                Ok(Self::Clone(Box::new(CloneContext::new(global_ctx, args)?)))
+            Subcommand::Clone(args) => Ok(Self::Clone(Box::new(CloneContext::new(
+                app_ctx.global()?,
+                args,
+            )?))),
+            Subcommand::Config(args) => Ok(Self::Config(Box::new(ConfigContext::new(args)))),
+            Subcommand::Doctor(args) => Ok(Self::Doctor(Box::new(DoctorContext::new(args)))),
+            Subcommand::Exec(args) => Ok(Self::Exec(Box::new(ExecContext::new(
+                app_ctx.global()?,
+                args,
+            )?))),
+            Subcommand::Grep(args) => Ok(Self::Grep(Box::new(GrepContext::new(
+                app_ctx.global()?,
+                args,
+            )?))),
+            Subcommand::Jump(args) => Ok(Self::Jump(Box::new(JumpContext::new(
+                app_ctx.global()?,
+                args,
+            )?))),
+            Subcommand::List(args) => Ok(Self::List(Box::new(ListContext::new(
+                app_ctx.global()?,
+                args,
+            )?))),
+            Subcommand::Path(args) => Ok(Self::Path(Box::new(PathContext::new(
+                app_ctx.global()?,
+                args,
+            )?))),
+            Subcommand::Pick(args) => Ok(Self::Pick(Box::new(PickContext::new(
+                app_ctx.global()?,
+                args,
+            )?))),
+            Subcommand::Query(args) => Ok(Self::QueryExplain(Box::new(QueryExplainContext::new(
+                app_ctx.global()?,
+                args,
+            )?))),
+            Subcommand::Relocate(args) => Ok(Self::Relocate(Box::new(RelocateContext::new(
+                app_ctx.global()?,
+                args,
+            )?))),
+            Subcommand::ShellInit(args) => {
+                Ok(Self::ShellInit(Box::new(ShellInitContext::new(args))))
             }
-            Subcommand::List(args) => Ok(Self::List(Box::new(ListContext::new(global_ctx, args)?))),
+            Subcommand::Visit(args) => Ok(Self::Visit(Box::new(VisitContext::new(
+                app_ctx.global()?,
+                args,
+            )?))),
+            Subcommand::Worktree(args) => Ok(Self::Worktree(Box::new(WorktreeContext::new(
+                app_ctx.global()?,
+                args,
+            )?))),
         }
     }
 }
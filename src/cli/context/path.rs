@@ -0,0 +1,41 @@
+use color_eyre::eyre::{Result, WrapErr as _};
+
+use crate::{
+    cli::{
+        args::path::PathArgs,
+        context::{global::GlobalContext, root::RootContext},
+        input::app_param::AppParam,
+    },
+    domain::model::query::Query,
+};
+
+#[derive(Debug)]
+pub(in crate::cli) struct PathContext {
+    root: AppParam<RootContext>,
+    query: Query,
+}
+
+impl PathContext {
+    pub(in crate::cli) fn new(global_ctx: &GlobalContext, args: &PathArgs) -> Result<Self> {
+        let query_parse_option = global_ctx.query()?.parse_option();
+        let query_str = args.query();
+        let query = Query::parse(query_str, query_parse_option)
+            .wrap_err_with(|| format!("invalid query: {query_str}"))?;
+
+        let root = global_ctx
+            .route()?
+            .select_root(global_ctx.root_map(), args.root_name(), &query)?
+            .root
+            .clone();
+
+        Ok(Self { root, query })
+    }
+
+    pub(in crate::cli) fn root_context(&self) -> &AppParam<RootContext> {
+        &self.root
+    }
+
+    pub(in crate::cli) fn query(&self) -> &Query {
+        &self.query
+    }
+}
@@ -0,0 +1,41 @@
+use color_eyre::eyre::Result;
+
+use crate::{
+    cli::{
+        args::pick::PickArgs,
+        context::{global::GlobalContext, root::RootContext},
+        input::app_param::AppParam,
+        render::list::RepoListTemplateContext,
+    },
+    domain::model::template::Template,
+};
+
+#[derive(Debug)]
+pub(in crate::cli) struct PickContext {
+    roots: Vec<AppParam<RootContext>>,
+    template: Template<RepoListTemplateContext>,
+    query: String,
+}
+
+impl PickContext {
+    pub(in crate::cli) fn new(global_ctx: &GlobalContext, args: &PickArgs) -> Result<Self> {
+        let roots = global_ctx.root_map().roots_by_names(args.root_name())?;
+        Ok(Self {
+            roots,
+            template: args.template().clone(),
+            query: args.query().to_owned(),
+        })
+    }
+
+    pub(in crate::cli) fn roots(&self) -> &[AppParam<RootContext>] {
+        &self.roots
+    }
+
+    pub(in crate::cli) fn template(&self) -> &Template<RepoListTemplateContext> {
+        &self.template
+    }
+
+    pub(in crate::cli) fn query(&self) -> &str {
+        &self.query
+    }
+}
@@ -1,11 +1,13 @@
-use std::{collections::HashMap, str::FromStr as _};
+use std::{collections::HashMap, path::PathBuf, str::FromStr as _};
 
 use crate::{
-    cli::config::QueryConfig,
+    cli::config::{NormalizeConfig, QueryConfig, SchemeNormalizeConfig},
     domain::model::{
         query::{CustomSchemeTemplateContext, ParseOption},
+        repo::RepoNormalization,
         scheme::Scheme,
         template::Template,
+        url_rewrite::{UrlRewriteRule, UrlRewrites},
     },
 };
 
@@ -15,20 +17,32 @@ pub(in crate::cli) struct QueryContext {
 }
 
 fn predefined_aliases() -> HashMap<Scheme, Scheme> {
-    [("gh", "github"), ("gl", "gitlab")]
-        .into_iter()
-        .map(|(src, dst)| {
-            let src = Scheme::from_str(src).unwrap();
-            let dst = Scheme::from_str(dst).unwrap();
-            (src, dst)
-        })
-        .collect()
+    [
+        ("gh", "github"),
+        ("gl", "gitlab"),
+        ("cb", "codeberg"),
+        ("sr", "sourcehut"),
+        ("bb", "bitbucket"),
+    ]
+    .into_iter()
+    .map(|(src, dst)| {
+        let src = Scheme::from_str(src).unwrap();
+        let dst = Scheme::from_str(dst).unwrap();
+        (src, dst)
+    })
+    .collect()
 }
 
 fn predefined_custom_schemes() -> HashMap<Scheme, Template<CustomSchemeTemplateContext<'static>>> {
     [
         ("github", "https://github.com/{path}.git"),
         ("gitlab", "https://gitlab.com/{path}.git"),
+        ("codeberg", "https://codeberg.org/{path}.git"),
+        ("sourcehut", "https://git.sr.ht/{path}"),
+        ("bitbucket", "https://bitbucket.org/{path}.git"),
+        ("gitea", "https://{host}/{path}.git"),
+        ("forgejo", "https://{host}/{path}.git"),
+        ("azure", "https://dev.azure.com/{owner}/_git/{repo}"),
     ]
     .into_iter()
     .map(|(scheme, template)| {
@@ -39,12 +53,82 @@ fn predefined_custom_schemes() -> HashMap<Scheme, Template<CustomSchemeTemplateC
     .collect()
 }
 
+/// Query settings that come from outside of the souko configuration file.
+#[derive(Debug, Default)]
+pub(in crate::cli) struct QueryEnvironment {
+    /// URL rewrite rules read from the user's git configuration.
+    ///
+    /// They are applied after the rules in the configuration file when both match equally.
+    pub(in crate::cli) git_url_rewrites: Vec<UrlRewriteRule>,
+    /// Host aliases read from the user's ssh configuration.
+    ///
+    /// `[query.host_alias]` in the configuration file takes precedence over them.
+    pub(in crate::cli) ssh_host_aliases: HashMap<String, String>,
+    /// Base directory of relative local paths in queries.
+    pub(in crate::cli) working_dir: Option<PathBuf>,
+}
+
+fn normalization(config: &NormalizeConfig) -> RepoNormalization {
+    RepoNormalization {
+        lowercase_host: config.lowercase_host,
+        lowercase_path: config.lowercase_path,
+        strip_git_suffix: config.strip_git_suffix,
+        remove_userinfo: config.remove_userinfo,
+    }
+}
+
+fn scheme_normalization(
+    global: &NormalizeConfig,
+    overrides: &SchemeNormalizeConfig,
+) -> RepoNormalization {
+    RepoNormalization {
+        lowercase_host: overrides.lowercase_host.unwrap_or(global.lowercase_host),
+        lowercase_path: overrides.lowercase_path.unwrap_or(global.lowercase_path),
+        strip_git_suffix: overrides
+            .strip_git_suffix
+            .unwrap_or(global.strip_git_suffix),
+        remove_userinfo: overrides.remove_userinfo.unwrap_or(global.remove_userinfo),
+    }
+}
+
 impl QueryContext {
-    pub(in crate::cli) fn from_config(config: &QueryConfig) -> Self {
+    pub(in crate::cli) fn from_config(config: &QueryConfig, env: QueryEnvironment) -> Self {
+        let QueryEnvironment {
+            git_url_rewrites,
+            ssh_host_aliases,
+            working_dir,
+        } = env;
+        let url_rewrites = config
+            .url_rewrite
+            .iter()
+            .map(|(base, rewrite)| UrlRewriteRule {
+                base: base.clone(),
+                instead_of: rewrite.instead_of.clone(),
+                push_instead_of: rewrite.push_instead_of.clone(),
+            })
+            .chain(git_url_rewrites)
+            .collect();
         let mut parse_option = ParseOption {
             default_scheme: Some(config.default_scheme.clone()),
             scheme_alias: predefined_aliases(),
             custom_scheme: predefined_custom_schemes(),
+            custom_scheme_remotes: config
+                .custom_scheme_remote
+                .iter()
+                .map(|(scheme, remotes)| (scheme.clone(), remotes.clone()))
+                .collect(),
+            url_rewrites: UrlRewrites::new(url_rewrites),
+            working_dir,
+            host_alias: ssh_host_aliases,
+            normalization: normalization(&config.normalize),
+            scheme_normalization: config
+                .scheme_normalize
+                .iter()
+                .map(|(scheme, overrides)| {
+                    let normalization = scheme_normalization(&config.normalize, overrides);
+                    (scheme.clone(), normalization)
+                })
+                .collect(),
         };
         // existing key will be overwritten by the values from config file
         // see `std::iter::Extend`.
@@ -60,6 +144,12 @@ impl QueryContext {
                 .iter()
                 .map(|(scheme, template)| (scheme.clone(), template.clone())),
         );
+        parse_option.host_alias.extend(
+            config
+                .host_alias
+                .iter()
+                .map(|(alias, host)| (alias.clone(), host.clone())),
+        );
 
         Self { parse_option }
     }
@@ -87,7 +177,7 @@ mod tests {
         "#;
 
         let config: Config = toml_edit::de::from_str(input).unwrap();
-        let query = QueryContext::from_config(&config.query);
+        let query = QueryContext::from_config(&config.query, QueryEnvironment::default());
         let option = query.parse_option();
 
         assert_eq!(option.default_scheme, Some("gl".parse().unwrap()));
@@ -120,7 +210,7 @@ mod tests {
         "#;
 
         let config: Config = toml_edit::de::from_str(input).unwrap();
-        let query = QueryContext::from_config(&config.query);
+        let query = QueryContext::from_config(&config.query, QueryEnvironment::default());
         let option = query.parse_option();
 
         assert_eq!(option.default_scheme, Some("gh".parse().unwrap()));
@@ -131,4 +221,159 @@ mod tests {
         let query = crate::domain::model::query::Query::parse("gl:gifnksm/souko", option).unwrap();
         assert_eq!(query.url().as_str(), "https://gitlab.com/gifnksm/souko.git");
     }
+
+    #[test]
+    fn deserialize_query_with_url_rewrite() {
+        let input = r#"
+            [query.url_rewrite."ssh://git@github.com/corp/"]
+            instead_of = ["https://github.com/corp/"]
+
+            [query.url_rewrite."ssh://git@gitlab.com/"]
+            push_instead_of = ["https://gitlab.com/"]
+        "#;
+
+        let config: Config = toml_edit::de::from_str(input).unwrap();
+        assert!(!config.query.use_git_url_rewrites);
+        let git_url_rewrites = vec![UrlRewriteRule {
+            base: "https://mirror.example/".to_owned(),
+            instead_of: vec!["https://github.com/".to_owned()],
+            push_instead_of: vec![],
+        }];
+        let env = QueryEnvironment {
+            git_url_rewrites,
+            ..Default::default()
+        };
+        let query = QueryContext::from_config(&config.query, env);
+        let option = query.parse_option();
+
+        let query = crate::domain::model::query::Query::parse("corp/app", option).unwrap();
+        assert_eq!(query.url().as_str(), "ssh://git@github.com/corp/app.git");
+
+        let query = crate::domain::model::query::Query::parse("gifnksm/souko", option).unwrap();
+        assert_eq!(
+            query.url().as_str(),
+            "https://mirror.example/gifnksm/souko.git"
+        );
+
+        let query = crate::domain::model::query::Query::parse("gl:a/b", option).unwrap();
+        assert_eq!(query.url().as_str(), "https://gitlab.com/a/b.git");
+        assert_eq!(query.push_url(), Some("ssh://git@gitlab.com/a/b.git"));
+    }
+
+    #[test]
+    fn predefined_schemes_expand_to_forge_urls() {
+        let query = QueryContext::from_config(&QueryConfig::default(), QueryEnvironment::default());
+        let option = query.parse_option();
+
+        let cases = [
+            ("cb:owner/repo", "https://codeberg.org/owner/repo.git"),
+            ("sr:~user/repo", "https://git.sr.ht/~user/repo"),
+            ("bb:owner/repo", "https://bitbucket.org/owner/repo.git"),
+            (
+                "gitea+git.example.com:owner/repo",
+                "https://git.example.com/owner/repo.git",
+            ),
+            (
+                "forgejo+git.example.com:owner/repo",
+                "https://git.example.com/owner/repo.git",
+            ),
+            (
+                "gl+gitlab.example.com:group/subgroup/repo",
+                "https://gitlab.example.com/group/subgroup/repo.git",
+            ),
+            (
+                "azure:org/project/repo",
+                "https://dev.azure.com/org/project/_git/repo",
+            ),
+        ];
+        for (input, expected) in cases {
+            let query = crate::domain::model::query::Query::parse(input, option).unwrap();
+            assert_eq!(query.url().as_str(), expected, "query: {input}");
+        }
+    }
+
+    #[test]
+    fn host_alias_in_config_overrides_ssh_config() {
+        let input = r#"
+            [query.host_alias]
+            work-gh = "github.com"
+        "#;
+
+        let config: Config = toml_edit::de::from_str(input).unwrap();
+        assert!(!config.query.use_ssh_config);
+        let env = QueryEnvironment {
+            ssh_host_aliases: HashMap::from_iter([
+                ("work-gh".to_owned(), "ssh.github.com".to_owned()),
+                ("work-gl".to_owned(), "gitlab.com".to_owned()),
+            ]),
+            ..Default::default()
+        };
+        let query = QueryContext::from_config(&config.query, env);
+        let option = query.parse_option();
+
+        let query =
+            crate::domain::model::query::Query::parse("git@work-gh:corp/app.git", option).unwrap();
+        assert_eq!(query.url().as_str(), "ssh://git@work-gh/corp/app.git");
+        assert_eq!(
+            query.layout_url().as_str(),
+            "ssh://git@github.com/corp/app.git"
+        );
+
+        let query =
+            crate::domain::model::query::Query::parse("git@work-gl:corp/app.git", option).unwrap();
+        assert_eq!(
+            query.layout_url().as_str(),
+            "ssh://git@gitlab.com/corp/app.git"
+        );
+    }
+
+    #[test]
+    fn deserialize_query_normalize() {
+        let input = r#"
+            [query.normalize]
+            lowercase_host = true
+            remove_userinfo = true
+
+            [query.scheme_normalize.github]
+            lowercase_path = true
+
+            [query.scheme_normalize.ssh]
+            lowercase_host = false
+        "#;
+
+        let config: Config = toml_edit::de::from_str(input).unwrap();
+        let query = QueryContext::from_config(&config.query, QueryEnvironment::default());
+        let option = query.parse_option();
+
+        assert_eq!(
+            option.normalization,
+            RepoNormalization {
+                lowercase_host: true,
+                remove_userinfo: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            option.scheme_normalization.get("github"),
+            Some(&RepoNormalization {
+                lowercase_host: true,
+                lowercase_path: true,
+                remove_userinfo: true,
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            option.scheme_normalization.get("ssh"),
+            Some(&RepoNormalization {
+                remove_userinfo: true,
+                ..Default::default()
+            })
+        );
+
+        let query = crate::domain::model::query::Query::parse("gh:Gifnksm/Souko", option).unwrap();
+        assert_eq!(
+            query.layout_url().as_str(),
+            "https://github.com/gifnksm/souko.git"
+        );
+    }
 }
@@ -0,0 +1,52 @@
+use color_eyre::eyre::{Result, WrapErr as _};
+
+use crate::{
+    cli::{
+        args::query::{QueryArgs, QuerySubcommand},
+        context::global::GlobalContext,
+    },
+    domain::model::query::Query,
+};
+
+#[derive(Debug)]
+pub(in crate::cli) struct QueryExplainContext {
+    query: Query,
+    selected_root: String,
+    json: bool,
+}
+
+impl QueryExplainContext {
+    pub(in crate::cli) fn new(global_ctx: &GlobalContext, args: &QueryArgs) -> Result<Self> {
+        let QuerySubcommand::Explain(args) = args.subcommand();
+
+        let query_parse_option = global_ctx.query()?.parse_option();
+        let query_str = args.query();
+        let query = Query::parse(query_str, query_parse_option)
+            .wrap_err_with(|| format!("invalid query: {query_str}"))?;
+        let selected_root = global_ctx
+            .route()?
+            .select_root(global_ctx.root_map(), None, &query)?
+            .root
+            .value()
+            .name()
+            .to_owned();
+
+        Ok(Self {
+            query,
+            selected_root,
+            json: args.json(),
+        })
+    }
+
+    pub(in crate::cli) fn query(&self) -> &Query {
+        &self.query
+    }
+
+    pub(in crate::cli) fn selected_root(&self) -> &str {
+        &self.selected_root
+    }
+
+    pub(in crate::cli) fn json(&self) -> bool {
+        self.json
+    }
+}
@@ -0,0 +1,37 @@
+use color_eyre::eyre::Result;
+
+use crate::cli::{
+    args::relocate::RelocateArgs,
+    context::{global::GlobalContext, root::RootContext},
+    input::app_param::AppParam,
+};
+
+#[derive(Debug)]
+pub(in crate::cli) struct RelocateContext {
+    roots: Vec<AppParam<RootContext>>,
+    update_remote: bool,
+    apply: bool,
+}
+
+impl RelocateContext {
+    pub(in crate::cli) fn new(global_ctx: &GlobalContext, args: &RelocateArgs) -> Result<Self> {
+        let roots = global_ctx.root_map().roots_by_names(args.root_name())?;
+        Ok(Self {
+            roots,
+            update_remote: args.update_remote(),
+            apply: args.apply(),
+        })
+    }
+
+    pub(in crate::cli) fn roots(&self) -> &[AppParam<RootContext>] {
+        &self.roots
+    }
+
+    pub(in crate::cli) fn update_remote(&self) -> bool {
+        self.update_remote
+    }
+
+    pub(in crate::cli) fn apply(&self) -> bool {
+        self.apply
+    }
+}
@@ -11,7 +11,7 @@ use crate::{
             unresolved_path::UnresolvedPath,
         },
     },
-    domain::model::{path_buf_pair::PathBufPair, root::Root},
+    domain::model::{layout::RepoLayout, path_buf_pair::PathBufPair, root::Root},
 };
 
 #[derive(Debug)]
@@ -68,9 +68,23 @@ impl RootContextMap {
     pub(in crate::cli) fn all_roots(&self) -> impl Iterator<Item = &AppParam<RootContext>> {
         self.map.values()
     }
+
+    /// Returns the roots named in `names`, or all roots if `names` is `None`.
+    pub(in crate::cli) fn roots_by_names(
+        &self,
+        names: Option<&[String]>,
+    ) -> Result<Vec<AppParam<RootContext>>, color_eyre::Report> {
+        match names {
+            Some(names) => names
+                .iter()
+                .map(|name| self.root_by_name_or_err(name).cloned())
+                .collect(),
+            None => Ok(self.all_roots().cloned().collect()),
+        }
+    }
 }
 
-fn default_path(app_dirs: &AppDirs) -> PathBufPair {
+pub(in crate::cli) fn default_path(app_dirs: &AppDirs) -> PathBufPair {
     UnresolvedPath::new(app_dirs.data_local_dir().join("root"))
         .normalize(&AppParamSource::ImplicitDefault, app_dirs)
 }
@@ -81,6 +95,8 @@ pub(in crate::cli) struct RootContext {
     visit_hidden_dirs: bool,
     visit_repo_subdirs: bool,
     include_bare_repo: bool,
+    include_linked_worktrees: bool,
+    follow_symlinks: bool,
 }
 
 impl RootContext {
@@ -91,10 +107,19 @@ impl RootContext {
             .map(|path| path.normalize(source, app_dirs))
             .unwrap_or_else(|| default_path(app_dirs));
         Self {
-            root: Root::new(config.name.clone(), path),
+            root: Root::new(config.name.clone(), path).with_layout(
+                config
+                    .layout
+                    .clone()
+                    .map(RepoLayout::new)
+                    .unwrap_or_default()
+                    .with_local_prefix(&config.local_prefix),
+            ),
             visit_hidden_dirs: config.visit_hidden_dirs,
             visit_repo_subdirs: config.visit_repo_subdirs,
             include_bare_repo: config.include_bare_repo,
+            include_linked_worktrees: config.include_linked_worktrees,
+            follow_symlinks: config.follow_symlinks,
         }
     }
 
@@ -102,7 +127,6 @@ impl RootContext {
         &self.root
     }
 
-    #[cfg(test)]
     pub(in crate::cli) fn name(&self) -> &str {
         self.root.name()
     }
@@ -119,18 +143,33 @@ impl RootContext {
         self.include_bare_repo
     }
 
+    pub(in crate::cli) fn include_linked_worktrees(&self) -> bool {
+        self.include_linked_worktrees
+    }
+
     pub(in crate::cli) fn visit_repo_subdirs(&self) -> bool {
         self.visit_repo_subdirs
     }
+
+    pub(in crate::cli) fn follow_symlinks(&self) -> bool {
+        self.follow_symlinks
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
 
     use tempfile::TempDir;
 
-    use crate::{cli::config::Config, domain::model::path_like::PathLike as _};
+    use crate::{
+        cli::config::Config,
+        domain::model::{
+            path_like::PathLike as _,
+            query::{ParseOption, Query},
+            repo::Repo,
+        },
+    };
 
     use super::*;
 
@@ -217,4 +256,51 @@ mod tests {
         assert!(default_root.source().is_implicit_default());
         assert_eq!(default_root.value().name(), "default");
     }
+
+    #[test]
+    fn configuration_file_root_layout_is_used_for_repo_paths() {
+        let home = TempDir::new().unwrap();
+        let app_dirs =
+            AppDirs::new_for_test(env!("CARGO_BIN_NAME"), home.path(), home.path()).unwrap();
+        let config_path = PathBufPair::from_pair(
+            home.path().join("config.toml"),
+            PathBuf::from("~/config.toml"),
+        );
+        let config: Config = toml_edit::de::from_str(
+            r#"
+            [[root]]
+            name = "work"
+            layout = "{owner}/{repo}"
+            "#,
+        )
+        .unwrap();
+
+        let root_ctx = RootContextMap::new(&config_path, &config.roots, &app_dirs);
+        let query = Query::parse(
+            "https://github.com/gifnksm/souko.git",
+            &ParseOption::default(),
+        )
+        .unwrap();
+
+        let work_root = root_ctx.root_by_name("work").unwrap().value().root();
+        let repo = Repo::from_query(work_root, &query, false).unwrap();
+        assert_eq!(repo.relative_path(), Path::new("gifnksm/souko"));
+
+        let default_root = root_ctx.default_root().value().root();
+        let repo = Repo::from_query(default_root, &query, false).unwrap();
+        assert_eq!(repo.relative_path(), Path::new("github.com/gifnksm/souko"));
+    }
+
+    #[test]
+    fn unknown_layout_variable_is_rejected() {
+        let err = toml_edit::de::from_str::<Config>(
+            r#"
+            [[root]]
+            name = "work"
+            layout = "{organization}/{repo}"
+            "#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("unknown template variable"));
+    }
 }
@@ -0,0 +1,301 @@
+use std::fmt::{self, Display};
+
+use color_eyre::eyre::{Result, WrapErr as _, bail};
+use regex::Regex;
+
+use crate::{
+    cli::{
+        config::RouteConfig,
+        context::root::{RootContext, RootContextMap},
+        input::app_param::AppParam,
+    },
+    domain::model::query::Query,
+};
+
+/// Rules that select the root for a repository when `--root` is not given.
+#[derive(Debug)]
+pub(in crate::cli) struct RouteContext {
+    routes: Vec<Route>,
+}
+
+#[derive(Debug)]
+pub(in crate::cli) struct Route {
+    index: usize,
+    root_name: String,
+    host: Option<String>,
+    url: Option<Regex>,
+}
+
+impl Route {
+    /// Matches the rule against the URL used to decide the repository path,
+    /// so that host aliases and normalization are taken into account.
+    fn is_match(&self, query: &Query) -> bool {
+        let url = query.layout_url();
+        let host_matched = self.host.as_ref().is_none_or(|host| {
+            url.host_str()
+                .is_some_and(|url_host| url_host.eq_ignore_ascii_case(host))
+        });
+        let url_matched = self
+            .url
+            .as_ref()
+            .is_none_or(|regex| regex.is_match(url.as_str()));
+        host_matched && url_matched
+    }
+}
+
+impl Display for Route {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "route #{}", self.index + 1)?;
+        let mut conditions = vec![];
+        if let Some(host) = &self.host {
+            conditions.push(format!("host = {host:?}"));
+        }
+        if let Some(url) = &self.url {
+            conditions.push(format!("url = {:?}", url.as_str()));
+        }
+        write!(f, " ({})", conditions.join(", "))
+    }
+}
+
+impl RouteContext {
+    pub(in crate::cli) fn from_config(
+        config: &[RouteConfig],
+        root_map: &RootContextMap,
+    ) -> Result<Self> {
+        let routes = config
+            .iter()
+            .enumerate()
+            .map(|(index, config)| {
+                root_map.root_by_name_or_err(&config.root)?;
+                if config.host.is_none() && config.url.is_none() {
+                    bail!(
+                        "route #{} for root `{}` must have `host` or `url`",
+                        index + 1,
+                        config.root
+                    );
+                }
+                let url = config
+                    .url
+                    .as_deref()
+                    .map(Regex::new)
+                    .transpose()
+                    .wrap_err_with(|| format!("invalid `url` pattern in route #{}", index + 1))?;
+                Ok(Route {
+                    index,
+                    root_name: config.root.clone(),
+                    host: config.host.clone(),
+                    url,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { routes })
+    }
+
+    /// Selects the root for the repository specified by `query`.
+    ///
+    /// The root given by `root_name` takes precedence. Otherwise, the root of
+    /// the first matching route is selected, falling back to the default root.
+    pub(in crate::cli) fn select_root<'a>(
+        &'a self,
+        root_map: &'a RootContextMap,
+        root_name: Option<&str>,
+        query: &Query,
+    ) -> Result<RootSelection<'a>> {
+        if let Some(name) = root_name {
+            return Ok(RootSelection {
+                root: root_map.root_by_name_or_err(name)?,
+                reason: RootSelectionReason::CommandLineArgument,
+            });
+        }
+        if let Some(route) = self.routes.iter().find(|route| route.is_match(query)) {
+            return Ok(RootSelection {
+                root: root_map.root_by_name_or_err(&route.root_name)?,
+                reason: RootSelectionReason::Route(route),
+            });
+        }
+        Ok(RootSelection {
+            root: root_map.default_root(),
+            reason: RootSelectionReason::Default,
+        })
+    }
+
+    /// Returns the root of the first route matching `query`, or `None` if no
+    /// route matches.
+    pub(in crate::cli) fn route_root<'a>(
+        &'a self,
+        root_map: &'a RootContextMap,
+        query: &Query,
+    ) -> Result<Option<&'a AppParam<RootContext>>> {
+        self.routes
+            .iter()
+            .find(|route| route.is_match(query))
+            .map(|route| root_map.root_by_name_or_err(&route.root_name))
+            .transpose()
+    }
+}
+
+#[derive(Debug)]
+pub(in crate::cli) struct RootSelection<'a> {
+    pub(in crate::cli) root: &'a AppParam<RootContext>,
+    pub(in crate::cli) reason: RootSelectionReason<'a>,
+}
+
+#[derive(Debug)]
+pub(in crate::cli) enum RootSelectionReason<'a> {
+    CommandLineArgument,
+    Route(&'a Route),
+    Default,
+}
+
+impl Display for RootSelectionReason<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CommandLineArgument => write!(f, "specified by `--root`"),
+            Self::Route(route) => write!(f, "matched {route}"),
+            Self::Default => write!(f, "no route matched, using the default root"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, path::PathBuf};
+
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::{
+        app_dirs::AppDirs,
+        cli::config::Config,
+        domain::model::{path_buf_pair::PathBufPair, query::ParseOption},
+    };
+
+    fn select(input: &str, query: &str, root_name: Option<&str>) -> Result<(String, String)> {
+        select_with(input, query, root_name, &Default::default())
+    }
+
+    fn select_with(
+        input: &str,
+        query: &str,
+        root_name: Option<&str>,
+        option: &ParseOption,
+    ) -> Result<(String, String)> {
+        let home = TempDir::new().unwrap();
+        let app_dirs =
+            AppDirs::new_for_test(env!("CARGO_BIN_NAME"), home.path(), home.path()).unwrap();
+        let config_path = PathBufPair::from_pair(
+            home.path().join("config.toml"),
+            PathBuf::from("~/config.toml"),
+        );
+        let config: Config = toml_edit::de::from_str(input).unwrap();
+        let root_map = RootContextMap::new(&config_path, &config.roots, &app_dirs);
+        let routes = RouteContext::from_config(&config.routes, &root_map)?;
+        let query = Query::parse(query, option).unwrap();
+        let selection = routes.select_root(&root_map, root_name, &query)?;
+        Ok((
+            selection.root.value().name().to_owned(),
+            selection.reason.to_string(),
+        ))
+    }
+
+    const CONFIG: &str = r#"
+        [[root]]
+        name = "work"
+
+        [[root]]
+        name = "oss"
+
+        [[route]]
+        root = "work"
+        host = "git.corp.example"
+
+        [[route]]
+        root = "oss"
+        url = '^https://github\.com/gifnksm/'
+    "#;
+
+    #[test]
+    fn select_root_by_route() {
+        assert_eq!(
+            select(CONFIG, "ssh://git@GIT.corp.example/team/app.git", None).unwrap(),
+            (
+                "work".to_owned(),
+                r#"matched route #1 (host = "git.corp.example")"#.to_owned()
+            )
+        );
+        assert_eq!(
+            select(CONFIG, "https://github.com/gifnksm/souko.git", None)
+                .unwrap()
+                .0,
+            "oss"
+        );
+        assert_eq!(
+            select(CONFIG, "https://github.com/rust-lang/rust.git", None).unwrap(),
+            (
+                "default".to_owned(),
+                "no route matched, using the default root".to_owned()
+            )
+        );
+        assert_eq!(
+            select(CONFIG, "https://github.com/gifnksm/souko.git", Some("work"))
+                .unwrap()
+                .0,
+            "work"
+        );
+    }
+
+    #[test]
+    fn routes_match_layout_url() {
+        let option = ParseOption {
+            host_alias: HashMap::from_iter([("work-gh".to_owned(), "git.corp.example".to_owned())]),
+            ..Default::default()
+        };
+        assert_eq!(
+            select_with(CONFIG, "git@work-gh:team/app.git", None, &option)
+                .unwrap()
+                .0,
+            "work"
+        );
+    }
+
+    #[test]
+    fn invalid_routes_are_rejected() {
+        let err = select(
+            r#"
+            [[route]]
+            root = "missing"
+            host = "example.com"
+            "#,
+            "https://example.com/a/b",
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "root `missing` not found in config file");
+
+        let err = select(
+            r#"
+            [[route]]
+            root = "default"
+            "#,
+            "https://example.com/a/b",
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "route #1 for root `default` must have `host` or `url`"
+        );
+
+        let err = select(
+            r#"
+            [[route]]
+            root = "default"
+            url = "("
+            "#,
+            "https://example.com/a/b",
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "invalid `url` pattern in route #1");
+    }
+}
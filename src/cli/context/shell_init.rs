@@ -0,0 +1,18 @@
+use crate::cli::args::shell_init::{Shell, ShellInitArgs};
+
+#[derive(Debug)]
+pub(in crate::cli) struct ShellInitContext {
+    shell: Shell,
+}
+
+impl ShellInitContext {
+    pub(in crate::cli) fn new(args: &ShellInitArgs) -> Self {
+        Self {
+            shell: args.shell(),
+        }
+    }
+
+    pub(in crate::cli) fn shell(&self) -> Shell {
+        self.shell
+    }
+}
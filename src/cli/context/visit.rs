@@ -0,0 +1,34 @@
+use color_eyre::eyre::Result;
+
+use crate::{
+    cli::{args::visit::VisitArgs, context::global::GlobalContext},
+    domain::model::{path_buf_pair::PathBufPair, root::Root},
+};
+
+#[derive(Debug)]
+pub(in crate::cli) struct VisitContext {
+    roots: Vec<Root>,
+    path: PathBufPair,
+}
+
+impl VisitContext {
+    pub(in crate::cli) fn new(global_ctx: &GlobalContext, args: &VisitArgs) -> Result<Self> {
+        let roots = global_ctx
+            .root_map()
+            .all_roots()
+            .map(|root| root.value().root().clone())
+            .collect();
+        Ok(Self {
+            roots,
+            path: PathBufPair::new(args.path()),
+        })
+    }
+
+    pub(in crate::cli) fn roots(&self) -> &[Root] {
+        &self.roots
+    }
+
+    pub(in crate::cli) fn path(&self) -> &PathBufPair {
+        &self.path
+    }
+}
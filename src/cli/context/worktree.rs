@@ -0,0 +1,81 @@
+use color_eyre::eyre::{Result, WrapErr as _};
+
+use crate::{
+    cli::{
+        args::worktree::{WorktreeArgs, WorktreeRepoArgs, WorktreeSubcommand},
+        context::{global::GlobalContext, root::RootContext},
+        input::app_param::AppParam,
+    },
+    domain::model::query::Query,
+};
+
+#[derive(Debug)]
+pub(in crate::cli) struct WorktreeContext {
+    root: AppParam<RootContext>,
+    query: Query,
+    action: WorktreeAction,
+}
+
+#[derive(Debug)]
+pub(in crate::cli) enum WorktreeAction {
+    Add { branch: String },
+    List,
+    Remove { branch: String, force: bool },
+}
+
+impl WorktreeContext {
+    pub(in crate::cli) fn new(global_ctx: &GlobalContext, args: &WorktreeArgs) -> Result<Self> {
+        let (repo_args, action) = match args.subcommand() {
+            WorktreeSubcommand::Add(args) => (
+                args.repo(),
+                WorktreeAction::Add {
+                    branch: args.branch().to_owned(),
+                },
+            ),
+            WorktreeSubcommand::List(args) => (args, WorktreeAction::List),
+            WorktreeSubcommand::Remove(args) => (
+                args.target().repo(),
+                WorktreeAction::Remove {
+                    branch: args.target().branch().to_owned(),
+                    force: args.force(),
+                },
+            ),
+        };
+        let (root, query) = resolve_repo(global_ctx, repo_args)?;
+        Ok(Self {
+            root,
+            query,
+            action,
+        })
+    }
+
+    pub(in crate::cli) fn root_context(&self) -> &AppParam<RootContext> {
+        &self.root
+    }
+
+    pub(in crate::cli) fn query(&self) -> &Query {
+        &self.query
+    }
+
+    pub(in crate::cli) fn action(&self) -> &WorktreeAction {
+        &self.action
+    }
+}
+
+fn resolve_repo(
+    global_ctx: &GlobalContext,
+    args: &WorktreeRepoArgs,
+) -> Result<(AppParam<RootContext>, Query)> {
+    let query_parse_option = global_ctx.query()?.parse_option();
+    let query_str = args.query();
+    let query = Query::parse(query_str, query_parse_option)
+        .wrap_err_with(|| format!("invalid query: {query_str}"))?;
+
+    let root = global_ctx
+        .route()?
+        .select_root(global_ctx.root_map(), args.root_name(), &query)?
+        .root
+        .clone();
+
+    Ok((root, query))
+}
@@ -4,7 +4,7 @@ use std::{
     str::FromStr,
 };
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     app_dirs::AppDirs,
@@ -12,7 +12,7 @@ use crate::{
     domain::model::{path_buf_pair::PathBufPair, path_like::PathLike},
 };
 
-#[derive(Debug, Default, Clone, Deserialize)]
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
 #[serde(transparent)]
 pub(in super::super) struct UnresolvedPath(PathBuf);
 
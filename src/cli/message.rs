@@ -1,5 +1,7 @@
 use std::fmt;
 
+use crate::cli::color::{self, Style};
+
 macro_rules! _message_info {
     ($($arg:tt)*) => {
         $crate::cli::message::_info(::std::format_args!($($arg)*))
@@ -16,9 +18,15 @@ pub(in crate::cli) use _message_info as info;
 pub(in crate::cli) use _message_warn as warn;
 
 pub(in crate::cli) fn _info(message: fmt::Arguments<'_>) {
-    eprintln!("info: {message}");
+    eprintln!(
+        "{} {message}",
+        Style::BoldGreen.paint(color::stderr_enabled(), "info:")
+    );
 }
 
 pub(in crate::cli) fn _warn(message: fmt::Arguments<'_>) {
-    eprintln!("warning: {message}");
+    eprintln!(
+        "{} {message}",
+        Style::BoldYellow.paint(color::stderr_enabled(), "warning:")
+    );
 }
@@ -1,13 +1,10 @@
-use std::io;
-
 use clap::CommandFactory as _;
-use clap_complete::{Generator, Shell};
-use color_eyre::eyre::{Result, WrapErr as _, bail};
+use color_eyre::eyre::{Result, WrapErr as _};
 
 use crate::{
     app_dirs::AppDirs,
     application::usecase::Usecases,
-    cli::context::{SubcommandContext, global::GlobalContext},
+    cli::context::{SubcommandContext, app::AppContext},
 };
 
 use self::args::Args;
@@ -15,47 +12,39 @@ use self::args::Args;
 mod message;
 
 pub(crate) mod args;
+mod color;
 mod command;
+pub(crate) mod complete;
 mod config;
 mod context;
 mod input;
 mod render;
 
-pub(crate) fn print_completion(bin_name: &str, shell: &str) -> Result<()> {
-    fn generate_completion<G>(bin_name: &str, g: G)
-    where
-        G: Generator,
-    {
-        clap_complete::generate(g, &mut Args::command(), bin_name, &mut io::stdout());
-    }
-    match shell {
-        "bash" => generate_completion(bin_name, Shell::Bash),
-        "elvish" => generate_completion(bin_name, Shell::Elvish),
-        "fish" => generate_completion(bin_name, Shell::Fish),
-        "powershell" => generate_completion(bin_name, Shell::PowerShell),
-        "zsh" => generate_completion(bin_name, Shell::Zsh),
-        "nushell" => generate_completion(bin_name, clap_complete_nushell::Nushell),
-        _ => {
-            bail!(
-                "unknown shell `{shell}`, expected one of `bash`, `elvish`, `fish`, `powershell`, `zsh`, `nushell`"
-            );
-        }
-    }
-    Ok(())
-}
-
 pub(crate) fn generate_man(output_dir: &str) -> Result<()> {
     clap_mangen::generate_to(Args::command(), output_dir)
         .wrap_err_with(|| format!("failed to generate man pages in {output_dir}"))?;
     Ok(())
 }
 
-pub(crate) fn dispatch(args: &Args, usecases: Usecases, app_dirs: AppDirs) -> Result<()> {
+/// How a subcommand finished, for `main` to choose the exit status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Outcome {
+    Success,
+    /// `grep` found nothing.
+    NoMatch,
+    /// `grep` failed to search some repositories.
+    SearchFailed,
+    /// `pick` was cancelled without a selection.
+    Cancelled,
+}
+
+pub(crate) fn dispatch(args: &Args, usecases: Usecases, app_dirs: AppDirs) -> Result<Outcome> {
     let Some(subcommand) = args.subcommand() else {
         Args::command().print_help()?;
-        return Ok(());
+        return Ok(Outcome::Success);
     };
-    let global_ctx = GlobalContext::new(args, usecases, app_dirs)?;
-    let subcommand_ctx = SubcommandContext::new(&global_ctx, subcommand)?;
-    command::dispatch(&global_ctx, &subcommand_ctx)
+    color::init(args.global_args().color());
+    let app_ctx = AppContext::new(args.global_args(), usecases, app_dirs);
+    let subcommand_ctx = SubcommandContext::new(&app_ctx, subcommand)?;
+    command::dispatch(&app_ctx, &subcommand_ctx)
 }
@@ -0,0 +1,182 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::Result;
+use serde::Serialize;
+
+use crate::{
+    cli::{args::grep::GrepFormat, color::Style},
+    domain::model::grep::{FileMatches, LineMatch},
+};
+
+/// Files matching the search pattern in a repository.
+#[derive(Debug)]
+pub(in crate::cli) struct RepoMatches {
+    pub(in crate::cli) root_name: String,
+    pub(in crate::cli) repo_relative_path: String,
+    pub(in crate::cli) repo_real_path: PathBuf,
+    pub(in crate::cli) files: Vec<FileMatches>,
+}
+
+/// Writes matches repository by repository.
+///
+/// Line-oriented formats are written as soon as each repository is given,
+/// while JSON output is written at once by [`Renderer::finish`].
+#[derive(Debug)]
+pub(in crate::cli) struct Renderer {
+    format: GrepFormat,
+    color: bool,
+    first_file: bool,
+    json_repos: Vec<JsonRepo>,
+}
+
+impl Renderer {
+    pub(in crate::cli) fn new(format: GrepFormat, color: bool) -> Self {
+        Self {
+            format,
+            color,
+            first_file: true,
+            json_repos: vec![],
+        }
+    }
+
+    pub(in crate::cli) fn repo<W>(&mut self, mut out: W, repo: RepoMatches) -> Result<()>
+    where
+        W: io::Write,
+    {
+        match self.format {
+            GrepFormat::Line => {
+                for file in &repo.files {
+                    let path = file_path(&repo, file);
+                    for line in file.lines() {
+                        writeln!(
+                            &mut out,
+                            "{}:{}:{}",
+                            Style::Magenta.paint(self.color, path.display()),
+                            Style::Green.paint(self.color, line.line_number()),
+                            highlight(self.color, line),
+                        )?;
+                    }
+                }
+            }
+            GrepFormat::Group => {
+                for file in &repo.files {
+                    if !self.first_file {
+                        writeln!(&mut out)?;
+                    }
+                    self.first_file = false;
+                    let path = file_path(&repo, file);
+                    writeln!(
+                        &mut out,
+                        "{}",
+                        Style::Magenta.paint(self.color, path.display())
+                    )?;
+                    for line in file.lines() {
+                        writeln!(
+                            &mut out,
+                            "{}:{}",
+                            Style::Green.paint(self.color, line.line_number()),
+                            highlight(self.color, line),
+                        )?;
+                    }
+                }
+            }
+            GrepFormat::Json => self.json_repos.push(JsonRepo::from(repo)),
+        }
+        out.flush()?;
+        Ok(())
+    }
+
+    pub(in crate::cli) fn finish<W>(self, mut out: W) -> Result<()>
+    where
+        W: io::Write,
+    {
+        if self.format == GrepFormat::Json {
+            serde_json::to_writer(
+                &mut out,
+                &JsonGrep {
+                    repos: self.json_repos,
+                },
+            )?;
+            writeln!(&mut out)?;
+            out.flush()?;
+        }
+        Ok(())
+    }
+}
+
+fn file_path(repo: &RepoMatches, file: &FileMatches) -> PathBuf {
+    Path::new(&repo.repo_relative_path).join(file.path())
+}
+
+fn highlight(color: bool, line: &LineMatch) -> String {
+    if !color {
+        return line.line().to_owned();
+    }
+    let text = line.line();
+    let mut highlighted = String::new();
+    let mut last = 0;
+    for range in line.ranges() {
+        highlighted.push_str(&text[last..range.start]);
+        highlighted.push_str(&Style::Red.paint(color, &text[range.clone()]).to_string());
+        last = range.end;
+    }
+    highlighted.push_str(&text[last..]);
+    highlighted
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonGrep {
+    repos: Vec<JsonRepo>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonRepo {
+    root_name: String,
+    relative_path: String,
+    real_path: PathBuf,
+    files: Vec<JsonFile>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonFile {
+    path: PathBuf,
+    matches: Vec<JsonMatch>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonMatch {
+    line_number: usize,
+    line: String,
+}
+
+impl From<RepoMatches> for JsonRepo {
+    fn from(repo: RepoMatches) -> Self {
+        Self {
+            root_name: repo.root_name,
+            relative_path: repo.repo_relative_path,
+            real_path: repo.repo_real_path,
+            files: repo
+                .files
+                .iter()
+                .map(|file| JsonFile {
+                    path: file.path().to_owned(),
+                    matches: file
+                        .lines()
+                        .iter()
+                        .map(|line| JsonMatch {
+                            line_number: line.line_number(),
+                            line: line.line().to_owned(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
@@ -1,10 +1,18 @@
-use std::{fmt::Display, io, path::PathBuf};
+use std::{
+    borrow::Cow,
+    fmt::{Display, Write as _},
+    io, iter, path,
+};
 
 use color_eyre::eyre::Result;
-use serde::Serialize;
+use serde::{Serialize, ser::SerializeMap as _};
+use unicode_width::{UnicodeWidthChar as _, UnicodeWidthStr as _};
 
 use crate::{
-    cli::args::list::Format,
+    cli::{
+        args::list::{Column, Format, TableFormat, Terminator},
+        color::{Style, TemplateColors},
+    },
     domain::model::{
         path_like::PathLike as _,
         repo::CanonicalRepo,
@@ -13,9 +21,22 @@ use crate::{
     },
 };
 
+/// Columns are not narrowed below this width when fitting a table to the terminal.
+const MIN_COLUMN_WIDTH: usize = 8;
+const COLUMN_SEPARATOR: &str = "  ";
+
+/// How the output is displayed.
+#[derive(Debug, Clone, Copy, Default)]
+pub(in crate::cli) struct RenderOptions {
+    /// Width of the terminal, if the output is a terminal.
+    pub(in crate::cli) max_width: Option<usize>,
+    pub(in crate::cli) color: bool,
+}
+
 pub(in crate::cli) fn render<W, Roots, Repos>(
     mut out: W,
     format: &Format,
+    options: RenderOptions,
     roots: Roots,
 ) -> Result<()>
 where
@@ -24,21 +45,42 @@ where
     Repos: Iterator<Item = CanonicalRepo>,
 {
     match format {
-        Format::Default => render_default(&mut out, roots),
+        Format::Default(terminator) => render_default(&mut out, roots, *terminator, options),
         Format::Json => render_json(&mut out, roots),
-        Format::Template(template) => render_template(&mut out, roots, template),
+        Format::Ndjson(columns) => render_ndjson(&mut out, roots, columns),
+        Format::Template(template, terminator) => {
+            render_template(&mut out, roots, template, *terminator, options)
+        }
+        Format::Table(table) => render_table(&mut out, roots, table, options),
+        Format::Csv(table) => render_separated(&mut out, roots, table, ',', "\r\n", escape_csv),
+        Format::Tsv(table) => render_separated(&mut out, roots, table, '\t', "\n", escape_tsv),
     }
 }
 
-fn render_default<W, Roots, Repos>(mut out: W, roots: Roots) -> Result<()>
+fn render_default<W, Roots, Repos>(
+    mut out: W,
+    roots: Roots,
+    terminator: Terminator,
+    options: RenderOptions,
+) -> Result<()>
 where
     W: io::Write,
     Roots: Iterator<Item = (CanonicalRoot, Repos)>,
     Repos: Iterator<Item = CanonicalRepo>,
 {
+    let terminator = terminator.as_str();
     for (_root, repos) in roots {
         for repo in repos {
-            writeln!(&mut out, "{}", repo.canonical_path().display())?;
+            let path = repo.canonical_path().display().to_string();
+            let relative_path = repo.relative_path().display().to_string();
+            match path.strip_suffix(&relative_path) {
+                Some(root_path) if options.color => write!(
+                    &mut out,
+                    "{root_path}{}{terminator}",
+                    paint_relative_path(&relative_path, true)
+                )?,
+                _ => write!(&mut out, "{path}{terminator}")?,
+            }
         }
     }
     out.flush()?;
@@ -49,22 +91,282 @@ fn render_template<W, Roots, Repos>(
     mut out: W,
     roots: Roots,
     template: &Template<RepoListTemplateContext>,
+    terminator: Terminator,
+    options: RenderOptions,
+) -> Result<()>
+where
+    W: io::Write,
+    Roots: Iterator<Item = (CanonicalRoot, Repos)>,
+    Repos: Iterator<Item = CanonicalRepo>,
+{
+    let terminator = terminator.as_str();
+    for (root, repos) in roots {
+        for repo in repos {
+            let context = RepoListTemplateContext::new(&root, &repo, options.color);
+            write!(&mut out, "{}{terminator}", template.expand(&context))?;
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn render_table<W, Roots, Repos>(
+    mut out: W,
+    roots: Roots,
+    table: &TableFormat,
+    options: RenderOptions,
+) -> Result<()>
+where
+    W: io::Write,
+    Roots: Iterator<Item = (CanonicalRoot, Repos)>,
+    Repos: Iterator<Item = CanonicalRepo>,
+{
+    let TableFormat { columns, header } = table;
+
+    // Column widths depend on every row, so the whole table is buffered.
+    let mut rows = vec![];
+    if *header {
+        rows.push(
+            columns
+                .iter()
+                .map(|column| column.name().to_owned())
+                .collect(),
+        );
+    }
+    for (root, repos) in roots {
+        for repo in repos {
+            let context = RepoListTemplateContext::new(&root, &repo, false);
+            rows.push(
+                columns
+                    .iter()
+                    .map(|column| context.field(*column).to_owned())
+                    .collect::<Vec<_>>(),
+            );
+        }
+    }
+
+    let mut widths = vec![0; columns.len()];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.width());
+        }
+    }
+    if let Some(max_width) = options.max_width {
+        fit_column_widths(&mut widths, max_width);
+    }
+
+    for (row_index, row) in rows.iter().enumerate() {
+        let is_header = *header && row_index == 0;
+        let mut line = String::new();
+        for (i, ((cell, width), column)) in row.iter().zip(&widths).zip(columns).enumerate() {
+            let is_last = i + 1 == row.len();
+            if i > 0 {
+                line.push_str(COLUMN_SEPARATOR);
+            }
+            let cell = truncate_to_width(cell, *width);
+            let padding = width - cell.width();
+            if is_header {
+                write!(&mut line, "{}", Style::Bold.paint(options.color, &cell))?;
+            } else {
+                line.push_str(&paint_cell(*column, &cell, options.color));
+            }
+            if !is_last {
+                line.extend(iter::repeat_n(' ', padding));
+            }
+        }
+        writeln!(&mut out, "{line}")?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn render_separated<W, Roots, Repos>(
+    mut out: W,
+    roots: Roots,
+    table: &TableFormat,
+    separator: char,
+    line_break: &str,
+    escape: fn(&str) -> Cow<'_, str>,
 ) -> Result<()>
 where
     W: io::Write,
     Roots: Iterator<Item = (CanonicalRoot, Repos)>,
     Repos: Iterator<Item = CanonicalRepo>,
 {
+    let TableFormat { columns, header } = table;
+    let mut write_record = |fields: &mut dyn Iterator<Item = &str>| -> io::Result<()> {
+        for (i, field) in fields.enumerate() {
+            if i > 0 {
+                write!(&mut out, "{separator}")?;
+            }
+            write!(&mut out, "{}", escape(field))?;
+        }
+        write!(&mut out, "{line_break}")
+    };
+
+    if *header {
+        write_record(&mut columns.iter().map(|column| column.name()))?;
+    }
     for (root, repos) in roots {
         for repo in repos {
-            let context = RepoListTemplateContext::new(&root, &repo);
-            writeln!(&mut out, "{}", template.expand(&context))?;
+            let context = RepoListTemplateContext::new(&root, &repo, false);
+            write_record(&mut columns.iter().map(|column| context.field(*column)))?;
         }
     }
     out.flush()?;
     Ok(())
 }
 
+/// Quotes a CSV field if it contains a separator, a quote or a line break.
+fn escape_csv(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\r', '\n']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// Escapes characters that cannot appear in a TSV field.
+fn escape_tsv(field: &str) -> Cow<'_, str> {
+    if !field.contains(['\t', '\n', '\r', '\\']) {
+        return Cow::Borrowed(field);
+    }
+    let mut escaped = String::with_capacity(field.len());
+    for ch in field.chars() {
+        match ch {
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\\' => escaped.push_str("\\\\"),
+            ch => escaped.push(ch),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Writes each repository as soon as it is found, without buffering the whole list.
+fn render_ndjson<W, Roots, Repos>(mut out: W, roots: Roots, columns: &[Column]) -> Result<()>
+where
+    W: io::Write,
+    Roots: Iterator<Item = (CanonicalRoot, Repos)>,
+    Repos: Iterator<Item = CanonicalRepo>,
+{
+    for (root, repos) in roots {
+        for repo in repos {
+            let context = RepoListTemplateContext::new(&root, &repo, false);
+            serde_json::to_writer(
+                &mut out,
+                &ColumnRecord {
+                    context: &context,
+                    columns,
+                },
+            )?;
+            writeln!(&mut out)?;
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// A repository serialized as a map from column names to fields.
+struct ColumnRecord<'a> {
+    context: &'a RepoListTemplateContext,
+    columns: &'a [Column],
+}
+
+impl Serialize for ColumnRecord<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.columns.len()))?;
+        for column in self.columns {
+            map.serialize_entry(column.name(), self.context.field(*column))?;
+        }
+        map.end()
+    }
+}
+
+fn paint_cell(column: Column, cell: &str, color: bool) -> String {
+    match column {
+        Column::RootName => Style::Magenta.paint(color, cell).to_string(),
+        Column::RepoRelativePath => paint_relative_path(cell, color),
+        _ => cell.to_owned(),
+    }
+}
+
+/// Colors the host, owner and repository name parts of a repository path relative to its root.
+///
+/// Paths with three or more components are assumed to be `<host>/<owner>/<repo>`, and paths
+/// with two components `<owner>/<repo>`.
+fn paint_relative_path(path: &str, color: bool) -> String {
+    if !color {
+        return path.to_owned();
+    }
+    let repo_start = path.rfind(path::is_separator).map_or(0, |idx| idx + 1);
+    let (parent, repo) = path.split_at(repo_start);
+    let mut result = String::new();
+    if let Some(parent) = parent.get(..parent.len().saturating_sub(1)) {
+        let separator = &path[parent.len()..repo_start];
+        match parent.find(path::is_separator) {
+            Some(host_end) => {
+                let (host, owner) = parent.split_at(host_end);
+                let (owner_separator, owner) = owner.split_at(1);
+                write!(
+                    &mut result,
+                    "{}{owner_separator}{}",
+                    Style::Blue.paint(true, host),
+                    Style::Cyan.paint(true, owner)
+                )
+                .unwrap();
+            }
+            None if !parent.is_empty() => {
+                write!(&mut result, "{}", Style::Cyan.paint(true, parent)).unwrap();
+            }
+            None => {}
+        }
+        result.push_str(separator);
+    }
+    write!(&mut result, "{}", Style::Bold.paint(true, repo)).unwrap();
+    result
+}
+
+/// Narrows the widest columns until the table fits in `max_width`.
+fn fit_column_widths(widths: &mut [usize], max_width: usize) {
+    let separators = COLUMN_SEPARATOR.len() * widths.len().saturating_sub(1);
+    let mut total = widths.iter().sum::<usize>() + separators;
+    while total > max_width {
+        let Some(widest) = widths
+            .iter_mut()
+            .filter(|width| **width > MIN_COLUMN_WIDTH)
+            .max_by_key(|width| **width)
+        else {
+            break;
+        };
+        *widest -= 1;
+        total -= 1;
+    }
+}
+
+/// Truncates `s` to `width` columns, marking truncated cells with `…`.
+pub(super) fn truncate_to_width(s: &str, width: usize) -> String {
+    if s.width() <= width {
+        return s.to_owned();
+    }
+    let mut result = String::new();
+    let mut result_width = 0;
+    for ch in s.chars() {
+        let ch_width = ch.width().unwrap_or(0);
+        if result_width + ch_width + 1 > width {
+            break;
+        }
+        result.push(ch);
+        result_width += ch_width;
+    }
+    result.push('…');
+    result
+}
+
 fn render_json<W, Roots, Repos>(mut out: W, roots: Roots) -> Result<()>
 where
     W: io::Write,
@@ -74,11 +376,10 @@ where
     let list = JsonList {
         roots: roots
             .map(|(root, repos)| JsonRoot {
-                name: root.name().to_owned(),
-                display_path: root.path().as_display_path().to_owned(),
-                real_path: root.path().as_real_path().to_owned(),
-                canonical_path: root.canonical_path().to_owned(),
-                repos: repos.map(JsonRepo::from).collect(),
+                fields: root_fields(&root),
+                repos: repos
+                    .map(|repo| RepoListTemplateContext::new(&root, &repo, false))
+                    .collect(),
             })
             .collect(),
     };
@@ -101,6 +402,10 @@ pub(in crate::cli) struct RepoListTemplateContext {
     repo_display_path: String,
     repo_real_path: String,
     repo_canonical_path: String,
+    repo_kind: String,
+    repo_common_dir: String,
+    #[serde(flatten)]
+    colors: TemplateColors,
 }
 
 impl TemplateContext for RepoListTemplateContext {}
@@ -109,55 +414,156 @@ fn format_displayable_path(path: impl Display) -> String {
     path.to_string()
 }
 
+/// Returns the fields of the root columns.
+fn root_fields(root: &CanonicalRoot) -> [(Column, String); 4] {
+    [
+        (Column::RootName, root.name().to_owned()),
+        (
+            Column::RootDisplayPath,
+            format_displayable_path(root.path().as_display_path().display()),
+        ),
+        (
+            Column::RootRealPath,
+            format_displayable_path(root.path().as_real_path().display()),
+        ),
+        (
+            Column::RootCanonicalPath,
+            format_displayable_path(root.canonical_path().display()),
+        ),
+    ]
+}
+
 impl RepoListTemplateContext {
-    pub(in crate::cli) fn new(root: &CanonicalRoot, repo: &CanonicalRepo) -> Self {
+    pub(in crate::cli) fn new(root: &CanonicalRoot, repo: &CanonicalRepo, color: bool) -> Self {
+        let [
+            root_name,
+            root_display_path,
+            root_real_path,
+            root_canonical_path,
+        ] = root_fields(root).map(|(_, field)| field);
         Self {
-            root_name: root.name().to_owned(),
-            root_display_path: format_displayable_path(root.path().as_display_path().display()),
-            root_real_path: format_displayable_path(root.path().as_real_path().display()),
-            root_canonical_path: format_displayable_path(root.canonical_path().display()),
+            root_name,
+            root_display_path,
+            root_real_path,
+            root_canonical_path,
             repo_relative_path: format_displayable_path(
                 repo.relative_path().as_real_path().display(),
             ),
             repo_display_path: format_displayable_path(repo.path().as_display_path().display()),
             repo_real_path: format_displayable_path(repo.path().as_real_path().display()),
             repo_canonical_path: format_displayable_path(repo.canonical_path().display()),
+            repo_kind: repo.kind().to_string(),
+            repo_common_dir: format_displayable_path(repo.common_dir().display()),
+            colors: TemplateColors::new(color),
+        }
+    }
+
+    pub(in crate::cli) fn field(&self, column: Column) -> &str {
+        match column {
+            Column::RootName => &self.root_name,
+            Column::RootDisplayPath => &self.root_display_path,
+            Column::RootRealPath => &self.root_real_path,
+            Column::RootCanonicalPath => &self.root_canonical_path,
+            Column::RepoRelativePath => &self.repo_relative_path,
+            Column::RepoDisplayPath => &self.repo_display_path,
+            Column::RepoRealPath => &self.repo_real_path,
+            Column::RepoCanonicalPath => &self.repo_canonical_path,
+            Column::RepoKind => &self.repo_kind,
+            Column::RepoCommonDir => &self.repo_common_dir,
         }
     }
 }
 
+/// Every root and repository, with the same field names as the other formats.
 #[derive(Debug, Serialize)]
-#[serde(rename_all = "camelCase")]
 struct JsonList {
     roots: Vec<JsonRoot>,
 }
 
-#[derive(Debug, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug)]
 struct JsonRoot {
-    name: String,
-    real_path: PathBuf,
-    display_path: PathBuf,
-    canonical_path: PathBuf,
-    repos: Vec<JsonRepo>,
+    fields: [(Column, String); 4],
+    repos: Vec<RepoListTemplateContext>,
 }
 
-#[derive(Debug, Serialize)]
-#[serde(rename_all = "camelCase")]
-struct JsonRepo {
-    relative_path: PathBuf,
-    real_path: PathBuf,
-    display_path: PathBuf,
-    canonical_path: PathBuf,
+impl Serialize for JsonRoot {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let repo_columns = Column::ALL
+            .iter()
+            .copied()
+            .filter(|column| !column.is_root())
+            .collect::<Vec<_>>();
+        let repos = self
+            .repos
+            .iter()
+            .map(|context| ColumnRecord {
+                context,
+                columns: &repo_columns,
+            })
+            .collect::<Vec<_>>();
+
+        let mut map = serializer.serialize_map(Some(self.fields.len() + 1))?;
+        for (column, field) in &self.fields {
+            map.serialize_entry(column.name(), field)?;
+        }
+        map.serialize_entry("repos", &repos)?;
+        map.end()
+    }
 }
 
-impl From<CanonicalRepo> for JsonRepo {
-    fn from(value: CanonicalRepo) -> Self {
-        Self {
-            relative_path: value.relative_path().as_real_path().to_owned(),
-            display_path: value.path().as_display_path().to_owned(),
-            real_path: value.path().as_real_path().to_owned(),
-            canonical_path: value.canonical_path().to_owned(),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_column_widths_narrows_widest_columns() {
+        let mut widths = vec![7, 40, 20];
+        fit_column_widths(&mut widths, 50);
+        assert_eq!(widths, [7, 20, 19]);
+
+        let mut widths = vec![10, 10];
+        fit_column_widths(&mut widths, 5);
+        assert_eq!(widths, [MIN_COLUMN_WIDTH, MIN_COLUMN_WIDTH]);
+    }
+
+    #[test]
+    fn truncate_to_width_marks_truncated_cells() {
+        assert_eq!(truncate_to_width("abc", 3), "abc");
+        assert_eq!(truncate_to_width("abcdef", 4), "abc…");
+        assert_eq!(truncate_to_width("日本語テキスト", 6), "日本…");
+    }
+
+    #[test]
+    fn escape_csv_quotes_special_fields() {
+        assert_eq!(escape_csv("plain/path"), "plain/path");
+        assert_eq!(escape_csv("a,b"), r#""a,b""#);
+        assert_eq!(escape_csv("say \"hi\""), r#""say ""hi""""#);
+        assert_eq!(escape_csv("line\nbreak"), "\"line\nbreak\"");
+    }
+
+    #[test]
+    fn escape_tsv_escapes_control_characters() {
+        assert_eq!(escape_tsv("plain path"), "plain path");
+        assert_eq!(escape_tsv("a\tb\nc\rd\\e"), r"a\tb\nc\rd\\e");
+    }
+
+    #[test]
+    fn paint_relative_path_colors_host_owner_and_repo() {
+        assert_eq!(paint_relative_path("a/b/c", false), "a/b/c");
+        if path::MAIN_SEPARATOR != '/' {
+            return;
         }
+        assert_eq!(
+            paint_relative_path("example.com/owner/repo", true),
+            "\x1b[34mexample.com\x1b[0m/\x1b[36mowner\x1b[0m/\x1b[1mrepo\x1b[0m"
+        );
+        assert_eq!(
+            paint_relative_path("owner/repo", true),
+            "\x1b[36mowner\x1b[0m/\x1b[1mrepo\x1b[0m"
+        );
+        assert_eq!(paint_relative_path("repo", true), "\x1b[1mrepo\x1b[0m");
     }
 }
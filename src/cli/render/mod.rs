@@ -1 +1,4 @@
+pub(in crate::cli) mod grep;
 pub(in crate::cli) mod list;
+pub(in crate::cli) mod pick;
+pub(in crate::cli) mod query;
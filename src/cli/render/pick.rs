@@ -0,0 +1,285 @@
+use std::{cmp::Reverse, io, path::PathBuf};
+
+use crossterm::{
+    cursor::{MoveTo, Show},
+    event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    queue,
+    style::{Attribute, Print, SetAttribute},
+    terminal::{Clear, ClearType},
+};
+use fuzzy_matcher::{FuzzyMatcher as _, skim::SkimMatcherV2};
+use unicode_width::UnicodeWidthStr as _;
+
+use crate::cli::render::list::truncate_to_width;
+
+const PROMPT: &str = "> ";
+/// Rows used by the prompt and the status line.
+const HEADER_ROWS: u16 = 2;
+
+/// State of the interactive repository picker.
+pub(in crate::cli) struct Picker {
+    matcher: SkimMatcherV2,
+    items: Vec<PickerItem>,
+    query: String,
+    /// Indices of the items matching the query, best match first.
+    matches: Vec<(Reverse<i64>, usize)>,
+    cursor: usize,
+    scroll: usize,
+    scanning: bool,
+}
+
+#[derive(Debug)]
+struct PickerItem {
+    label: String,
+    path: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(in crate::cli) enum PickerAction {
+    Continue,
+    Select,
+    Cancel,
+}
+
+impl Picker {
+    pub(in crate::cli) fn new(query: &str) -> Self {
+        Self {
+            matcher: SkimMatcherV2::default(),
+            items: vec![],
+            query: query.to_owned(),
+            matches: vec![],
+            cursor: 0,
+            scroll: 0,
+            scanning: true,
+        }
+    }
+
+    /// Adds a candidate found by the scan.
+    pub(in crate::cli) fn push(&mut self, label: String, path: PathBuf) {
+        let index = self.items.len();
+        if let Some(score) = self.score(&label) {
+            let key = (Reverse(score), index);
+            let pos = self.matches.partition_point(|m| *m < key);
+            // Keep the cursor on the selected item when a better match is
+            // inserted above it.
+            if !self.matches.is_empty() && pos <= self.cursor {
+                self.cursor += 1;
+            }
+            self.matches.insert(pos, key);
+        }
+        self.items.push(PickerItem { label, path });
+    }
+
+    pub(in crate::cli) fn finish_scan(&mut self) {
+        self.scanning = false;
+    }
+
+    /// Returns the path of the item under the cursor.
+    pub(in crate::cli) fn selected(&self) -> Option<&PathBuf> {
+        let (_, index) = self.matches.get(self.cursor)?;
+        Some(&self.items[*index].path)
+    }
+
+    pub(in crate::cli) fn handle_key(&mut self, key: KeyEvent) -> PickerAction {
+        if key.kind == KeyEventKind::Release {
+            return PickerAction::Continue;
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Enter => return PickerAction::Select,
+            KeyCode::Esc => return PickerAction::Cancel,
+            KeyCode::Char('c' | 'g') if ctrl => return PickerAction::Cancel,
+            KeyCode::Up => self.move_cursor(-1),
+            KeyCode::Char('p' | 'k') if ctrl => self.move_cursor(-1),
+            KeyCode::Down => self.move_cursor(1),
+            KeyCode::Char('n' | 'j') if ctrl => self.move_cursor(1),
+            KeyCode::Char('u') if ctrl => self.set_query(String::new()),
+            KeyCode::Char('w') if ctrl => {
+                let query = self.query.trim_end();
+                let end = query.rfind(char::is_whitespace).map_or(0, |idx| idx + 1);
+                self.set_query(query[..end].to_owned());
+            }
+            KeyCode::Backspace => {
+                let mut query = self.query.clone();
+                query.pop();
+                self.set_query(query);
+            }
+            KeyCode::Char(ch) if !ctrl => {
+                let mut query = self.query.clone();
+                query.push(ch);
+                self.set_query(query);
+            }
+            _ => {}
+        }
+        PickerAction::Continue
+    }
+
+    pub(in crate::cli) fn draw<W>(
+        &mut self,
+        mut out: W,
+        (width, height): (u16, u16),
+    ) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let width = usize::from(width);
+        let visible_rows = usize::from(height.saturating_sub(HEADER_ROWS));
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        } else if visible_rows > 0 && self.cursor >= self.scroll + visible_rows {
+            self.scroll = self.cursor + 1 - visible_rows;
+        }
+
+        let status = format!(
+            "  {}/{}{}",
+            self.matches.len(),
+            self.items.len(),
+            if self.scanning { " (scanning...)" } else { "" }
+        );
+        queue!(
+            out,
+            MoveTo(0, 1),
+            Clear(ClearType::CurrentLine),
+            SetAttribute(Attribute::Dim),
+            Print(truncate_to_width(&status, width)),
+            SetAttribute(Attribute::Reset),
+        )?;
+
+        let rows = self.matches.iter().skip(self.scroll).take(visible_rows);
+        for (row, (_, index)) in (HEADER_ROWS..).zip(rows) {
+            let is_selected = usize::from(row - HEADER_ROWS) + self.scroll == self.cursor;
+            let marker = if is_selected { PROMPT } else { "  " };
+            let line = format!("{marker}{}", self.items[*index].label);
+            queue!(out, MoveTo(0, row), Clear(ClearType::CurrentLine))?;
+            if is_selected {
+                queue!(out, SetAttribute(Attribute::Reverse))?;
+            }
+            queue!(
+                out,
+                Print(truncate_to_width(&line, width)),
+                SetAttribute(Attribute::Reset)
+            )?;
+        }
+        let drawn_rows = self
+            .matches
+            .len()
+            .saturating_sub(self.scroll)
+            .min(visible_rows);
+        queue!(
+            out,
+            MoveTo(
+                0,
+                HEADER_ROWS.saturating_add(u16::try_from(drawn_rows).unwrap_or(u16::MAX))
+            ),
+            Clear(ClearType::FromCursorDown),
+        )?;
+
+        let prompt = truncate_to_width(&format!("{PROMPT}{}", self.query), width);
+        queue!(
+            out,
+            MoveTo(0, 0),
+            Clear(ClearType::CurrentLine),
+            Print(&prompt),
+            MoveTo(u16::try_from(prompt.width()).unwrap_or(u16::MAX), 0),
+            Show,
+        )?;
+        out.flush()
+    }
+
+    fn score(&self, label: &str) -> Option<i64> {
+        if self.query.is_empty() {
+            return Some(0);
+        }
+        self.matcher.fuzzy_match(label, &self.query)
+    }
+
+    fn set_query(&mut self, query: String) {
+        self.query = query;
+        self.matches = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| Some((Reverse(self.score(&item.label)?), index)))
+            .collect();
+        self.matches.sort_unstable();
+        self.cursor = 0;
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        if self.matches.is_empty() {
+            return;
+        }
+        self.cursor = self
+            .cursor
+            .saturating_add_signed(delta)
+            .min(self.matches.len() - 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_query(picker: &mut Picker, query: &str) {
+        for ch in query.chars() {
+            picker.handle_key(key(KeyCode::Char(ch)));
+        }
+    }
+
+    #[test]
+    fn picker_filters_and_selects() {
+        let mut picker = Picker::new("");
+        picker.push(
+            "default github.com/gifnksm/souko".to_owned(),
+            "/souko".into(),
+        );
+        picker.push(
+            "default github.com/rust-lang/rust".to_owned(),
+            "/rust".into(),
+        );
+        assert_eq!(picker.selected(), Some(&PathBuf::from("/souko")));
+
+        type_query(&mut picker, "rust");
+        assert_eq!(picker.selected(), Some(&PathBuf::from("/rust")));
+        assert_eq!(picker.matches.len(), 1);
+
+        picker.handle_key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+        assert_eq!(picker.matches.len(), 2);
+        picker.handle_key(key(KeyCode::Down));
+        assert_eq!(picker.selected(), Some(&PathBuf::from("/rust")));
+        picker.handle_key(key(KeyCode::Down));
+        assert_eq!(picker.selected(), Some(&PathBuf::from("/rust")));
+
+        assert_eq!(picker.handle_key(key(KeyCode::Enter)), PickerAction::Select);
+        assert_eq!(picker.handle_key(key(KeyCode::Esc)), PickerAction::Cancel);
+    }
+
+    #[test]
+    fn picker_matches_items_pushed_after_query() {
+        let mut picker = Picker::new("xyz");
+        picker.push("abc".to_owned(), "/abc".into());
+        picker.push("xyz".to_owned(), "/xyz".into());
+        assert_eq!(picker.matches.len(), 1);
+        assert_eq!(picker.selected(), Some(&PathBuf::from("/xyz")));
+    }
+
+    #[test]
+    fn picker_keeps_selection_when_better_match_is_pushed() {
+        let mut picker = Picker::new("abc");
+        picker.push("a-b-c-1".to_owned(), "/1".into());
+        picker.push("a-b-c-2".to_owned(), "/2".into());
+        picker.handle_key(key(KeyCode::Down));
+        assert_eq!(picker.selected(), Some(&PathBuf::from("/2")));
+
+        picker.push("abc".to_owned(), "/abc".into());
+        assert_eq!(picker.matches[0].1, 2);
+        assert_eq!(picker.selected(), Some(&PathBuf::from("/2")));
+        picker.handle_key(key(KeyCode::Up));
+        picker.handle_key(key(KeyCode::Up));
+        assert_eq!(picker.selected(), Some(&PathBuf::from("/abc")));
+    }
+}
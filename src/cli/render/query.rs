@@ -0,0 +1,127 @@
+use std::{io, path::PathBuf};
+
+use color_eyre::eyre::Result;
+use serde::Serialize;
+
+use crate::domain::model::{
+    path_like::PathLike as _,
+    query::{ExpansionStep, Query},
+    repo::Repo,
+};
+
+/// Where a query would be placed in a root.
+#[derive(Debug)]
+pub(in crate::cli) struct RootPlacement {
+    pub(in crate::cli) name: String,
+    pub(in crate::cli) selected: bool,
+    pub(in crate::cli) repo: Result<Repo, String>,
+}
+
+pub(in crate::cli) fn render<W>(
+    mut out: W,
+    json: bool,
+    query: &Query,
+    placements: &[RootPlacement],
+) -> Result<()>
+where
+    W: io::Write,
+{
+    if json {
+        render_json(&mut out, query, placements)
+    } else {
+        render_default(&mut out, query, placements)
+    }
+}
+
+fn render_default<W>(mut out: W, query: &Query, placements: &[RootPlacement]) -> Result<()>
+where
+    W: io::Write,
+{
+    writeln!(&mut out, "query: {}", query.original_query())?;
+    for (i, step) in query.steps().iter().enumerate() {
+        writeln!(&mut out, "step #{}: {step}", i + 1)?;
+        writeln!(&mut out, "  -> {}", step.result())?;
+    }
+    writeln!(&mut out, "url: {}", query.url())?;
+    if let Some(push_url) = query.push_url() {
+        writeln!(&mut out, "push url: {push_url}")?;
+    }
+    if query.layout_url() != query.url() {
+        writeln!(&mut out, "layout url: {}", query.layout_url())?;
+    }
+    for placement in placements {
+        let marker = if placement.selected {
+            " (selected)"
+        } else {
+            ""
+        };
+        match &placement.repo {
+            Ok(repo) => writeln!(
+                &mut out,
+                "root {}{marker}: {}",
+                placement.name,
+                repo.relative_path().display()
+            )?,
+            Err(e) => writeln!(&mut out, "root {}{marker}: error: {e}", placement.name)?,
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn render_json<W>(mut out: W, query: &Query, placements: &[RootPlacement]) -> Result<()>
+where
+    W: io::Write,
+{
+    let explain = JsonExplain {
+        query: query.original_query(),
+        steps: query.steps(),
+        url: query.url().as_str(),
+        push_url: query.push_url(),
+        layout_url: query.layout_url().as_str(),
+        roots: placements.iter().map(JsonRoot::from).collect(),
+    };
+
+    serde_json::to_writer(&mut out, &explain)?;
+    out.flush()?;
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonExplain<'a> {
+    query: &'a str,
+    steps: &'a [ExpansionStep],
+    url: &'a str,
+    push_url: Option<&'a str>,
+    layout_url: &'a str,
+    roots: Vec<JsonRoot<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonRoot<'a> {
+    name: &'a str,
+    selected: bool,
+    relative_path: Option<PathBuf>,
+    real_path: Option<PathBuf>,
+    display_path: Option<PathBuf>,
+    error: Option<&'a str>,
+}
+
+impl<'a> From<&'a RootPlacement> for JsonRoot<'a> {
+    fn from(value: &'a RootPlacement) -> Self {
+        let (repo, error) = match &value.repo {
+            Ok(repo) => (Some(repo), None),
+            Err(e) => (None, Some(e.as_str())),
+        };
+        Self {
+            name: &value.name,
+            selected: value.selected,
+            relative_path: repo.map(|repo| repo.relative_path().to_owned()),
+            real_path: repo.map(|repo| repo.path().as_real_path().to_owned()),
+            display_path: repo.map(|repo| repo.path().as_display_path().to_owned()),
+            error,
+        }
+    }
+}
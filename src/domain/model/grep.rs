@@ -0,0 +1,124 @@
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+
+/// Number of leading bytes inspected to detect binary files, the same as git.
+const BINARY_DETECTION_LEN: usize = 8000;
+
+/// A line matching the search pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LineMatch {
+    line_number: usize,
+    line: String,
+    ranges: Vec<Range<usize>>,
+}
+
+impl LineMatch {
+    /// Returns the 1-based line number.
+    pub(crate) fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// Returns the line without the line terminator.
+    ///
+    /// Invalid UTF-8 sequences are replaced with U+FFFD.
+    pub(crate) fn line(&self) -> &str {
+        &self.line
+    }
+
+    /// Returns the byte ranges of the matches in the line.
+    pub(crate) fn ranges(&self) -> &[Range<usize>] {
+        &self.ranges
+    }
+}
+
+/// Lines matching the search pattern in a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FileMatches {
+    path: PathBuf,
+    lines: Vec<LineMatch>,
+}
+
+impl FileMatches {
+    /// Searches `content` of the file at `path` for `pattern`.
+    ///
+    /// Returns `None` if no line matches or the file looks binary.
+    pub(crate) fn search(path: &Path, content: &[u8], pattern: &Regex) -> Option<Self> {
+        let head = &content[..content.len().min(BINARY_DETECTION_LEN)];
+        if head.contains(&0) {
+            return None;
+        }
+
+        let content = String::from_utf8_lossy(content);
+        let lines = content
+            .lines()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                let ranges = pattern
+                    .find_iter(line)
+                    .map(|m| m.range())
+                    .collect::<Vec<_>>();
+                (!ranges.is_empty()).then(|| LineMatch {
+                    line_number: i + 1,
+                    line: line.to_owned(),
+                    ranges,
+                })
+            })
+            .collect::<Vec<_>>();
+        (!lines.is_empty()).then(|| Self {
+            path: path.to_owned(),
+            lines,
+        })
+    }
+
+    /// Returns the path of the file relative to the repository.
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub(crate) fn lines(&self) -> &[LineMatch] {
+        &self.lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_returns_matching_lines() {
+        let pattern = Regex::new("fo+").unwrap();
+        let matches =
+            FileMatches::search(Path::new("a.txt"), b"foo bar\nbaz\r\nfoo fooo\n", &pattern)
+                .unwrap();
+        assert_eq!(matches.path(), Path::new("a.txt"));
+        let lines = matches.lines();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].line_number(), 1);
+        assert_eq!(lines[0].line(), "foo bar");
+        assert_eq!(lines[0].ranges(), &[Range { start: 0, end: 3 }]);
+        assert_eq!(lines[1].line_number(), 3);
+        assert_eq!(lines[1].ranges(), [0..3, 4..8]);
+    }
+
+    #[test]
+    fn search_returns_none_without_matches() {
+        let pattern = Regex::new("qux").unwrap();
+        assert_eq!(
+            FileMatches::search(Path::new("a.txt"), b"foo\n", &pattern),
+            None
+        );
+    }
+
+    #[test]
+    fn search_skips_binary_files() {
+        let pattern = Regex::new("foo").unwrap();
+        assert_eq!(
+            FileMatches::search(Path::new("a.bin"), b"foo\0bar\n", &pattern),
+            None
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use std::path::{Component, PathBuf};
+
+use thiserror::Error;
+
+use super::{
+    query::Query,
+    repo_url::{RepoUrlTemplateContext, url_path_segments},
+    template::Template,
+};
+
+#[derive(Debug, Error)]
+pub(crate) enum RepoLayoutError {
+    #[error("repository path `{path}` must be a relative path inside the root")]
+    InvalidPath { path: String },
+}
+
+pub(crate) const DEFAULT_LOCAL_PREFIX: &str = "local";
+
+/// Rule that decides where a repository cloned from a URL is placed under a root.
+///
+/// By default, repositories are placed at `<host>[:<port>]/<path>`, and
+/// repositories cloned from local paths or `file://` URLs at `<local prefix>/<path>`.
+#[derive(Debug, Clone)]
+pub(crate) struct RepoLayout {
+    template: Option<Template<RepoUrlTemplateContext>>,
+    local_prefix: String,
+}
+
+impl Default for RepoLayout {
+    fn default() -> Self {
+        Self {
+            template: None,
+            local_prefix: DEFAULT_LOCAL_PREFIX.to_owned(),
+        }
+    }
+}
+
+impl RepoLayout {
+    pub(crate) fn new(template: Template<RepoUrlTemplateContext>) -> Self {
+        Self {
+            template: Some(template),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn with_local_prefix(self, local_prefix: impl Into<String>) -> Self {
+        Self {
+            local_prefix: local_prefix.into(),
+            ..self
+        }
+    }
+
+    /// Returns the path of the repository relative to the root.
+    pub(crate) fn relative_path(
+        &self,
+        query: &Query,
+        bare: bool,
+    ) -> Result<PathBuf, RepoLayoutError> {
+        let url = query.layout_url();
+        let expanded = match &self.template {
+            Some(template) => template.expand(&RepoUrlTemplateContext::new(url, bare)),
+            None => {
+                let mut parts = vec![];
+                if url.scheme() == "file" {
+                    parts.push(self.local_prefix.clone());
+                } else if let Some(host) = url.host_str() {
+                    match url.port() {
+                        Some(port) => parts.push(format!("{host}:{port}")),
+                        None => parts.push(host.to_owned()),
+                    }
+                }
+                parts.extend(url_path_segments(url, bare));
+                parts.join("/")
+            }
+        };
+
+        let mut relative_path = PathBuf::new();
+        for part in expanded.split('/').filter(|part| !part.is_empty()) {
+            relative_path.push(part);
+        }
+        let is_valid = !relative_path.as_os_str().is_empty()
+            && relative_path
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)));
+        if !is_valid {
+            return Err(RepoLayoutError::InvalidPath { path: expanded });
+        }
+        Ok(relative_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, str::FromStr as _};
+
+    use super::*;
+    use crate::domain::model::query::ParseOption;
+
+    #[test]
+    fn relative_path_with_template() {
+        let parse_option = ParseOption::default();
+
+        let cases = [
+            (
+                "{host}/{owner}/{repo}",
+                "https://github.com/owner/repo.git",
+                "github.com/owner/repo",
+            ),
+            (
+                "{owner}/{repo}",
+                "ssh://git@github.com/owner/repo.git",
+                "owner/repo",
+            ),
+            (
+                "{host}/{owner}/{repo}",
+                "https://gitlab.com/group/subgroup/repo.git",
+                "gitlab.com/group/subgroup/repo",
+            ),
+            (
+                "{scheme}/{user}@{host}_{port}/{path}",
+                "ssh://git@example.com:2222/owner/repo.git",
+                "ssh/git@example.com_2222/owner/repo",
+            ),
+            (
+                "{host}//{path}/",
+                "https://github.com/owner///repo.git",
+                "github.com/owner/repo",
+            ),
+        ];
+
+        for (template, url, expected) in cases {
+            let layout = RepoLayout::new(Template::from_str(template).unwrap());
+            let query = Query::parse(url, &parse_option).unwrap();
+            assert_eq!(
+                layout.relative_path(&query, false).unwrap(),
+                Path::new(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn relative_path_with_invalid_template() {
+        let parse_option = ParseOption::default();
+        let query = Query::parse("https://github.com/owner/repo.git", &parse_option).unwrap();
+
+        for template in ["", "{port}", "{host}/../{repo}", "./{repo}"] {
+            let layout = RepoLayout::new(Template::from_str(template).unwrap());
+            assert!(matches!(
+                layout.relative_path(&query, false).unwrap_err(),
+                RepoLayoutError::InvalidPath { .. }
+            ));
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn relative_path_of_local_repo() {
+        let parse_option = ParseOption::default();
+        let query = Query::parse("/srv/git/foo.git", &parse_option).unwrap();
+
+        assert_eq!(
+            RepoLayout::default().relative_path(&query, false).unwrap(),
+            Path::new("local/srv/git/foo")
+        );
+        assert_eq!(
+            RepoLayout::default()
+                .with_local_prefix("offline/mirror")
+                .relative_path(&query, true)
+                .unwrap(),
+            Path::new("offline/mirror/srv/git/foo.git")
+        );
+        assert!(matches!(
+            RepoLayout::default()
+                .with_local_prefix("..")
+                .relative_path(&query, false)
+                .unwrap_err(),
+            RepoLayoutError::InvalidPath { .. }
+        ));
+    }
+}
@@ -1,7 +1,14 @@
+pub(crate) mod grep;
+pub(crate) mod layout;
 pub(crate) mod path_buf_pair;
 pub(crate) mod path_like;
 pub(crate) mod query;
+pub(crate) mod remote;
 pub(crate) mod repo;
+pub(crate) mod repo_url;
 pub(crate) mod root;
 pub(crate) mod scheme;
 pub(crate) mod template;
+pub(crate) mod url_rewrite;
+pub(crate) mod visit;
+pub(crate) mod worktree;
@@ -1,6 +1,7 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt::{self, Display},
+    path::{Component, Path, PathBuf},
 };
 
 use serde::Serialize;
@@ -9,12 +10,105 @@ use url::Url;
 
 use crate::domain::model::template::TemplateContext;
 
-use super::{scheme::Scheme, template::Template};
+use super::{
+    repo::RepoNormalization, scheme::Scheme, template::Template, url_rewrite::UrlRewrites,
+};
 
 #[derive(Debug, Clone)]
 pub(crate) struct Query {
     original_query: String,
     url: Url,
+    push_url: Option<String>,
+    layout_url: Url,
+    remotes: Vec<(String, String)>,
+    steps: Vec<ExpansionStep>,
+}
+
+/// A step taken by [`Query::parse`] to expand a query into a URL.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "kebab-case",
+    rename_all_fields = "camelCase"
+)]
+pub(crate) enum ExpansionStep {
+    /// The default scheme was prepended to a query without scheme.
+    DefaultScheme { scheme: String, result: String },
+    /// A scheme alias was replaced with its target scheme.
+    SchemeAlias {
+        alias: String,
+        scheme: String,
+        result: String,
+    },
+    /// A custom scheme template was expanded.
+    CustomScheme {
+        scheme: String,
+        template: String,
+        host: Option<String>,
+        result: String,
+    },
+    /// An unknown scheme was interpreted as scp-like syntax.
+    ScpLike { result: String },
+    /// A local path was converted to a `file://` URL.
+    LocalPath { result: String },
+    /// A URL prefix was rewritten by an `insteadOf` rule.
+    UrlRewrite { base: String, result: String },
+    /// The default port of the URL scheme was removed.
+    DefaultPortRemoved { port: u16, result: String },
+    /// A host alias was resolved for the layout URL.
+    HostAlias {
+        alias: String,
+        host: String,
+        result: String,
+    },
+    /// The layout URL was normalized.
+    Normalization { result: String },
+}
+
+impl ExpansionStep {
+    /// Returns the query or URL after this step.
+    pub(crate) fn result(&self) -> &str {
+        match self {
+            Self::DefaultScheme { result, .. }
+            | Self::SchemeAlias { result, .. }
+            | Self::CustomScheme { result, .. }
+            | Self::ScpLike { result }
+            | Self::LocalPath { result }
+            | Self::UrlRewrite { result, .. }
+            | Self::DefaultPortRemoved { result, .. }
+            | Self::HostAlias { result, .. }
+            | Self::Normalization { result } => result,
+        }
+    }
+}
+
+impl Display for ExpansionStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DefaultScheme { scheme, .. } => write!(f, "default scheme `{scheme}`"),
+            Self::SchemeAlias { alias, scheme, .. } => {
+                write!(f, "scheme alias `{alias}` -> `{scheme}`")
+            }
+            Self::CustomScheme {
+                scheme,
+                template,
+                host,
+                ..
+            } => {
+                write!(f, "custom scheme `{scheme}` (template `{template}`")?;
+                if let Some(host) = host {
+                    write!(f, ", host `{host}`")?;
+                }
+                write!(f, ")")
+            }
+            Self::ScpLike { .. } => write!(f, "scp-like syntax"),
+            Self::LocalPath { .. } => write!(f, "local path"),
+            Self::UrlRewrite { base, .. } => write!(f, "URL rewrite to `{base}`"),
+            Self::DefaultPortRemoved { port, .. } => write!(f, "default port {port} removed"),
+            Self::HostAlias { alias, host, .. } => write!(f, "host alias `{alias}` -> `{host}`"),
+            Self::Normalization { .. } => write!(f, "normalization"),
+        }
+    }
 }
 
 #[derive(Debug, Error)]
@@ -35,6 +129,28 @@ pub(crate) enum ParseError {
         original_query: String,
         expanded_query: String,
     },
+    #[error("scheme `{scheme}` requires a host, use `{scheme}+<host>:<path>` {}", ErrorDisplayHelper { original_query, expanded_query })]
+    HostRequired {
+        scheme: String,
+        original_query: String,
+        expanded_query: String,
+    },
+    #[error("cannot replace host with `{host}` {}", ErrorDisplayHelper { original_query, expanded_query })]
+    InvalidHost {
+        host: String,
+        original_query: String,
+        expanded_query: String,
+    },
+    #[error("relative local path requires a working directory {}", ErrorDisplayHelper { original_query, expanded_query })]
+    RelativeLocalPath {
+        original_query: String,
+        expanded_query: String,
+    },
+    #[error("invalid local path {}", ErrorDisplayHelper { original_query, expanded_query })]
+    InvalidLocalPath {
+        original_query: String,
+        expanded_query: String,
+    },
 }
 
 #[derive(Debug)]
@@ -62,32 +178,79 @@ pub(crate) struct ParseOption {
     pub(crate) default_scheme: Option<Scheme>,
     pub(crate) scheme_alias: HashMap<Scheme, Scheme>,
     pub(crate) custom_scheme: HashMap<Scheme, Template<CustomSchemeTemplateContext<'static>>>,
+    /// Remotes added by custom schemes, as maps from remote names to templates.
+    ///
+    /// The templates are expanded with the same variables as the custom scheme.
+    pub(crate) custom_scheme_remotes:
+        HashMap<Scheme, BTreeMap<String, Template<CustomSchemeTemplateContext<'static>>>>,
+    pub(crate) url_rewrites: UrlRewrites,
+    /// Base directory of relative local paths such as `./repo` or `../repo`.
+    pub(crate) working_dir: Option<PathBuf>,
+    /// Map from host aliases (such as ssh config `Host`) to real host names.
+    pub(crate) host_alias: HashMap<String, String>,
+    /// Normalization of the URL used to decide the repository path.
+    pub(crate) normalization: RepoNormalization,
+    /// Normalization for queries expanded by the custom scheme or with the URL scheme of the key.
+    ///
+    /// The custom scheme takes precedence over the URL scheme.
+    pub(crate) scheme_normalization: HashMap<Scheme, RepoNormalization>,
 }
 
 #[derive(Debug, Default, Serialize)]
 pub(crate) struct CustomSchemeTemplateContext<'a> {
     path: &'a str,
+    owner: &'a str,
+    repo: &'a str,
+    host: &'a str,
 }
 
 impl<'a> TemplateContext for CustomSchemeTemplateContext<'a> {}
 
 impl<'a> CustomSchemeTemplateContext<'a> {
-    fn new(path: &'a str) -> Self {
-        Self { path }
+    fn new(path: &'a str, host: &'a str) -> Self {
+        let (owner, repo) = path.rsplit_once('/').unwrap_or(("", path));
+        Self {
+            path,
+            owner,
+            repo,
+            host,
+        }
     }
 }
 
 impl Query {
     pub(crate) fn parse(query: &str, option: &ParseOption) -> Result<Self, ParseError> {
         let url_schemes = [
-            "http://", "https://", "ssh://", "git://", "ftp://", "ftps://",
+            "http://", "https://", "ssh://", "git://", "ftp://", "ftps://", "file://",
         ];
         let mut visited_scheme = HashSet::new();
+        let mut rewritten = false;
+        let mut expanded_url = None;
+        let mut custom_scheme = None;
+        let mut remotes = vec![];
+        let mut steps = vec![];
 
         let original_query = query.to_string();
         let mut query = query.to_string();
         loop {
             if url_schemes.iter().any(|scheme| query.starts_with(scheme)) {
+                // The push URL is rewritten from the URL before `insteadOf` rewriting, as git does
+                expanded_url.get_or_insert_with(|| query.clone());
+
+                // URL rewrite, applied at most once as git does. The result is expanded again
+                if !rewritten
+                    && let Some((index, rewritten_query)) = option.url_rewrites.rewrite(&query)
+                {
+                    rewritten = true;
+                    query = rewritten_query;
+                    steps.push(ExpansionStep::UrlRewrite {
+                        base: option.url_rewrites.rule(index).base.clone(),
+                        result: query.clone(),
+                    });
+                    visited_scheme.clear();
+                    continue;
+                }
+
                 // URL detected, no need to expand
                 let mut url = Url::parse(&query).map_err(|e| ParseError::InvalidUrl {
                     original_query: original_query.clone(),
@@ -105,15 +268,77 @@ impl Query {
                         | ("ftp", 21)
                         | ("ftps", 990) => {
                             url.set_port(None).unwrap();
+                            steps.push(ExpansionStep::DefaultPortRemoved {
+                                port,
+                                result: url.to_string(),
+                            });
                         }
                         _ => {}
                     }
                 }
 
+                let push_url = expanded_url
+                    .and_then(|expanded_url| option.url_rewrites.rewrite_push(&expanded_url));
+
+                let mut layout_url = url.clone();
+                if let Some(real_host) = url.host_str().and_then(|host| option.host_alias.get(host))
+                {
+                    // keep the alias if the real host name is not valid for the URL
+                    if layout_url.set_host(Some(real_host)).is_ok() {
+                        steps.push(ExpansionStep::HostAlias {
+                            alias: url.host_str().unwrap_or_default().to_owned(),
+                            host: real_host.clone(),
+                            result: layout_url.to_string(),
+                        });
+                    }
+                }
+                let normalization = custom_scheme
+                    .as_deref()
+                    .and_then(|scheme| option.scheme_normalization.get(scheme))
+                    .or_else(|| option.scheme_normalization.get(url.scheme()))
+                    .unwrap_or(&option.normalization);
+                let before_normalization = layout_url.clone();
+                normalization.apply(&mut layout_url);
+                if layout_url != before_normalization {
+                    steps.push(ExpansionStep::Normalization {
+                        result: layout_url.to_string(),
+                    });
+                }
+
                 return Ok(Self {
                     original_query,
                     url,
+                    push_url,
+                    layout_url,
+                    remotes,
+                    steps,
+                });
+            }
+
+            // local path, converted to a `file://` URL
+            if is_local_path(&query) {
+                let path = Path::new(&query);
+                let path = if path.is_absolute() {
+                    path.to_owned()
+                } else if let Some(working_dir) = &option.working_dir {
+                    working_dir.join(path)
+                } else {
+                    return Err(ParseError::RelativeLocalPath {
+                        original_query,
+                        expanded_query: query,
+                    });
+                };
+                let Ok(url) = Url::from_file_path(normalize_path(&path)) else {
+                    return Err(ParseError::InvalidLocalPath {
+                        original_query,
+                        expanded_query: query,
+                    });
+                };
+                query = url.into();
+                steps.push(ExpansionStep::LocalPath {
+                    result: query.clone(),
                 });
+                continue;
             }
 
             if let Some((scheme, rest)) = query.split_once(':') {
@@ -126,26 +351,108 @@ impl Query {
                 visited_scheme.insert(scheme.to_owned());
 
                 // scheme alias
-                if let Some(scheme) = option.scheme_alias.get(scheme) {
-                    query = format!("{scheme}:{rest}");
+                if let Some(target) = option.scheme_alias.get(scheme) {
+                    let result = format!("{target}:{rest}");
+                    steps.push(ExpansionStep::SchemeAlias {
+                        alias: scheme.to_owned(),
+                        scheme: target.to_string(),
+                        result: result.clone(),
+                    });
+                    query = result;
                     continue;
                 }
 
                 // custom scheme
                 if let Some(template) = option.custom_scheme.get(scheme) {
-                    let context = CustomSchemeTemplateContext::new(rest);
-                    query = template.expand(&context);
+                    if template.uses_variable("host") {
+                        return Err(ParseError::HostRequired {
+                            scheme: scheme.to_owned(),
+                            original_query,
+                            expanded_query: query,
+                        });
+                    }
+                    let context = CustomSchemeTemplateContext::new(rest, "");
+                    custom_scheme = Some(scheme.to_owned());
+                    expand_remotes(&mut remotes, option, scheme, &context);
+                    let result = template.expand(&context);
+                    steps.push(ExpansionStep::CustomScheme {
+                        scheme: scheme.to_owned(),
+                        template: template.to_string(),
+                        host: None,
+                        result: result.clone(),
+                    });
+                    query = result;
                     continue;
                 }
 
+                // custom scheme with a host parameter, `<scheme>+<host>:<path>`
+                if let Some((base, host)) = scheme.split_once('+')
+                    && !host.is_empty()
+                {
+                    if let Some(target) = option.scheme_alias.get(base) {
+                        let result = format!("{target}+{host}:{rest}");
+                        steps.push(ExpansionStep::SchemeAlias {
+                            alias: base.to_owned(),
+                            scheme: target.to_string(),
+                            result: result.clone(),
+                        });
+                        query = result;
+                        continue;
+                    }
+                    if let Some(template) = option.custom_scheme.get(base) {
+                        let context = CustomSchemeTemplateContext::new(rest, host);
+                        let expanded = template.expand(&context);
+                        custom_scheme = Some(base.to_string());
+                        expand_remotes(&mut remotes, option, base, &context);
+                        if template.uses_variable("host") {
+                            steps.push(ExpansionStep::CustomScheme {
+                                scheme: base.to_owned(),
+                                template: template.to_string(),
+                                host: Some(host.to_owned()),
+                                result: expanded.clone(),
+                            });
+                            query = expanded;
+                            continue;
+                        }
+                        // the template has a fixed host, replace it with the given one
+                        let Some(url) = Url::parse(&expanded)
+                            .ok()
+                            .filter(|url| url.has_host())
+                            .and_then(|mut url| url.set_host(Some(host)).ok().map(|()| url))
+                        else {
+                            return Err(ParseError::InvalidHost {
+                                host: host.to_owned(),
+                                original_query,
+                                expanded_query: expanded,
+                            });
+                        };
+                        let result = String::from(url);
+                        steps.push(ExpansionStep::CustomScheme {
+                            scheme: base.to_owned(),
+                            template: template.to_string(),
+                            host: Some(host.to_owned()),
+                            result: result.clone(),
+                        });
+                        query = result;
+                        continue;
+                    }
+                }
+
                 // unknown scheme, assume it's a scp-like syntax
                 query = format!("ssh://{scheme}/{rest}");
+                steps.push(ExpansionStep::ScpLike {
+                    result: query.clone(),
+                });
                 continue;
             }
 
             // no scheme, add default scheme
             if let Some(scheme) = &option.default_scheme {
                 query = format!("{scheme}:{query}");
+                steps.push(ExpansionStep::DefaultScheme {
+                    scheme: scheme.to_string(),
+                    result: query.clone(),
+                });
                 continue;
             }
 
@@ -163,11 +470,79 @@ impl Query {
     pub(crate) fn url(&self) -> &Url {
         &self.url
     }
+
+    /// Returns the remotes added by the custom scheme, as pairs of a remote name
+    /// and a query.
+    pub(crate) fn remotes(&self) -> &[(String, String)] {
+        &self.remotes
+    }
+
+    /// Returns the URL used to decide the repository path under a root.
+    ///
+    /// This is [`Self::url`] with its host alias resolved to the real host name
+    /// and normalized by [`RepoNormalization`].
+    pub(crate) fn layout_url(&self) -> &Url {
+        &self.layout_url
+    }
+
+    /// Returns the URL used for pushing, if it differs from [`Self::url`].
+    pub(crate) fn push_url(&self) -> Option<&str> {
+        self.push_url.as_deref()
+    }
+
+    /// Returns the steps taken to expand the original query into the URL.
+    pub(crate) fn steps(&self) -> &[ExpansionStep] {
+        &self.steps
+    }
+}
+
+/// Expands the remotes of a custom scheme, unless an earlier custom scheme already did.
+fn expand_remotes(
+    remotes: &mut Vec<(String, String)>,
+    option: &ParseOption,
+    scheme: &str,
+    context: &CustomSchemeTemplateContext<'_>,
+) {
+    if !remotes.is_empty() {
+        return;
+    }
+    if let Some(templates) = option.custom_scheme_remotes.get(scheme) {
+        *remotes = templates
+            .iter()
+            .map(|(name, template)| (name.clone(), template.expand(context)))
+            .collect();
+    }
+}
+
+/// Returns `true` if the query is an absolute path or a relative path starting with `.` or `..`.
+fn is_local_path(query: &str) -> bool {
+    Path::new(query).is_absolute()
+        || query.starts_with('/')
+        || matches!(query, "." | "..")
+        || ["./", "../", ".\\", "..\\"]
+            .iter()
+            .any(|prefix| query.starts_with(prefix))
+}
+
+/// Removes `.` and `..` components without accessing the file system.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::model::url_rewrite::UrlRewriteRule;
 
     #[test]
     fn test_parse_with_empty_config() {
@@ -175,6 +550,12 @@ mod tests {
             default_scheme: None,
             scheme_alias: HashMap::new(),
             custom_scheme: HashMap::new(),
+            custom_scheme_remotes: HashMap::new(),
+            url_rewrites: UrlRewrites::default(),
+            working_dir: None,
+            host_alias: HashMap::new(),
+            normalization: RepoNormalization::default(),
+            scheme_normalization: HashMap::new(),
         };
 
         let query = Query::parse("ssh://github.com/gifnksm/souko.git", &option).unwrap();
@@ -214,6 +595,12 @@ mod tests {
                     "https://gitlab.com/{path}.git".parse().unwrap(),
                 ),
             ]),
+            custom_scheme_remotes: HashMap::new(),
+            url_rewrites: UrlRewrites::default(),
+            working_dir: None,
+            host_alias: HashMap::new(),
+            normalization: RepoNormalization::default(),
+            scheme_normalization: HashMap::new(),
         };
 
         let query = Query::parse("ssh://github.com/gifnksm/souko.git", &option).unwrap();
@@ -250,6 +637,12 @@ mod tests {
                 ("d2".parse().unwrap(), "d3:x{path}".parse().unwrap()),
                 ("d4".parse().unwrap(), "d1:y{path}".parse().unwrap()),
             ]),
+            custom_scheme_remotes: HashMap::new(),
+            url_rewrites: UrlRewrites::default(),
+            working_dir: None,
+            host_alias: HashMap::new(),
+            normalization: RepoNormalization::default(),
+            scheme_normalization: HashMap::new(),
         };
 
         let err = Query::parse("c1:test", &option).unwrap_err();
@@ -261,4 +654,263 @@ mod tests {
             "invalid option: circular alias `d4:test` (expanded to `d4:xytest`)"
         );
     }
+
+    #[test]
+    fn test_parse_with_url_rewrites() {
+        let option = ParseOption {
+            default_scheme: Some("gh".parse().unwrap()),
+            scheme_alias: HashMap::new(),
+            custom_scheme: HashMap::from_iter([(
+                "gh".parse().unwrap(),
+                "https://github.com/{path}.git".parse().unwrap(),
+            )]),
+            custom_scheme_remotes: HashMap::new(),
+            url_rewrites: UrlRewrites::new(vec![
+                UrlRewriteRule {
+                    base: "git@github.com:corp/".to_owned(),
+                    instead_of: vec!["https://github.com/corp/".to_owned()],
+                    push_instead_of: vec![],
+                },
+                UrlRewriteRule {
+                    base: "ssh://git@github.com/".to_owned(),
+                    instead_of: vec![],
+                    push_instead_of: vec!["https://github.com/".to_owned()],
+                },
+            ]),
+            working_dir: None,
+            host_alias: HashMap::new(),
+            normalization: RepoNormalization::default(),
+            scheme_normalization: HashMap::new(),
+        };
+
+        let query = Query::parse("corp/app", &option).unwrap();
+        assert_eq!(query.url.as_str(), "ssh://git@github.com/corp/app.git");
+        assert_eq!(query.push_url(), Some("ssh://git@github.com/corp/app.git"));
+
+        let query = Query::parse("gifnksm/souko", &option).unwrap();
+        assert_eq!(query.url.as_str(), "https://github.com/gifnksm/souko.git");
+        assert_eq!(
+            query.push_url(),
+            Some("ssh://git@github.com/gifnksm/souko.git")
+        );
+
+        let query = Query::parse("https://gitlab.com/a/b.git", &option).unwrap();
+        assert_eq!(query.push_url(), None);
+    }
+
+    #[test]
+    fn test_parse_applies_url_rewrite_once() {
+        let option = ParseOption {
+            default_scheme: None,
+            scheme_alias: HashMap::new(),
+            custom_scheme: HashMap::new(),
+            custom_scheme_remotes: HashMap::new(),
+            url_rewrites: UrlRewrites::new(vec![
+                UrlRewriteRule {
+                    base: "https://b.example/".to_owned(),
+                    instead_of: vec!["https://a.example/".to_owned()],
+                    push_instead_of: vec![],
+                },
+                UrlRewriteRule {
+                    base: "https://a.example/".to_owned(),
+                    instead_of: vec!["https://b.example/".to_owned()],
+                    push_instead_of: vec![],
+                },
+                UrlRewriteRule {
+                    base: "https://c.example/x/".to_owned(),
+                    instead_of: vec!["https://c.example/".to_owned()],
+                    push_instead_of: vec![],
+                },
+            ]),
+            working_dir: None,
+            host_alias: HashMap::new(),
+            normalization: RepoNormalization::default(),
+            scheme_normalization: HashMap::new(),
+        };
+
+        let query = Query::parse("https://a.example/repo", &option).unwrap();
+        assert_eq!(query.url.as_str(), "https://b.example/repo");
+
+        let query = Query::parse("https://c.example/repo", &option).unwrap();
+        assert_eq!(query.url.as_str(), "https://c.example/x/repo");
+    }
+
+    #[test]
+    fn test_parse_with_host_parameter() {
+        let option = ParseOption {
+            default_scheme: None,
+            scheme_alias: HashMap::from_iter([("gh".parse().unwrap(), "github".parse().unwrap())]),
+            custom_scheme: HashMap::from_iter([
+                (
+                    "github".parse().unwrap(),
+                    "https://github.com/{path}.git".parse().unwrap(),
+                ),
+                (
+                    "gitea".parse().unwrap(),
+                    "https://{host}/{path}.git".parse().unwrap(),
+                ),
+                (
+                    "azure".parse().unwrap(),
+                    "https://dev.azure.com/{owner}/_git/{repo}".parse().unwrap(),
+                ),
+                (
+                    "scp".parse().unwrap(),
+                    "git@example.com:{path}.git".parse().unwrap(),
+                ),
+            ]),
+            custom_scheme_remotes: HashMap::new(),
+            url_rewrites: UrlRewrites::default(),
+            working_dir: None,
+            host_alias: HashMap::new(),
+            normalization: RepoNormalization::default(),
+            scheme_normalization: HashMap::new(),
+        };
+
+        let query = Query::parse("gitea+git.example.com:owner/repo", &option).unwrap();
+        assert_eq!(query.url.as_str(), "https://git.example.com/owner/repo.git");
+
+        let query = Query::parse("gh+ghe.example.com:owner/repo", &option).unwrap();
+        assert_eq!(query.url.as_str(), "https://ghe.example.com/owner/repo.git");
+
+        let query = Query::parse("azure:org/project/repo", &option).unwrap();
+        assert_eq!(
+            query.url.as_str(),
+            "https://dev.azure.com/org/project/_git/repo"
+        );
+
+        let err = Query::parse("gitea:owner/repo", &option).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "scheme `gitea` requires a host, use `gitea+<host>:<path>` `gitea:owner/repo`"
+        );
+
+        let err = Query::parse("scp+example.org:owner/repo", &option).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot replace host with `example.org` `scp+example.org:owner/repo` (expanded to `git@example.com:owner/repo.git`)"
+        );
+
+        // unknown base scheme is treated as scp-like syntax
+        let query = Query::parse("user+x@example.com:owner/repo", &option).unwrap();
+        assert_eq!(query.url.as_str(), "ssh://user+x@example.com/owner/repo");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_parse_local_path() {
+        let mut option = ParseOption {
+            default_scheme: Some("gh".parse().unwrap()),
+            ..Default::default()
+        };
+
+        let query = Query::parse("file:///srv/git/foo.git", &option).unwrap();
+        assert_eq!(query.url.as_str(), "file:///srv/git/foo.git");
+
+        let query = Query::parse("/srv/git/../git/./foo.git", &option).unwrap();
+        assert_eq!(query.url.as_str(), "file:///srv/git/foo.git");
+
+        let err = Query::parse("./foo", &option).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "relative local path requires a working directory `./foo`"
+        );
+
+        option.working_dir = Some(PathBuf::from("/home/user/src"));
+        let query = Query::parse("../foo", &option).unwrap();
+        assert_eq!(query.url.as_str(), "file:///home/user/foo");
+
+        let query = Query::parse(".", &option).unwrap();
+        assert_eq!(query.url.as_str(), "file:///home/user/src");
+    }
+
+    #[test]
+    fn test_parse_with_custom_scheme_remotes() {
+        let option = ParseOption {
+            custom_scheme: HashMap::from_iter([
+                (
+                    "fork".parse().unwrap(),
+                    "https://github.com/someone/{repo}.git".parse().unwrap(),
+                ),
+                (
+                    "github".parse().unwrap(),
+                    "https://github.com/{path}.git".parse().unwrap(),
+                ),
+            ]),
+            custom_scheme_remotes: HashMap::from_iter([(
+                "fork".parse().unwrap(),
+                BTreeMap::from_iter([("upstream".to_owned(), "github:{path}".parse().unwrap())]),
+            )]),
+            ..Default::default()
+        };
+
+        let query = Query::parse("fork:gifnksm/souko", &option).unwrap();
+        assert_eq!(query.url.as_str(), "https://github.com/someone/souko.git");
+        assert_eq!(
+            query.remotes(),
+            [("upstream".to_owned(), "github:gifnksm/souko".to_owned())]
+        );
+
+        let query = Query::parse("github:gifnksm/souko", &option).unwrap();
+        assert!(query.remotes().is_empty());
+    }
+
+    #[test]
+    fn test_parse_with_host_alias() {
+        let option = ParseOption {
+            host_alias: HashMap::from_iter([("work-gh".to_owned(), "github.com".to_owned())]),
+            ..Default::default()
+        };
+
+        let query = Query::parse("git@work-gh:corp/app.git", &option).unwrap();
+        assert_eq!(query.url().as_str(), "ssh://git@work-gh/corp/app.git");
+        assert_eq!(
+            query.layout_url().as_str(),
+            "ssh://git@github.com/corp/app.git"
+        );
+
+        let query = Query::parse("git@github.com:corp/app.git", &option).unwrap();
+        assert_eq!(query.layout_url(), query.url());
+    }
+
+    #[test]
+    fn test_parse_records_expansion_steps() {
+        let option = ParseOption {
+            default_scheme: Some("gh".parse().unwrap()),
+            scheme_alias: HashMap::from_iter([("gh".parse().unwrap(), "github".parse().unwrap())]),
+            custom_scheme: HashMap::from_iter([(
+                "github".parse().unwrap(),
+                "ssh://git@github.com:22/{path}.git".parse().unwrap(),
+            )]),
+            normalization: RepoNormalization {
+                lowercase_path: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let query = Query::parse("Gifnksm/Souko", &option).unwrap();
+        let steps = query
+            .steps()
+            .iter()
+            .map(|step| format!("{step}: {}", step.result()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            steps,
+            [
+                "default scheme `gh`: gh:Gifnksm/Souko",
+                "scheme alias `gh` -> `github`: github:Gifnksm/Souko",
+                "custom scheme `github` (template `ssh://git@github.com:22/{path}.git`): ssh://git@github.com:22/Gifnksm/Souko.git",
+                "default port 22 removed: ssh://git@github.com/Gifnksm/Souko.git",
+                "normalization: ssh://git@github.com/gifnksm/souko.git",
+            ]
+        );
+
+        let query = Query::parse("git@example.com:owner/repo", &option).unwrap();
+        assert_eq!(
+            query.steps(),
+            [ExpansionStep::ScpLike {
+                result: "ssh://git@example.com/owner/repo".to_owned()
+            }]
+        );
+    }
 }
@@ -0,0 +1,81 @@
+use url::Url;
+
+use super::{query::Query, repo_url::RepoUrlTemplateContext, template::Template};
+
+pub(crate) const ORIGIN_REMOTE_NAME: &str = "origin";
+
+/// A git remote configured on a cloned repository.
+#[derive(Debug, Clone)]
+pub(crate) struct Remote {
+    name: String,
+    url: Url,
+    push_url: Option<String>,
+}
+
+impl Remote {
+    /// Creates a remote fetching from the URL of `query`.
+    ///
+    /// The push URL is expanded from `push_url_template` if given, and otherwise
+    /// taken from the `pushInsteadOf` rewrite of the query.
+    pub(crate) fn from_query(
+        name: impl Into<String>,
+        query: &Query,
+        push_url_template: Option<&Template<RepoUrlTemplateContext>>,
+    ) -> Self {
+        let push_url = match push_url_template {
+            Some(template) => {
+                Some(template.expand(&RepoUrlTemplateContext::new(query.url(), false)))
+            }
+            None => query.push_url().map(str::to_owned),
+        };
+        Self {
+            name: name.into(),
+            url: query.url().clone(),
+            push_url,
+        }
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub(crate) fn push_url(&self) -> Option<&str> {
+        self.push_url.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::domain::model::query::ParseOption;
+
+    #[test]
+    fn push_url_from_template() {
+        let query = Query::parse(
+            "https://github.com/gifnksm/souko.git",
+            &ParseOption::default(),
+        )
+        .unwrap();
+
+        let remote = Remote::from_query(ORIGIN_REMOTE_NAME, &query, None);
+        assert_eq!(remote.name(), "origin");
+        assert_eq!(
+            remote.url().as_str(),
+            "https://github.com/gifnksm/souko.git"
+        );
+        assert_eq!(remote.push_url(), None);
+
+        let template = Template::from_str("ssh://git@{host}/{path}.git").unwrap();
+        let remote = Remote::from_query("upstream", &query, Some(&template));
+        assert_eq!(
+            remote.push_url(),
+            Some("ssh://git@github.com/gifnksm/souko.git")
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use serde::Serialize;
+use url::Url;
+
+use super::template::TemplateContext;
+
+/// Template variables derived from a repository URL.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct RepoUrlTemplateContext {
+    scheme: String,
+    user: String,
+    host: String,
+    port: String,
+    path: String,
+    owner: String,
+    repo: String,
+}
+
+impl TemplateContext for RepoUrlTemplateContext {}
+
+impl RepoUrlTemplateContext {
+    pub(crate) fn new(url: &Url, bare: bool) -> Self {
+        let path = url_path_segments(url, bare);
+        let (owner, repo) = match path.split_last() {
+            Some((repo, owner)) => (owner.join("/"), repo.clone()),
+            None => (String::new(), String::new()),
+        };
+        Self {
+            scheme: url.scheme().to_owned(),
+            user: url.username().to_owned(),
+            host: url.host_str().unwrap_or_default().to_owned(),
+            port: url.port().map(|port| port.to_string()).unwrap_or_default(),
+            path: path.join("/"),
+            owner,
+            repo,
+        }
+    }
+}
+
+/// Returns the non-empty segments of the URL path.
+///
+/// The trailing `.git` is removed unless the repository is bare.
+pub(crate) fn url_path_segments(url: &Url, bare: bool) -> Vec<String> {
+    let mut base_url = url.clone();
+    base_url.set_path("");
+    let Some(relative) = base_url.make_relative(url) else {
+        return vec![];
+    };
+    let mut relative = relative.as_str();
+    if !bare {
+        relative = relative.trim_end_matches(".git");
+    }
+    relative
+        .split('/')
+        .filter(|part| !part.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
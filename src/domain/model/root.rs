@@ -1,16 +1,25 @@
 use std::path::{Path, PathBuf};
 
-use super::path_buf_pair::PathBufPair;
+use super::{layout::RepoLayout, path_buf_pair::PathBufPair};
 
 #[derive(Debug, Clone)]
 pub(crate) struct Root {
     name: String,
     path: PathBufPair,
+    layout: RepoLayout,
 }
 
 impl Root {
     pub(crate) fn new(name: String, path: PathBufPair) -> Self {
-        Self { name, path }
+        Self {
+            name,
+            path,
+            layout: RepoLayout::default(),
+        }
+    }
+
+    pub(crate) fn with_layout(self, layout: RepoLayout) -> Self {
+        Self { layout, ..self }
     }
 
     pub(crate) fn name(&self) -> &str {
@@ -20,6 +29,10 @@ impl Root {
     pub(crate) fn path(&self) -> &PathBufPair {
         &self.path
     }
+
+    pub(crate) fn layout(&self) -> &RepoLayout {
+        &self.layout
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -3,6 +3,7 @@ use std::{
     collections::HashMap,
     fmt::{self, Display, Write},
     marker::PhantomData,
+    path::Path,
     str::FromStr,
 };
 
@@ -10,6 +11,14 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use thiserror::Error;
 
+/// A string with `{var}` placeholders expanded from a [`TemplateContext`].
+///
+/// Besides plain substitution, a template supports:
+///
+/// - filters applied from left to right: `{var | upper | trunc(40)}`
+/// - default values for empty variables: `{var | default("none")}`
+/// - conditionals that are only expanded when a variable is non-empty: `{?var}...{/var}`
+/// - `{{` and `}}` for literal braces
 #[derive(Deserialize)]
 #[serde(try_from = "String")]
 #[serde(bound = "C: TemplateContext")]
@@ -43,18 +52,71 @@ where
     pub(crate) fn expand(&self, context: &C) -> String {
         let mut result = String::new();
         let variables = context.to_hashmap();
-        for part in &self.parts {
-            match part {
-                Parts::Variable(name) => {
-                    if let Some(value) = variables.get(name) {
-                        write!(&mut result, "{value}").unwrap();
+        expand_parts(&mut result, &self.parts, &variables);
+        result
+    }
+
+    /// Returns `true` if the template refers to the variable `name`.
+    pub(crate) fn uses_variable(&self, name: &str) -> bool {
+        parts_use_variable(&self.parts, name)
+    }
+}
+
+fn expand_parts(result: &mut String, parts: &[Parts], variables: &HashMap<String, String>) {
+    for part in parts {
+        match part {
+            Parts::Text(text) => result.push_str(text),
+            Parts::Variable(expr) => {
+                let value = variables.get(&expr.variable).map_or("", String::as_str);
+                result.push_str(&expr.apply(value));
+            }
+            Parts::Conditional { variable, body } => {
+                if variables
+                    .get(variable)
+                    .is_some_and(|value| !value.is_empty())
+                {
+                    expand_parts(result, body, variables);
+                }
+            }
+        }
+    }
+}
+
+fn parts_use_variable(parts: &[Parts], name: &str) -> bool {
+    parts.iter().any(|part| match part {
+        Parts::Text(_) => false,
+        Parts::Variable(expr) => expr.variable == name,
+        Parts::Conditional { variable, body } => variable == name || parts_use_variable(body, name),
+    })
+}
+
+impl<C> Display for Template<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_parts(f, &self.parts)
+    }
+}
+
+fn fmt_parts(f: &mut fmt::Formatter<'_>, parts: &[Parts]) -> fmt::Result {
+    for part in parts {
+        match part {
+            Parts::Text(text) => {
+                for ch in text.chars() {
+                    match ch {
+                        '{' => f.write_str("{{")?,
+                        '}' => f.write_str("}}")?,
+                        ch => f.write_char(ch)?,
                     }
                 }
-                Parts::Text(text) => result.push_str(text),
+            }
+            Parts::Variable(expr) => write!(f, "{{{expr}}}")?,
+            Parts::Conditional { variable, body } => {
+                write!(f, "{{?{variable}}}")?;
+                fmt_parts(f, body)?;
+                write!(f, "{{/{variable}}}")?;
             }
         }
-        result
     }
+    Ok(())
 }
 
 pub(crate) trait TemplateContext: Serialize + Default + fmt::Debug {
@@ -83,16 +145,212 @@ pub(crate) trait TemplateContext: Serialize + Default + fmt::Debug {
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum Parts {
     Text(String),
-    Variable(String),
+    Variable(Expr),
+    Conditional { variable: String, body: Vec<Parts> },
 }
 
 impl Parts {
     fn text(s: impl Display) -> Self {
         Self::Text(s.to_string())
     }
+    #[cfg(test)]
     fn variable(s: impl Display) -> Self {
-        Self::Variable(s.to_string())
+        Self::Variable(Expr {
+            variable: s.to_string(),
+            filters: vec![],
+        })
+    }
+}
+
+/// A variable reference with filters, such as `{var | upper}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Expr {
+    variable: String,
+    filters: Vec<Filter>,
+}
+
+impl Expr {
+    fn apply(&self, value: &str) -> String {
+        self.filters
+            .iter()
+            .fold(value.to_owned(), |value, filter| filter.apply(value))
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.variable)?;
+        for filter in &self.filters {
+            write!(f, " | {filter}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Filter {
+    Upper,
+    Lower,
+    Basename,
+    Dirname,
+    ShellQuote,
+    Json,
+    Trunc(usize),
+    Pad(usize),
+    Default(String),
+}
+
+impl Filter {
+    fn parse(s: &str) -> Result<Self, TemplateParseError> {
+        let (name, arg) = match s.split_once('(') {
+            Some((name, rest)) => {
+                let Some(arg) = rest.strip_suffix(')') else {
+                    return Err(TemplateParseError::InvalidFilterArgument(s.to_owned()));
+                };
+                (name.trim(), Some(arg.trim()))
+            }
+            None => (s, None),
+        };
+        let invalid_argument = || TemplateParseError::InvalidFilterArgument(s.to_owned());
+        let width = || {
+            arg.and_then(|arg| arg.parse().ok())
+                .ok_or_else(invalid_argument)
+        };
+        let filter = match name {
+            "upper" => Self::Upper,
+            "lower" => Self::Lower,
+            "basename" => Self::Basename,
+            "dirname" => Self::Dirname,
+            "shell_quote" => Self::ShellQuote,
+            "json" => Self::Json,
+            "trunc" => return Ok(Self::Trunc(width()?)),
+            "pad" => return Ok(Self::Pad(width()?)),
+            "default" => {
+                return arg
+                    .and_then(parse_string_literal)
+                    .map(Self::Default)
+                    .ok_or_else(invalid_argument);
+            }
+            _ => return Err(TemplateParseError::UnknownFilter(name.to_owned())),
+        };
+        if arg.is_some() {
+            return Err(invalid_argument());
+        }
+        Ok(filter)
+    }
+
+    fn apply(&self, value: String) -> String {
+        match self {
+            Self::Upper => value.to_uppercase(),
+            Self::Lower => value.to_lowercase(),
+            Self::Basename => Path::new(&value)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            Self::Dirname => Path::new(&value)
+                .parent()
+                .map(|parent| parent.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            Self::ShellQuote => shell_quote(&value),
+            Self::Json => serde_json::to_string(&value).unwrap(),
+            Self::Trunc(width) => value.chars().take(*width).collect(),
+            Self::Pad(width) => format!("{value:<width$}"),
+            Self::Default(default) if value.is_empty() => default.clone(),
+            Self::Default(_) => value,
+        }
+    }
+}
+
+impl Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Upper => write!(f, "upper"),
+            Self::Lower => write!(f, "lower"),
+            Self::Basename => write!(f, "basename"),
+            Self::Dirname => write!(f, "dirname"),
+            Self::ShellQuote => write!(f, "shell_quote"),
+            Self::Json => write!(f, "json"),
+            Self::Trunc(width) => write!(f, "trunc({width})"),
+            Self::Pad(width) => write!(f, "pad({width})"),
+            Self::Default(default) => {
+                f.write_str("default(\"")?;
+                for ch in default.chars() {
+                    if matches!(ch, '"' | '\\') {
+                        f.write_char('\\')?;
+                    }
+                    f.write_char(ch)?;
+                }
+                f.write_str("\")")
+            }
+        }
+    }
+}
+
+/// Quotes `s` for POSIX shells, leaving it as is if no quoting is needed.
+fn shell_quote(s: &str) -> String {
+    let is_safe = |ch: char| ch.is_ascii_alphanumeric() || "_-./:@%+=,".contains(ch);
+    if !s.is_empty() && s.chars().all(is_safe) {
+        return s.to_owned();
+    }
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Parses a single- or double-quoted string with `\` escapes.
+fn parse_string_literal(s: &str) -> Option<String> {
+    let quote = s.chars().next().filter(|ch| matches!(ch, '"' | '\''))?;
+    let inner = s[1..].strip_suffix(quote)?;
+    let mut result = String::new();
+    let mut chars = inner.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => result.push(chars.next()?),
+            ch if ch == quote => return None,
+            ch => result.push(ch),
+        }
+    }
+    Some(result)
+}
+
+/// Splits `s` at each `sep` that is not inside a quoted string.
+fn split_unquoted(s: &str, sep: char) -> Vec<&str> {
+    let mut result = vec![];
+    let mut start = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for (idx, ch) in s.char_indices() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if ch == '\\' => escaped = true,
+            Some(q) if ch == q => quote = None,
+            Some(_) => {}
+            None if matches!(ch, '"' | '\'') => quote = Some(ch),
+            None if ch == sep => {
+                result.push(&s[start..idx]);
+                start = idx + ch.len_utf8();
+            }
+            None => {}
+        }
     }
+    result.push(&s[start..]);
+    result
+}
+
+/// Returns the byte offset of the `}` closing the placeholder at the start of `s`.
+fn find_closing_brace(s: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut escaped = false;
+    for (idx, ch) in s.char_indices() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if ch == '\\' => escaped = true,
+            Some(q) if ch == q => quote = None,
+            Some(_) => {}
+            None if matches!(ch, '"' | '\'') => quote = Some(ch),
+            None if ch == '}' => return Some(idx),
+            None => {}
+        }
+    }
+    None
 }
 
 #[derive(Debug, Error)]
@@ -103,6 +361,14 @@ pub(crate) enum TemplateParseError {
     NoClosingBrace,
     #[error("unknown template variable: {0}")]
     UnknownVariable(String),
+    #[error("unknown template filter: {0}")]
+    UnknownFilter(String),
+    #[error("invalid template filter argument: {0}")]
+    InvalidFilterArgument(String),
+    #[error("conditional '{{?{0}}}' is not closed with '{{/{0}}}'")]
+    UnclosedConditional(String),
+    #[error("unexpected conditional end: '{{/{0}}}'")]
+    UnexpectedConditionalEnd(String),
 }
 
 impl<C> FromStr for Template<C>
@@ -113,38 +379,7 @@ where
 
     fn from_str(mut s: &str) -> Result<Self, Self::Err> {
         let valid_variables = C::default().to_hashmap();
-        let mut parts = vec![];
-
-        while let Some(idx) = s.find(['{', '}']) {
-            let (text, brace) = s.split_at(idx);
-            push_str(&mut parts, text);
-            s = brace;
-            if let Some(rest) = s.strip_prefix("{{") {
-                s = rest;
-                push_char(&mut parts, '{');
-                continue;
-            }
-            if let Some(rest) = s.strip_prefix("}}") {
-                s = rest;
-                push_char(&mut parts, '}');
-                continue;
-            }
-            if s.starts_with('}') {
-                return Err(TemplateParseError::UnexpectedChar('}'));
-            }
-            assert!(s.starts_with('{'));
-            if let Some(end) = s.find('}') {
-                let variable = s[1..end].trim();
-                if !valid_variables.contains_key(variable) {
-                    return Err(TemplateParseError::UnknownVariable(variable.to_string()));
-                }
-                s = &s[end + 1..];
-                parts.push(Parts::variable(variable));
-            } else {
-                return Err(TemplateParseError::NoClosingBrace);
-            }
-        }
-        push_str(&mut parts, s);
+        let parts = parse_parts(&mut s, &valid_variables, None)?;
         Ok(Self {
             parts,
             _context: PhantomData,
@@ -152,6 +387,86 @@ where
     }
 }
 
+/// Parses parts until the end of `s`, or until `{/closing}` if `closing` is given.
+fn parse_parts(
+    s: &mut &str,
+    valid_variables: &HashMap<String, String>,
+    closing: Option<&str>,
+) -> Result<Vec<Parts>, TemplateParseError> {
+    let mut parts = vec![];
+
+    while let Some(idx) = s.find(['{', '}']) {
+        let (text, brace) = s.split_at(idx);
+        push_str(&mut parts, text);
+        *s = brace;
+        if let Some(rest) = s.strip_prefix("{{") {
+            *s = rest;
+            push_char(&mut parts, '{');
+            continue;
+        }
+        if let Some(rest) = s.strip_prefix("}}") {
+            *s = rest;
+            push_char(&mut parts, '}');
+            continue;
+        }
+        if s.starts_with('}') {
+            return Err(TemplateParseError::UnexpectedChar('}'));
+        }
+        assert!(s.starts_with('{'));
+        let Some(end) = find_closing_brace(s) else {
+            return Err(TemplateParseError::NoClosingBrace);
+        };
+        let inner = s[1..end].trim();
+        *s = &s[end + 1..];
+
+        if let Some(variable) = inner.strip_prefix('?') {
+            let variable = validate_variable(variable.trim(), valid_variables)?;
+            let body = parse_parts(s, valid_variables, Some(variable))?;
+            parts.push(Parts::Conditional {
+                variable: variable.to_owned(),
+                body,
+            });
+            continue;
+        }
+        if let Some(variable) = inner.strip_prefix('/') {
+            let variable = variable.trim();
+            if closing != Some(variable) {
+                return Err(TemplateParseError::UnexpectedConditionalEnd(
+                    variable.to_owned(),
+                ));
+            }
+            return Ok(parts);
+        }
+
+        let mut segments = split_unquoted(inner, '|').into_iter();
+        let variable = validate_variable(segments.next().unwrap().trim(), valid_variables)?;
+        let filters = segments
+            .map(|filter| Filter::parse(filter.trim()))
+            .collect::<Result<_, _>>()?;
+        parts.push(Parts::Variable(Expr {
+            variable: variable.to_owned(),
+            filters,
+        }));
+    }
+    push_str(&mut parts, s);
+    *s = "";
+
+    if let Some(closing) = closing {
+        return Err(TemplateParseError::UnclosedConditional(closing.to_owned()));
+    }
+    Ok(parts)
+}
+
+fn validate_variable<'a>(
+    variable: &'a str,
+    valid_variables: &HashMap<String, String>,
+) -> Result<&'a str, TemplateParseError> {
+    if !valid_variables.contains_key(variable) {
+        return Err(TemplateParseError::UnknownVariable(variable.to_string()));
+    }
+    Ok(variable)
+}
+
 fn push_char(parts: &mut Vec<Parts>, ch: char) {
     if let Some(Parts::Text(last)) = parts.last_mut() {
         last.push(ch)
@@ -193,6 +508,15 @@ where
     }
 }
 
+impl<C> Serialize for Template<C> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -332,4 +656,113 @@ mod tests {
             "sushi is my favorite food. I eat sushi everyday"
         );
     }
+
+    #[test]
+    fn test_display_round_trip() {
+        for input in ["foo", "", "{var}", "{{{var}}} }}{var}{{", "a{var2}b"] {
+            let template = Template::<Context>::from_str(input).unwrap();
+            assert_eq!(template.to_string(), input);
+        }
+        let template = Template::<Context>::from_str("{ var }").unwrap();
+        assert_eq!(template.to_string(), "{var}");
+    }
+
+    fn expand(template: &str, var: &str, var2: &str) -> String {
+        Template::<Context>::from_str(template)
+            .unwrap()
+            .expand(&Context {
+                var: var.to_owned(),
+                var2: var2.to_owned(),
+            })
+    }
+
+    #[test]
+    fn test_expand_filters() {
+        assert_eq!(expand("{var | upper}", "Foo/Bar", ""), "FOO/BAR");
+        assert_eq!(expand("{var|lower}", "Foo/Bar", ""), "foo/bar");
+        assert_eq!(expand("{var | basename}", "foo/bar/baz", ""), "baz");
+        assert_eq!(expand("{var | dirname}", "foo/bar/baz", ""), "foo/bar");
+        assert_eq!(expand("{var | dirname}", "foo", ""), "");
+        assert_eq!(expand("{var | shell_quote}", "foo/bar", ""), "foo/bar");
+        assert_eq!(expand("{var | shell_quote}", "it's", ""), r"'it'\''s'");
+        assert_eq!(expand("{var | shell_quote}", "", ""), "''");
+        assert_eq!(expand("{var | json}", "a\"b", ""), r#""a\"b""#);
+        assert_eq!(expand("{var | trunc(3)}", "日本語テキスト", ""), "日本語");
+        assert_eq!(expand("[{var | pad(5)}]", "ab", ""), "[ab   ]");
+        assert_eq!(expand("{var | trunc(3) | upper}", "abcdef", ""), "ABC");
+        assert_eq!(expand(r#"{var | default("a|b}")}"#, "", ""), "a|b}");
+        assert_eq!(expand("{var | default('none')}", "x", ""), "x");
+    }
+
+    #[test]
+    fn test_expand_conditional() {
+        let template = "{var}{?var2}@{var2}{/var2}";
+        assert_eq!(expand(template, "repo", "main"), "repo@main");
+        assert_eq!(expand(template, "repo", ""), "repo");
+        assert_eq!(
+            expand("{?var}[{?var2}{var2}{/var2}]{/var}", "x", "y"),
+            "[y]"
+        );
+        assert_eq!(expand("{?var}[{?var2}{var2}{/var2}]{/var}", "", "y"), "");
+    }
+
+    #[test]
+    fn test_from_str_err_filters_and_conditionals() {
+        assert!(matches!(
+            Template::<Context>::from_str("{var | reverse}").unwrap_err(),
+            TemplateParseError::UnknownFilter(s) if s == "reverse"
+        ));
+        for input in [
+            "{var | trunc}",
+            "{var | trunc(x)}",
+            "{var | upper(1)}",
+            "{var | default(none)}",
+            "{var | pad(3}",
+        ] {
+            assert!(
+                matches!(
+                    Template::<Context>::from_str(input).unwrap_err(),
+                    TemplateParseError::InvalidFilterArgument(_)
+                ),
+                "{input}"
+            );
+        }
+        assert!(matches!(
+            Template::<Context>::from_str("{?var}foo").unwrap_err(),
+            TemplateParseError::UnclosedConditional(s) if s == "var"
+        ));
+        assert!(matches!(
+            Template::<Context>::from_str("{?var}foo{/var2}").unwrap_err(),
+            TemplateParseError::UnexpectedConditionalEnd(s) if s == "var2"
+        ));
+        assert!(matches!(
+            Template::<Context>::from_str("{?dessert}{/dessert}").unwrap_err(),
+            TemplateParseError::UnknownVariable(s) if s == "dessert"
+        ));
+        assert!(matches!(
+            Template::<Context>::from_str(r#"{var | default("x}"#).unwrap_err(),
+            TemplateParseError::NoClosingBrace
+        ));
+    }
+
+    #[test]
+    fn test_uses_variable() {
+        let template = Template::<Context>::from_str("{?var2}{var | upper}{/var2}").unwrap();
+        assert!(template.uses_variable("var"));
+        assert!(template.uses_variable("var2"));
+        let template = Template::<Context>::from_str("{var}").unwrap();
+        assert!(!template.uses_variable("var2"));
+    }
+
+    #[test]
+    fn test_display_round_trip_filters_and_conditionals() {
+        for input in [
+            "{var | upper | trunc(40)}",
+            r#"{var | default("a \"b\" \\ }")}"#,
+            "{?var}{var2 | pad(3)}{/var}",
+        ] {
+            let template = Template::<Context>::from_str(input).unwrap();
+            assert_eq!(template.to_string(), input);
+        }
+    }
 }
@@ -0,0 +1,107 @@
+/// A rule rewriting URLs that start with any of the given prefixes to `base`.
+///
+/// This corresponds to git's `url.<base>.insteadOf` and `url.<base>.pushInsteadOf`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct UrlRewriteRule {
+    pub(crate) base: String,
+    pub(crate) instead_of: Vec<String>,
+    pub(crate) push_instead_of: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct UrlRewrites {
+    rules: Vec<UrlRewriteRule>,
+}
+
+impl UrlRewrites {
+    pub(crate) fn new(rules: Vec<UrlRewriteRule>) -> Self {
+        Self { rules }
+    }
+
+    pub(crate) fn rule(&self, index: usize) -> &UrlRewriteRule {
+        &self.rules[index]
+    }
+
+    /// Rewrites `url` with the rule that has the longest matching `insteadOf` prefix.
+    ///
+    /// Returns the index of the applied rule and the rewritten URL.
+    /// When several rules match with the same length, the first one wins.
+    pub(crate) fn rewrite(&self, url: &str) -> Option<(usize, String)> {
+        self.rewrite_by(url, |rule| &rule.instead_of)
+    }
+
+    /// Rewrites `url` with the rule that has the longest matching `pushInsteadOf` prefix.
+    pub(crate) fn rewrite_push(&self, url: &str) -> Option<String> {
+        self.rewrite_by(url, |rule| &rule.push_instead_of)
+            .map(|(_, url)| url)
+    }
+
+    fn rewrite_by(
+        &self,
+        url: &str,
+        prefixes: impl Fn(&UrlRewriteRule) -> &Vec<String>,
+    ) -> Option<(usize, String)> {
+        let mut matched: Option<(usize, &str)> = None;
+        for (index, rule) in self.rules.iter().enumerate() {
+            for prefix in prefixes(rule) {
+                if url.starts_with(prefix.as_str())
+                    && matched.is_none_or(|(_, matched)| prefix.len() > matched.len())
+                {
+                    matched = Some((index, prefix));
+                }
+            }
+        }
+        matched.map(|(index, prefix)| {
+            let rewritten = format!("{}{}", self.rules[index].base, &url[prefix.len()..]);
+            (index, rewritten)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(base: &str, instead_of: &[&str], push_instead_of: &[&str]) -> UrlRewriteRule {
+        UrlRewriteRule {
+            base: base.to_owned(),
+            instead_of: instead_of.iter().map(|s| (*s).to_owned()).collect(),
+            push_instead_of: push_instead_of.iter().map(|s| (*s).to_owned()).collect(),
+        }
+    }
+
+    #[test]
+    fn longest_prefix_wins() {
+        let rewrites = UrlRewrites::new(vec![
+            rule("ssh://git@github.com/", &["https://github.com/"], &[]),
+            rule(
+                "ssh://git@github.com/corp/",
+                &["https://github.com/corp/"],
+                &[],
+            ),
+            rule("https://mirror.example/", &["https://github.com/"], &[]),
+        ]);
+
+        assert_eq!(
+            rewrites.rewrite("https://github.com/corp/app.git"),
+            Some((1, "ssh://git@github.com/corp/app.git".to_owned()))
+        );
+        assert_eq!(
+            rewrites.rewrite("https://github.com/gifnksm/souko.git"),
+            Some((0, "ssh://git@github.com/gifnksm/souko.git".to_owned()))
+        );
+        assert_eq!(rewrites.rewrite("https://gitlab.com/a/b.git"), None);
+    }
+
+    #[test]
+    fn push_rewrite_uses_push_instead_of() {
+        let rewrites =
+            UrlRewrites::new(vec![rule("git@github.com:", &[], &["https://github.com/"])]);
+
+        assert_eq!(rewrites.rewrite("https://github.com/a/b.git"), None);
+        assert_eq!(
+            rewrites.rewrite_push("https://github.com/a/b.git"),
+            Some("git@github.com:a/b.git".to_owned())
+        );
+    }
+}
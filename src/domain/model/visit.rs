@@ -0,0 +1,169 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// Repositories not visited for this long are removed from the visit history.
+pub(crate) const VISIT_RETENTION: TimeDelta = TimeDelta::days(90);
+
+/// Visits recorded for a repository in souko.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RepoVisits {
+    canonical_path: PathBuf,
+    count: u64,
+    last_visited: DateTime<Utc>,
+}
+
+impl RepoVisits {
+    pub(crate) fn new(canonical_path: PathBuf, count: u64, last_visited: DateTime<Utc>) -> Self {
+        Self {
+            canonical_path,
+            count,
+            last_visited,
+        }
+    }
+
+    pub(crate) fn canonical_path(&self) -> &Path {
+        &self.canonical_path
+    }
+
+    /// Returns the frecency score of the repository at `now`.
+    ///
+    /// The visit count is weighted by how recently the repository was visited,
+    /// in the same way as zoxide.
+    pub(crate) fn frecency(&self, now: DateTime<Utc>) -> f64 {
+        let elapsed = now - self.last_visited;
+        let weight = if elapsed < TimeDelta::hours(1) {
+            4.0
+        } else if elapsed < TimeDelta::days(1) {
+            2.0
+        } else if elapsed < TimeDelta::weeks(1) {
+            0.5
+        } else {
+            0.25
+        };
+        self.count as f64 * weight
+    }
+}
+
+/// Frecency scores of repositories, keyed by their canonical paths.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FrecencyScores {
+    scores: HashMap<PathBuf, f64>,
+}
+
+impl FrecencyScores {
+    pub(crate) fn new(visits: &[RepoVisits], now: DateTime<Utc>) -> Self {
+        let scores = visits
+            .iter()
+            .map(|visits| (visits.canonical_path().to_owned(), visits.frecency(now)))
+            .collect();
+        Self { scores }
+    }
+
+    /// Returns the score of the repository, or `0.0` if it has never been visited.
+    pub(crate) fn get(&self, canonical_path: &Path) -> f64 {
+        self.scores.get(canonical_path).copied().unwrap_or(0.0)
+    }
+}
+
+/// Keywords selecting a repository by `souko jump`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct JumpKeywords {
+    keywords: Vec<String>,
+}
+
+impl JumpKeywords {
+    pub(crate) fn new<I, S>(keywords: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let keywords = keywords
+            .into_iter()
+            .map(|keyword| keyword.as_ref().to_lowercase())
+            .filter(|keyword| !keyword.is_empty())
+            .collect();
+        Self { keywords }
+    }
+
+    /// Returns `true` if the keywords match the `/`-separated repository path.
+    ///
+    /// The keywords must appear in the path in order, ignoring case, and the
+    /// last keyword must appear in the last component of the path.
+    pub(crate) fn matches(&self, path: &str) -> bool {
+        let Some((last, rest)) = self.keywords.split_last() else {
+            return true;
+        };
+        let path = path.to_lowercase();
+        let mut remaining = path.as_str();
+        for keyword in rest {
+            let Some(index) = remaining.find(keyword.as_str()) else {
+                return false;
+            };
+            remaining = &remaining[index + keyword.len()..];
+        }
+        let last_component = remaining.rsplit('/').next().unwrap_or(remaining);
+        last_component.contains(last.as_str())
+    }
+}
+
+impl fmt::Display for JumpKeywords {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.keywords.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn visits(count: u64, last_visited: DateTime<Utc>) -> RepoVisits {
+        RepoVisits::new(PathBuf::from("/repo"), count, last_visited)
+    }
+
+    #[test]
+    fn frecency_weights_count_by_recency() {
+        let now = Utc::now();
+        assert_eq!(visits(3, now).frecency(now), 12.0);
+        assert_eq!(visits(3, now - TimeDelta::hours(2)).frecency(now), 6.0);
+        assert_eq!(visits(4, now - TimeDelta::days(2)).frecency(now), 2.0);
+        assert_eq!(visits(4, now - TimeDelta::weeks(2)).frecency(now), 1.0);
+    }
+
+    #[test]
+    fn frecency_scores_default_to_zero() {
+        let now = Utc::now();
+        let scores = FrecencyScores::new(&[visits(1, now)], now);
+        assert_eq!(scores.get(Path::new("/repo")), 4.0);
+        assert_eq!(scores.get(Path::new("/other")), 0.0);
+    }
+
+    #[test]
+    fn keywords_match_in_order_ignoring_case() {
+        let path = "github.com/gifnksm/Souko";
+        assert!(JumpKeywords::new(["souko"]).matches(path));
+        assert!(JumpKeywords::new(["gif", "sou"]).matches(path));
+        assert!(JumpKeywords::new(["GITHUB", "souko"]).matches(path));
+        assert!(!JumpKeywords::new(["souko", "gif"]).matches(path));
+        assert!(!JumpKeywords::new(["nomatch"]).matches(path));
+    }
+
+    #[test]
+    fn last_keyword_must_match_last_component() {
+        let path = "github.com/gifnksm/souko";
+        assert!(!JumpKeywords::new(["gifnksm"]).matches(path));
+        assert!(JumpKeywords::new(["gifnksm", "o"]).matches(path));
+        // Each keyword consumes the matched part of the path.
+        assert!(!JumpKeywords::new(["souko", "souko"]).matches(path));
+    }
+
+    #[test]
+    fn empty_keywords_match_everything() {
+        assert!(JumpKeywords::new(Vec::<String>::new()).matches("github.com/gifnksm/souko"));
+        assert!(JumpKeywords::new([""]).matches("github.com/gifnksm/souko"));
+    }
+}
@@ -0,0 +1,192 @@
+use std::path::{Component, Path, PathBuf};
+
+use serde::Serialize;
+use thiserror::Error;
+
+use super::{
+    path_buf_pair::PathBufPair,
+    repo::Repo,
+    root::Root,
+    template::{Template, TemplateContext},
+};
+
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct WorktreePathTemplateContext {
+    repo_relative_path: String,
+    repo_name: String,
+    branch: String,
+    branch_slug: String,
+}
+
+impl TemplateContext for WorktreePathTemplateContext {}
+
+impl WorktreePathTemplateContext {
+    fn new(repo: &Repo, branch: &str, name: &str) -> Self {
+        let relative_path = repo.relative_path();
+        Self {
+            repo_relative_path: relative_path
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+            repo_name: relative_path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            branch: branch.to_owned(),
+            branch_slug: name.to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub(crate) enum WorktreePathError {
+    #[error("worktree path `{path}` must be a relative path inside the root")]
+    InvalidPath { path: String },
+}
+
+/// A linked worktree of a repository placed under a root.
+#[derive(Debug, Clone)]
+pub(crate) struct Worktree {
+    name: String,
+    path: PathBufPair,
+}
+
+impl Worktree {
+    /// Computes the location of the worktree named `name` for `branch` of
+    /// `repo` from the path template.
+    ///
+    /// The name is also used as `{branch_slug}` in the template.
+    pub(crate) fn from_template(
+        root: &Root,
+        repo: &Repo,
+        branch: &str,
+        name: &str,
+        template: &Template<WorktreePathTemplateContext>,
+    ) -> Result<Self, WorktreePathError> {
+        let context = WorktreePathTemplateContext::new(repo, branch, name);
+        let expanded = template.expand(&context);
+
+        let mut relative_path = PathBuf::new();
+        for part in expanded.split('/').filter(|part| !part.is_empty()) {
+            relative_path.push(part);
+        }
+        let is_valid = !relative_path.as_os_str().is_empty()
+            && Path::new(&expanded).is_relative()
+            && relative_path
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)));
+        if !is_valid {
+            return Err(WorktreePathError::InvalidPath { path: expanded });
+        }
+
+        let path = root.path().join(&relative_path);
+        Ok(Self {
+            name: name.to_owned(),
+            path,
+        })
+    }
+
+    /// Returns the worktree name used by git for `branch`.
+    ///
+    /// Git stores worktree metadata in a directory named after the worktree,
+    /// so path separators in the branch name are replaced.
+    pub(crate) fn name_for_branch(branch: &str) -> String {
+        branch.replace(['/', '\\'], "-")
+    }
+
+    /// Returns the name for `branch` that is not used by the `existing` worktrees.
+    ///
+    /// Different branches such as `feature/foo` and `feature-foo` have the same
+    /// name, so a numeric suffix is added to the name taken later.
+    pub(crate) fn unique_name_for_branch<'a>(
+        branch: &str,
+        existing: impl IntoIterator<Item = &'a str> + Clone,
+    ) -> String {
+        let name = Self::name_for_branch(branch);
+        let is_used = |name: &str| existing.clone().into_iter().any(|used| used == name);
+        if !is_used(&name) {
+            return name;
+        }
+        (2..)
+            .map(|suffix| format!("{name}-{suffix}"))
+            .find(|name| !is_used(name))
+            .unwrap()
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn path(&self) -> &PathBufPair {
+        &self.path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::domain::model::{path_like::PathLike as _, repo::RepoKind};
+
+    #[test]
+    fn from_template() {
+        let root = Root::new(
+            "test".into(),
+            PathBufPair::from_pair("/home/user/root".into(), "~/root".into()),
+        );
+        let repo = Repo::from_relative_path(
+            &root,
+            PathBuf::from("github.com/owner/repo"),
+            RepoKind::Normal,
+        );
+
+        let pairs = [
+            (
+                "{repo_relative_path}@{branch_slug}",
+                "feature/foo",
+                "github.com/owner/repo@feature-foo",
+            ),
+            (
+                "worktrees/{repo_name}/{branch}",
+                "feature/foo",
+                "worktrees/repo/feature/foo",
+            ),
+        ];
+        for (template, branch, expected) in pairs {
+            let template = Template::from_str(template).unwrap();
+            let name = Worktree::name_for_branch(branch);
+            let worktree = Worktree::from_template(&root, &repo, branch, &name, &template).unwrap();
+            assert_eq!(
+                worktree.path().as_real_path(),
+                Path::new("/home/user/root").join(expected)
+            );
+            assert_eq!(worktree.name(), "feature-foo");
+        }
+
+        for template in ["/{branch}", "../{branch}", "{repo_relative_path}/../x"] {
+            let template = Template::from_str(template).unwrap();
+            assert!(matches!(
+                Worktree::from_template(&root, &repo, "main", "main", &template).unwrap_err(),
+                WorktreePathError::InvalidPath { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn unique_name_for_branch_avoids_names_in_use() {
+        assert_eq!(
+            Worktree::unique_name_for_branch("feature/foo", []),
+            "feature-foo"
+        );
+        assert_eq!(
+            Worktree::unique_name_for_branch("feature-foo", ["feature-foo"]),
+            "feature-foo-2"
+        );
+        assert_eq!(
+            Worktree::unique_name_for_branch("feature/foo", ["feature-foo", "feature-foo-2"]),
+            "feature-foo-3"
+        );
+    }
+}
@@ -1,13 +1,16 @@
 use std::fmt::Debug;
 
-use url::Url;
-
-use crate::domain::model::path_like::PathLike;
+use crate::domain::model::{path_like::PathLike, remote::Remote};
 
 pub(crate) trait RepoClone: Debug {
+    /// Clones the repository from `origin` into `path`.
+    ///
+    /// The push URL of `origin` and `extra_remotes` are configured after cloning.
+    /// Extra remotes are not fetched.
     fn clone_repo(
         &self,
-        url: &Url,
+        origin: &Remote,
+        extra_remotes: &[Remote],
         path: &dyn PathLike,
         bare: bool,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>>;
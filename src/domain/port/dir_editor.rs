@@ -63,6 +63,22 @@ pub(crate) trait DirEditor: Debug {
         &self,
         path: &Path,
     ) -> Result<bool, Box<dyn std::error::Error + Send + Sync + 'static>>;
+
+    /// Moves the directory at `from` to `to`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error in the following situations, but is not
+    /// limited to just these cases:
+    ///
+    /// * A file or directory already exists at `to`.
+    /// * The parent of `to` does not exist.
+    /// * `from` and `to` are on different file systems.
+    fn move_dir(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>>;
 }
 
 #[cfg(test)]
@@ -3,9 +3,15 @@ use std::{fmt::Debug, path::Path};
 use crate::domain::model::{path_buf_pair::PathBufPair, root::CanonicalRoot};
 
 pub(crate) trait DirWalker: Debug {
+    /// Returns an iterator over the directories under the given root.
+    ///
+    /// If `follow_symlinks` is `true`, symbolic links to directories are
+    /// traversed. Symbolic links that point to one of their ancestors are
+    /// reported as errors instead of being traversed endlessly.
     fn entries(
         &self,
         root: &CanonicalRoot,
+        follow_symlinks: bool,
     ) -> Result<Box<dyn DirEntries>, Box<dyn std::error::Error>>;
 }
 
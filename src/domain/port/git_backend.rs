@@ -0,0 +1,37 @@
+use std::fmt::Debug;
+
+/// Features of the git implementation souko is built with.
+#[derive(Debug, Clone)]
+pub(crate) struct GitBackendFeatures {
+    pub(crate) version: String,
+    pub(crate) https: bool,
+    pub(crate) ssh: bool,
+    /// `true` if the git library is statically linked instead of using the system library.
+    pub(crate) vendored: bool,
+}
+
+/// Whether an ssh-agent is available for authentication over ssh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SshAgentStatus {
+    Available,
+    /// `SSH_AUTH_SOCK` is not set.
+    NotConfigured,
+    /// `SSH_AUTH_SOCK` points to a socket that does not exist.
+    MissingSocket,
+    /// The agent cannot be detected on this platform.
+    Unknown,
+}
+
+pub(crate) trait GitBackend: Debug {
+    fn features(&self) -> GitBackendFeatures;
+
+    fn ssh_agent(&self) -> SshAgentStatus;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Assert object safety for trait object.
+    const _: Option<&dyn GitBackend> = None;
+}
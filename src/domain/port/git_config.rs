@@ -0,0 +1,19 @@
+use std::fmt::Debug;
+
+use crate::domain::model::url_rewrite::UrlRewriteRule;
+
+pub(crate) trait GitConfig: Debug {
+    /// Returns URL rewrite rules defined by `url.<base>.insteadOf` and
+    /// `url.<base>.pushInsteadOf` in the user's git configuration.
+    fn url_rewrites(
+        &self,
+    ) -> Result<Vec<UrlRewriteRule>, Box<dyn std::error::Error + Send + Sync + 'static>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Assert object safety for trait object.
+    const _: Option<&dyn GitConfig> = None;
+}
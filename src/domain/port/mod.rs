@@ -3,16 +3,25 @@ use std::sync::Arc;
 use crate::domain::port::repo_probe::RepoProbe;
 
 use self::{
-    clone_repo::RepoClone, dir_editor::DirEditor, dir_walker::DirWalker,
-    path_canonicalizer::PathCanonicalizer, repo_cache::RepoCache,
+    clone_repo::RepoClone, dir_editor::DirEditor, dir_walker::DirWalker, git_backend::GitBackend,
+    git_config::GitConfig, path_canonicalizer::PathCanonicalizer, repo_cache::RepoCache,
+    repo_files::RepoFiles, repo_remote::RepoRemote, repo_worktree::RepoWorktree,
+    ssh_config::SshConfig, visit_store::VisitStore,
 };
 
 pub(crate) mod clone_repo;
 pub(crate) mod dir_editor;
 pub(crate) mod dir_walker;
+pub(crate) mod git_backend;
+pub(crate) mod git_config;
 pub(crate) mod path_canonicalizer;
 pub(crate) mod repo_cache;
+pub(crate) mod repo_files;
 pub(crate) mod repo_probe;
+pub(crate) mod repo_remote;
+pub(crate) mod repo_worktree;
+pub(crate) mod ssh_config;
+pub(crate) mod visit_store;
 
 #[derive(Debug, Clone)]
 pub(crate) struct Ports {
@@ -21,5 +30,12 @@ pub(crate) struct Ports {
     pub(crate) dir_editor: Arc<dyn DirEditor>,
     pub(crate) repo_clone: Arc<dyn RepoClone>,
     pub(crate) repo_probe: Arc<dyn RepoProbe>,
+    pub(crate) repo_files: Arc<dyn RepoFiles>,
+    pub(crate) repo_remote: Arc<dyn RepoRemote>,
     pub(crate) repo_cache: Arc<dyn RepoCache>,
+    pub(crate) repo_worktree: Arc<dyn RepoWorktree>,
+    pub(crate) git_config: Arc<dyn GitConfig>,
+    pub(crate) git_backend: Arc<dyn GitBackend>,
+    pub(crate) ssh_config: Arc<dyn SshConfig>,
+    pub(crate) visit_store: Arc<dyn VisitStore>,
 }
@@ -24,6 +24,9 @@ pub(crate) trait RepoCache: Debug {
 
     /// Returns a handle to the cache entry for the given root/path pair.
     fn entry(&self, root: &CanonicalRoot, relative_path: &Path) -> Box<dyn RepoCacheEntry>;
+
+    /// Returns the cached repositories of the given root.
+    fn repos(&self, root: &CanonicalRoot) -> Vec<CanonicalRepo>;
 }
 
 pub(crate) trait RepoCacheEntry: Debug {
@@ -0,0 +1,40 @@
+use std::{fmt::Debug, path::Path};
+
+use crate::domain::model::path_like::PathLike;
+
+/// Which version of the files in a repository to read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FileSource {
+    /// Files committed at `HEAD`.
+    Head,
+    /// Tracked files in the working tree, and untracked files not ignored by
+    /// `.gitignore` if `untracked` is `true`.
+    ///
+    /// Bare repositories have no working tree, so files at `HEAD` are read instead.
+    WorkTree { untracked: bool },
+}
+
+/// Reads the files of a repository.
+///
+/// Unlike the other ports, implementations can be shared between threads so
+/// that several repositories can be read in parallel.
+pub(crate) trait RepoFiles: Debug + Send + Sync {
+    /// Calls `f` with the path relative to the repository and the content of each
+    /// regular file in `source`.
+    ///
+    /// Symbolic links and submodules are skipped.
+    fn for_each_file(
+        &self,
+        repo_path: &dyn PathLike,
+        source: FileSource,
+        f: &mut dyn FnMut(&Path, &[u8]),
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Assert object safety for trait object.
+    const _: Option<&dyn RepoFiles> = None;
+}
@@ -1,6 +1,6 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, path::PathBuf};
 
-use crate::domain::model::{path_buf_pair::PathBufPair, path_like::PathLike};
+use crate::domain::model::{path_buf_pair::PathBufPair, path_like::PathLike, repo::RepoKind};
 
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum RepoProbeError {
@@ -16,7 +16,9 @@ pub(crate) trait RepoProbe: Debug {
 
 #[derive(Debug)]
 pub(crate) struct RepoProbeResult {
-    pub(crate) is_bare: bool,
+    pub(crate) kind: RepoKind,
+    /// Canonical path of the common git directory shared with linked worktrees.
+    pub(crate) common_dir: PathBuf,
 }
 
 #[cfg(test)]
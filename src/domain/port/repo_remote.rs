@@ -0,0 +1,28 @@
+use std::fmt::Debug;
+
+use crate::domain::model::path_like::PathLike;
+
+pub(crate) trait RepoRemote: Debug {
+    /// Returns the fetch URL of the remote `name`, or `None` if the remote does not exist.
+    fn remote_url(
+        &self,
+        repo_path: &dyn PathLike,
+        name: &str,
+    ) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync + 'static>>;
+
+    /// Sets the fetch URL of the existing remote `name`.
+    fn set_remote_url(
+        &self,
+        repo_path: &dyn PathLike,
+        name: &str,
+        url: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Assert object safety for trait object.
+    const _: Option<&dyn RepoRemote> = None;
+}
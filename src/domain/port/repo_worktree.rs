@@ -0,0 +1,62 @@
+use std::{fmt::Debug, path::PathBuf};
+
+use crate::domain::model::path_like::PathLike;
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum RemoveWorktreeError {
+    #[error("worktree has uncommitted changes: {}", path.display())]
+    Dirty { path: PathBuf },
+    #[error(transparent)]
+    Backend(Box<dyn std::error::Error + Send + Sync + 'static>),
+}
+
+pub(crate) trait RepoWorktree: Debug {
+    /// Adds a linked worktree named `name` at `worktree_path` and checks out `branch` in it.
+    ///
+    /// If the local branch does not exist, it is created from the remote-tracking
+    /// branch `origin/<branch>` if any, or from `HEAD` otherwise.
+    fn add_worktree(
+        &self,
+        repo_path: &dyn PathLike,
+        name: &str,
+        branch: &str,
+        worktree_path: &dyn PathLike,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>>;
+
+    /// Returns the linked worktrees of the repository.
+    fn list_worktrees(
+        &self,
+        repo_path: &dyn PathLike,
+    ) -> Result<Vec<WorktreeInfo>, Box<dyn std::error::Error + Send + Sync + 'static>>;
+
+    /// Removes the linked worktree named `name` with its working directory.
+    ///
+    /// Unless `force` is `true`, worktrees with uncommitted changes or locked
+    /// worktrees are not removed.
+    fn remove_worktree(
+        &self,
+        repo_path: &dyn PathLike,
+        name: &str,
+        force: bool,
+    ) -> Result<(), RemoveWorktreeError>;
+}
+
+#[derive(Debug)]
+pub(crate) struct WorktreeInfo {
+    /// Name of the worktree in git, which is not always derived from the branch.
+    pub(crate) name: String,
+    pub(crate) path: PathBuf,
+    /// Branch checked out in the worktree, or `None` if `HEAD` is detached.
+    pub(crate) branch: Option<String>,
+    pub(crate) locked: bool,
+    /// `true` if the working directory of the worktree no longer exists.
+    pub(crate) prunable: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Assert object safety for trait object.
+    const _: Option<&dyn RepoWorktree> = None;
+}
@@ -0,0 +1,19 @@
+use std::{collections::HashMap, fmt::Debug, path::Path};
+
+pub(crate) trait SshConfig: Debug {
+    /// Returns a map from `Host` aliases to their `HostName` in the ssh config file at `path`.
+    ///
+    /// Returns an empty map if the file does not exist.
+    fn host_aliases(
+        &self,
+        path: &Path,
+    ) -> Result<HashMap<String, String>, Box<dyn std::error::Error + Send + Sync + 'static>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Assert object safety for trait object.
+    const _: Option<&dyn SshConfig> = None;
+}
@@ -0,0 +1,37 @@
+use std::{fmt::Debug, path::Path};
+
+use chrono::{DateTime, Utc};
+
+use crate::domain::model::{path_like::PathLike, visit::RepoVisits};
+
+pub(crate) trait VisitStore: Debug {
+    /// Loads persisted visits into memory.
+    fn load(
+        &self,
+        path: &dyn PathLike,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>>;
+
+    /// Records a visit to the repository at the given canonical path, and persists the visits.
+    ///
+    /// Repositories last visited before `expire_before` are forgotten. The
+    /// persisted visits are reloaded and updated under a file lock, so that
+    /// visits recorded concurrently by other processes are not lost.
+    fn record(
+        &self,
+        path: &dyn PathLike,
+        canonical_path: &Path,
+        now: DateTime<Utc>,
+        expire_before: DateTime<Utc>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>>;
+
+    /// Returns the visits recorded for each repository.
+    fn visits(&self) -> Vec<RepoVisits>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Assert object safety for trait object.
+    const _: Option<&dyn VisitStore> = None;
+}
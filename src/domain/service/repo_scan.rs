@@ -33,11 +33,15 @@ impl RepoScanService {
         }
     }
 
-    pub(crate) fn repos(&self, root: &CanonicalRoot) -> Result<Repos, Box<dyn std::error::Error>> {
+    pub(crate) fn repos(
+        &self,
+        root: &CanonicalRoot,
+        follow_symlinks: bool,
+    ) -> Result<Repos, Box<dyn std::error::Error>> {
         Ok(Repos {
             path_canonicalizer: Arc::clone(&self.path_canonicalizer),
             repo_probe: Arc::clone(&self.repo_probe),
-            port: self.dir_walker.entries(root)?,
+            port: self.dir_walker.entries(root, follow_symlinks)?,
         })
     }
 }
@@ -136,7 +140,7 @@ where
         let repo = Repo::from_relative_path(
             self.root().as_root(),
             self.relative_path().to_owned(),
-            repo_probe.is_bare,
+            repo_probe.kind,
         );
         let canonical_path =
             self.path_canonicalizer
@@ -146,7 +150,7 @@ where
                     repo_relative_path: repo.relative_path().to_owned(),
                     source,
                 })?;
-        let canonical_repo = CanonicalRepo::new(repo, canonical_path);
+        let canonical_repo = CanonicalRepo::new(repo, canonical_path, repo_probe.common_dir);
         Ok(Some(canonical_repo))
     }
 }
@@ -61,6 +61,23 @@ impl DirEditor for FsDirEditor {
 
         Ok(true)
     }
+
+    fn move_dir(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        // `rename` replaces an empty directory on some platforms, so check it first.
+        if fs::symlink_metadata(to).is_ok() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("destination already exists: {}", to.display()),
+            )
+            .into());
+        }
+        fs::rename(from, to)?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -264,4 +281,28 @@ mod tests {
         readonly_dir.set_readonly(false).unwrap();
         test_dir.close().unwrap();
     }
+
+    #[test]
+    fn move_dir() {
+        let test_dir = TempDir::new().unwrap();
+        let from = test_dir.child("from");
+        from.child("file").touch().unwrap();
+        let to = test_dir.child("to");
+        let existing = test_dir.child("existing");
+        existing.create_dir_all().unwrap();
+        let no_parent = test_dir.child("missing/to");
+
+        let editor = FsDirEditor::new();
+
+        // Returns `Err` if something already exists at `to`
+        assert!(editor.move_dir(&from, &existing).is_err());
+        // Returns `Err` if the parent of `to` does not exist
+        assert!(editor.move_dir(&from, &no_parent).is_err());
+
+        editor.move_dir(&from, &to).unwrap();
+        assert!(!from.exists());
+        assert!(to.child("file").exists());
+
+        test_dir.close().unwrap();
+    }
 }
@@ -21,8 +21,9 @@ impl DirWalker for FsDirWalker {
     fn entries(
         &self,
         root: &CanonicalRoot,
+        follow_symlinks: bool,
     ) -> Result<Box<dyn DirEntries>, Box<dyn std::error::Error>> {
-        Ok(Box::new(FsDirEntries::new(root)))
+        Ok(Box::new(FsDirEntries::new(root, follow_symlinks)))
     }
 }
 
@@ -35,9 +36,12 @@ pub(super) struct FsDirEntries {
 }
 
 impl FsDirEntries {
-    pub(super) fn new(root: &CanonicalRoot) -> Self {
+    pub(super) fn new(root: &CanonicalRoot, follow_symlinks: bool) -> Self {
         let root = Arc::new(root.clone());
+        // `walkdir` detects symlink loops by itself when following links, and
+        // reports them as errors without descending into them.
         let iter = walkdir::WalkDir::new(root.path().as_real_path())
+            .follow_links(follow_symlinks)
             .sort_by_file_name()
             .into_iter();
         Self {
@@ -1,5 +1,8 @@
-pub(in crate::infrastructure) use self::{dir_editor::*, dir_walker::*, path_canonicalizer::*};
+pub(in crate::infrastructure) use self::{
+    dir_editor::*, dir_walker::*, path_canonicalizer::*, ssh_config::*,
+};
 
 mod dir_editor;
 mod dir_walker;
 mod path_canonicalizer;
+mod ssh_config;
@@ -0,0 +1,123 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::domain::port::ssh_config::SshConfig;
+
+#[derive(Debug)]
+pub(in crate::infrastructure) struct FsSshConfig {}
+
+impl FsSshConfig {
+    pub(in crate::infrastructure) fn new() -> Self {
+        Self {}
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error("failed to read ssh config file: {}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+impl SshConfig for FsSshConfig {
+    fn host_aliases(
+        &self,
+        path: &Path,
+    ) -> Result<HashMap<String, String>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(HashMap::new()),
+            Err(source) => {
+                let path = path.to_owned();
+                return Err(Error::Read { path, source }.into());
+            }
+        };
+        Ok(parse_host_aliases(&content))
+    }
+}
+
+/// Collects `HostName` of each `Host` block whose patterns are plain host names.
+///
+/// Patterns containing wildcards or negations are ignored, and the first
+/// `HostName` obtained for a host wins, as ssh does. `Include` and `Match`
+/// directives are not evaluated.
+fn parse_host_aliases(content: &str) -> HashMap<String, String> {
+    let mut aliases = HashMap::new();
+    let mut hosts: Vec<&str> = vec![];
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (keyword, args) = line
+            .split_once(|c: char| c.is_whitespace() || c == '=')
+            .unwrap_or((line, ""));
+        let args = args.trim_start_matches(|c: char| c.is_whitespace() || c == '=');
+        if keyword.eq_ignore_ascii_case("host") {
+            hosts = args
+                .split_whitespace()
+                .filter(|pattern| !pattern.contains(['*', '?', '!']))
+                .collect();
+        } else if keyword.eq_ignore_ascii_case("match") {
+            hosts.clear();
+        } else if keyword.eq_ignore_ascii_case("hostname") {
+            let Some(host_name) = args.split_whitespace().next() else {
+                continue;
+            };
+            for host in &hosts {
+                if *host != host_name {
+                    aliases
+                        .entry((*host).to_owned())
+                        .or_insert_with(|| host_name.to_owned());
+                }
+            }
+        }
+    }
+    aliases
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ssh_config_host_aliases() {
+        let content = r#"
+# work account
+Host work-gh work-github
+    HostName github.com
+    User git
+    IdentityFile ~/.ssh/id_work
+
+Host=personal-gl
+    HostName=gitlab.com
+
+Host *.corp !bastion.corp
+    HostName proxy.corp
+
+Match host foo
+    HostName bar
+
+Host work-gh
+    HostName ignored.example
+"#;
+        let aliases = parse_host_aliases(content);
+        assert_eq!(
+            aliases,
+            HashMap::from_iter(
+                [
+                    ("work-gh", "github.com"),
+                    ("work-github", "github.com"),
+                    ("personal-gl", "gitlab.com"),
+                ]
+                .map(|(alias, host)| (alias.to_owned(), host.to_owned()))
+            )
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use crate::domain::port::git_backend::{GitBackend, GitBackendFeatures, SshAgentStatus};
+
+#[derive(Debug)]
+pub(in crate::infrastructure) struct Git2GitBackend {}
+
+impl Git2GitBackend {
+    pub(in crate::infrastructure) fn new() -> Self {
+        Self {}
+    }
+}
+
+impl GitBackend for Git2GitBackend {
+    fn features(&self) -> GitBackendFeatures {
+        let version = git2::Version::get();
+        let (major, minor, patch) = version.libgit2_version();
+        GitBackendFeatures {
+            version: format!("libgit2 {major}.{minor}.{patch}"),
+            https: version.https(),
+            ssh: version.ssh(),
+            vendored: version.vendored(),
+        }
+    }
+
+    fn ssh_agent(&self) -> SshAgentStatus {
+        if !cfg!(unix) {
+            // Windows uses a named pipe without `SSH_AUTH_SOCK`.
+            return SshAgentStatus::Unknown;
+        }
+        match std::env::var_os("SSH_AUTH_SOCK") {
+            None => SshAgentStatus::NotConfigured,
+            Some(path) if std::path::Path::new(&path).exists() => SshAgentStatus::Available,
+            Some(_) => SshAgentStatus::MissingSocket,
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::BTreeMap;
+
+use crate::domain::{model::url_rewrite::UrlRewriteRule, port::git_config::GitConfig};
+
+#[derive(Debug)]
+pub(in crate::infrastructure) struct Git2GitConfig {}
+
+impl Git2GitConfig {
+    pub(in crate::infrastructure) fn new() -> Self {
+        Self {}
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error("failed to open git config")]
+    OpenConfig {
+        #[source]
+        source: git2::Error,
+    },
+    #[error("failed to read git config entries")]
+    ReadEntries {
+        #[source]
+        source: git2::Error,
+    },
+}
+
+impl GitConfig for Git2GitConfig {
+    fn url_rewrites(
+        &self,
+    ) -> Result<Vec<UrlRewriteRule>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let config = git2::Config::open_default().map_err(|source| Error::OpenConfig { source })?;
+        Ok(url_rewrites(&config)?)
+    }
+}
+
+fn url_rewrites(config: &git2::Config) -> Result<Vec<UrlRewriteRule>, Error> {
+    let mut rules = BTreeMap::<String, UrlRewriteRule>::new();
+    let mut entries = config
+        .entries(Some(r"^url\..*\.(insteadof|pushinsteadof)$"))
+        .map_err(|source| Error::ReadEntries { source })?;
+    while let Some(entry) = entries.next() {
+        let entry = entry.map_err(|source| Error::ReadEntries { source })?;
+        let (Ok(name), Ok(value)) = (entry.name(), entry.value()) else {
+            continue;
+        };
+        // `name` is `url.<base>.insteadof`, and `<base>` may contain dots
+        let Some(name) = name.strip_prefix("url.") else {
+            continue;
+        };
+        let Some((base, key)) = name.rsplit_once('.') else {
+            continue;
+        };
+        let rule = rules
+            .entry(base.to_owned())
+            .or_insert_with(|| UrlRewriteRule {
+                base: base.to_owned(),
+                ..Default::default()
+            });
+        match key {
+            "insteadof" => rule.instead_of.push(value.to_owned()),
+            "pushinsteadof" => rule.push_instead_of.push(value.to_owned()),
+            _ => {}
+        }
+    }
+    Ok(rules.into_values().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::{TempDir, prelude::*};
+
+    use super::*;
+
+    #[test]
+    fn read_url_rewrites() {
+        let test_dir = TempDir::new().unwrap();
+        let config_file = test_dir.child("gitconfig");
+        config_file
+            .write_str(
+                r#"
+[url "ssh://git@github.com/corp/"]
+    insteadOf = https://github.com/corp/
+    insteadOf = gh-corp:
+[url "git@github.com:"]
+    pushInsteadOf = https://github.com/
+[user]
+    name = souko
+"#,
+            )
+            .unwrap();
+        let config = git2::Config::open(config_file.path()).unwrap();
+
+        let rules = url_rewrites(&config).unwrap();
+        assert_eq!(
+            rules,
+            [
+                UrlRewriteRule {
+                    base: "git@github.com:".to_owned(),
+                    instead_of: vec![],
+                    push_instead_of: vec!["https://github.com/".to_owned()],
+                },
+                UrlRewriteRule {
+                    base: "ssh://git@github.com/corp/".to_owned(),
+                    instead_of: vec!["https://github.com/corp/".to_owned(), "gh-corp:".to_owned()],
+                    push_instead_of: vec![],
+                },
+            ]
+        );
+
+        // Ensure `test_dir` and its contents are deleted
+        test_dir.close().unwrap();
+    }
+}
@@ -1,4 +1,12 @@
-pub(in crate::infrastructure) use self::{repo_clone::*, repo_probe::*};
+pub(in crate::infrastructure) use self::{
+    git_backend::*, git_config::*, repo_clone::*, repo_files::*, repo_probe::*, repo_remote::*,
+    repo_worktree::*,
+};
 
+mod git_backend;
+mod git_config;
 mod repo_clone;
+mod repo_files;
 mod repo_probe;
+mod repo_remote;
+mod repo_worktree;
@@ -1,7 +1,10 @@
 use git2_credentials::CredentialHandler;
 use url::Url;
 
-use crate::domain::{model::path_like::PathLike, port::clone_repo::RepoClone};
+use crate::domain::{
+    model::{path_like::PathLike, remote::Remote},
+    port::clone_repo::RepoClone,
+};
 
 #[derive(Debug)]
 pub(in crate::infrastructure) struct Git2RepoClone {}
@@ -25,12 +28,27 @@ enum Error {
         #[source]
         source: git2::Error,
     },
+    #[error("failed to add remote `{name}` with URL {url}")]
+    AddRemote {
+        name: String,
+        url: String,
+        #[source]
+        source: git2::Error,
+    },
+    #[error("failed to set push URL of remote `{name}` to {push_url}")]
+    SetPushUrl {
+        name: String,
+        push_url: String,
+        #[source]
+        source: git2::Error,
+    },
 }
 
 impl RepoClone for Git2RepoClone {
     fn clone_repo(
         &self,
-        url: &url::Url,
+        origin: &Remote,
+        extra_remotes: &[Remote],
         path: &dyn PathLike,
         bare: bool,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
@@ -52,15 +70,38 @@ impl RepoClone for Git2RepoClone {
             .download_tags(git2::AutotagOption::All)
             .update_fetchhead(true);
 
-        let _repo = git2::build::RepoBuilder::new()
+        let repo = git2::build::RepoBuilder::new()
             .bare(bare)
             .fetch_options(fetch_opt)
-            .clone(url.as_str(), path.as_real_path())
+            .clone(origin.url().as_str(), path.as_real_path())
             .map_err(|err| Error::Clone {
-                url: url.clone(),
+                url: origin.url().clone(),
                 source: err,
             })?;
 
+        set_push_url(&repo, origin)?;
+        for remote in extra_remotes {
+            repo.remote(remote.name(), remote.url().as_str())
+                .map_err(|err| Error::AddRemote {
+                    name: remote.name().to_owned(),
+                    url: remote.url().to_string(),
+                    source: err,
+                })?;
+            set_push_url(&repo, remote)?;
+        }
+
         Ok(())
     }
 }
+
+fn set_push_url(repo: &git2::Repository, remote: &Remote) -> Result<(), Error> {
+    let Some(push_url) = remote.push_url() else {
+        return Ok(());
+    };
+    repo.remote_set_pushurl(remote.name(), Some(push_url))
+        .map_err(|err| Error::SetPushUrl {
+            name: remote.name().to_owned(),
+            push_url: push_url.to_owned(),
+            source: err,
+        })
+}
@@ -0,0 +1,165 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::domain::{
+    model::path_like::PathLike,
+    port::repo_files::{FileSource, RepoFiles},
+};
+
+#[derive(Debug)]
+pub(in crate::infrastructure) struct Git2RepoFiles {}
+
+impl Git2RepoFiles {
+    pub(in crate::infrastructure) fn new() -> Self {
+        Self {}
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error("failed to open repository: {}", path.display())]
+    Open {
+        path: PathBuf,
+        #[source]
+        source: git2::Error,
+    },
+    #[error("failed to read HEAD of {}", path.display())]
+    Head {
+        path: PathBuf,
+        #[source]
+        source: git2::Error,
+    },
+    #[error("failed to read index of {}", path.display())]
+    Index {
+        path: PathBuf,
+        #[source]
+        source: git2::Error,
+    },
+    #[error("failed to get status of {}", path.display())]
+    Status {
+        path: PathBuf,
+        #[source]
+        source: git2::Error,
+    },
+}
+
+const MODE_MASK: u32 = 0o170000;
+const MODE_REGULAR: u32 = 0o100000;
+
+fn is_regular_file(mode: u32) -> bool {
+    mode & MODE_MASK == MODE_REGULAR
+}
+
+fn for_each_head_file(
+    repo: &git2::Repository,
+    repo_path: &Path,
+    f: &mut dyn FnMut(&Path, &[u8]),
+) -> Result<(), Error> {
+    let head_err = |source| Error::Head {
+        path: repo_path.to_owned(),
+        source,
+    };
+    let head = match repo.head() {
+        Ok(head) => head,
+        // A repository without commits has no files at HEAD.
+        Err(e) if e.code() == git2::ErrorCode::UnbornBranch => return Ok(()),
+        Err(e) => return Err(head_err(e)),
+    };
+    let tree = head.peel_to_tree().map_err(head_err)?;
+    tree.walk(git2::TreeWalkMode::PreOrder, |dir, entry| {
+        if !is_regular_file(entry.filemode() as u32) {
+            return git2::TreeWalkResult::Ok;
+        }
+        let Ok(name) = entry.name() else {
+            return git2::TreeWalkResult::Ok;
+        };
+        if let Ok(blob) = repo.find_blob(entry.id()) {
+            f(&Path::new(dir).join(name), blob.content());
+        }
+        git2::TreeWalkResult::Ok
+    })
+    .map_err(head_err)?;
+    Ok(())
+}
+
+fn for_each_work_tree_file(
+    repo: &git2::Repository,
+    repo_path: &Path,
+    workdir: &Path,
+    untracked: bool,
+    f: &mut dyn FnMut(&Path, &[u8]),
+) -> Result<(), Error> {
+    let mut paths = vec![];
+    let index = repo.index().map_err(|source| Error::Index {
+        path: repo_path.to_owned(),
+        source,
+    })?;
+    for entry in index.iter() {
+        if is_regular_file(entry.mode) {
+            paths.push(PathBuf::from(
+                String::from_utf8_lossy(&entry.path).into_owned(),
+            ));
+        }
+    }
+    if untracked {
+        let mut opts = git2::StatusOptions::new();
+        opts.include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .include_ignored(false)
+            .exclude_submodules(true);
+        let statuses = repo
+            .statuses(Some(&mut opts))
+            .map_err(|source| Error::Status {
+                path: repo_path.to_owned(),
+                source,
+            })?;
+        for status in statuses.iter() {
+            if status.status().is_wt_new()
+                && let Ok(path) = status.path()
+            {
+                paths.push(PathBuf::from(path));
+            }
+        }
+        paths.sort();
+    }
+
+    for path in paths {
+        let full_path = workdir.join(&path);
+        // Files deleted from the working tree or replaced with other kinds of
+        // files are skipped.
+        let is_file = fs::symlink_metadata(&full_path).is_ok_and(|meta| meta.is_file());
+        if !is_file {
+            continue;
+        }
+        if let Ok(content) = fs::read(&full_path) {
+            f(&path, &content);
+        }
+    }
+    Ok(())
+}
+
+impl RepoFiles for Git2RepoFiles {
+    fn for_each_file(
+        &self,
+        repo_path: &dyn PathLike,
+        source: FileSource,
+        f: &mut dyn FnMut(&Path, &[u8]),
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let repo_path = repo_path.as_real_path();
+        let repo = git2::Repository::open(repo_path).map_err(|source| Error::Open {
+            path: repo_path.to_owned(),
+            source,
+        })?;
+        match (source, repo.workdir()) {
+            (FileSource::WorkTree { untracked }, Some(workdir)) => {
+                for_each_work_tree_file(&repo, repo_path, workdir, untracked, f)?;
+            }
+            (FileSource::Head, _) | (FileSource::WorkTree { .. }, None) => {
+                for_each_head_file(&repo, repo_path, f)?;
+            }
+        }
+        Ok(())
+    }
+}
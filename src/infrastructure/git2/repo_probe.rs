@@ -1,5 +1,7 @@
+use std::path::{Path, PathBuf};
+
 use crate::domain::{
-    model::{path_buf_pair::PathBufPair, path_like::PathLike},
+    model::{path_buf_pair::PathBufPair, path_like::PathLike, repo::RepoKind},
     port::repo_probe::{RepoProbe, RepoProbeError, RepoProbeResult},
 };
 
@@ -32,8 +34,97 @@ impl RepoProbe for Git2RepoProbe {
                 RepoProbeError::Backend(Error::Open { path, source }.into())
             }
         })?;
+
+        let kind = if repo.is_bare() {
+            RepoKind::Bare
+        } else if repo.is_worktree() {
+            RepoKind::LinkedWorktree
+        } else if is_submodule_git_dir(repo.path()) {
+            RepoKind::Submodule
+        } else {
+            RepoKind::Normal
+        };
+
         Ok(RepoProbeResult {
-            is_bare: repo.is_bare(),
+            kind,
+            common_dir: canonicalize_or_keep(repo.commondir()),
         })
     }
 }
+
+/// Returns `true` if the git directory is stored under `<superproject>/.git/modules/`.
+///
+/// This is where `git submodule` places the git directory of a submodule and
+/// refers to it from the `.git` file in the submodule's working tree.
+fn is_submodule_git_dir(git_dir: &Path) -> bool {
+    git_dir.ancestors().any(|path| {
+        path.file_name().is_some_and(|name| name == "modules")
+            && path
+                .parent()
+                .and_then(Path::file_name)
+                .is_some_and(|name| name == ".git")
+    })
+}
+
+fn canonicalize_or_keep(path: &Path) -> PathBuf {
+    dunce::canonicalize(path).unwrap_or_else(|_| path.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::{TempDir, prelude::*};
+
+    use super::*;
+
+    #[test]
+    fn probe_repo_kinds() {
+        let test_dir = TempDir::new().unwrap();
+        let probe = Git2RepoProbe::new();
+
+        let normal = test_dir.child("normal");
+        let repo = git2::Repository::init(normal.path()).unwrap();
+        let sig = git2::Signature::now("souko", "souko@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "initial", &tree, &[])
+            .unwrap();
+
+        let bare = test_dir.child("bare.git");
+        git2::Repository::init_bare(bare.path()).unwrap();
+
+        let worktree = test_dir.child("worktree");
+        repo.worktree("worktree", worktree.path(), None).unwrap();
+
+        let submodule = test_dir.child("normal/sub");
+        let module_git_dir = test_dir.child("normal/.git/modules/sub");
+        // a git directory in the superproject referred to by a `.git` file
+        git2::Repository::init_opts(
+            module_git_dir.path(),
+            git2::RepositoryInitOptions::new().workdir_path(submodule.path()),
+        )
+        .unwrap();
+        assert!(submodule.child(".git").path().is_file());
+
+        let canonical_git_dir = dunce::canonicalize(normal.child(".git").path()).unwrap();
+
+        let result = probe.probe(&normal.path()).unwrap();
+        assert_eq!(result.kind, RepoKind::Normal);
+        assert_eq!(result.common_dir, canonical_git_dir);
+
+        let result = probe.probe(&bare.path()).unwrap();
+        assert_eq!(result.kind, RepoKind::Bare);
+
+        let result = probe.probe(&worktree.path()).unwrap();
+        assert_eq!(result.kind, RepoKind::LinkedWorktree);
+        assert_eq!(result.common_dir, canonical_git_dir);
+
+        let result = probe.probe(&submodule.path()).unwrap();
+        assert_eq!(result.kind, RepoKind::Submodule);
+
+        let err = probe.probe(&test_dir.path()).unwrap_err();
+        assert!(matches!(err, RepoProbeError::NotARepo { .. }));
+
+        // Ensure `test_dir` and its contents are deleted
+        test_dir.close().unwrap();
+    }
+}
@@ -0,0 +1,84 @@
+use std::path::{Path, PathBuf};
+
+use crate::domain::{model::path_like::PathLike, port::repo_remote::RepoRemote};
+
+#[derive(Debug)]
+pub(in crate::infrastructure) struct Git2RepoRemote {}
+
+impl Git2RepoRemote {
+    pub(in crate::infrastructure) fn new() -> Self {
+        Self {}
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error("failed to open repository: {}", path.display())]
+    Open {
+        path: PathBuf,
+        #[source]
+        source: git2::Error,
+    },
+    #[error("failed to set URL of remote `{name}` of {}", path.display())]
+    Write {
+        name: String,
+        path: PathBuf,
+        #[source]
+        source: git2::Error,
+    },
+    #[error("failed to read remote `{name}` of {}", path.display())]
+    Read {
+        name: String,
+        path: PathBuf,
+        #[source]
+        source: git2::Error,
+    },
+}
+
+fn open(path: &Path) -> Result<git2::Repository, Error> {
+    git2::Repository::open(path).map_err(|source| Error::Open {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+impl RepoRemote for Git2RepoRemote {
+    fn remote_url(
+        &self,
+        repo_path: &dyn PathLike,
+        name: &str,
+    ) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let path = repo_path.as_real_path();
+        let repo = open(path)?;
+        let remote = match repo.find_remote(name) {
+            Ok(remote) => remote,
+            Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(None),
+            Err(source) => {
+                return Err(Error::Read {
+                    name: name.to_owned(),
+                    path: path.to_owned(),
+                    source,
+                }
+                .into());
+            }
+        };
+        Ok(remote.url().ok().map(str::to_owned))
+    }
+
+    fn set_remote_url(
+        &self,
+        repo_path: &dyn PathLike,
+        name: &str,
+        url: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let path = repo_path.as_real_path();
+        let repo = open(path)?;
+        repo.remote_set_url(name, url)
+            .map_err(|source| Error::Write {
+                name: name.to_owned(),
+                path: path.to_owned(),
+                source,
+            })?;
+        Ok(())
+    }
+}
@@ -0,0 +1,239 @@
+use std::path::{Path, PathBuf};
+
+use crate::domain::{
+    model::path_like::PathLike,
+    port::repo_worktree::{RemoveWorktreeError, RepoWorktree, WorktreeInfo},
+};
+
+#[derive(Debug)]
+pub(in crate::infrastructure) struct Git2RepoWorktree {}
+
+impl Git2RepoWorktree {
+    pub(in crate::infrastructure) fn new() -> Self {
+        Self {}
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error("failed to open repository: {}", path.display())]
+    Open {
+        path: PathBuf,
+        #[source]
+        source: git2::Error,
+    },
+    #[error("failed to create branch `{branch}`")]
+    CreateBranch {
+        branch: String,
+        #[source]
+        source: git2::Error,
+    },
+    #[error("failed to add worktree `{name}`: {}", path.display())]
+    Add {
+        name: String,
+        path: PathBuf,
+        #[source]
+        source: git2::Error,
+    },
+    #[error("failed to list worktrees of {}", path.display())]
+    List {
+        path: PathBuf,
+        #[source]
+        source: git2::Error,
+    },
+    #[error("worktree `{name}` not found")]
+    NotFound {
+        name: String,
+        #[source]
+        source: git2::Error,
+    },
+    #[error("worktree `{name}` is locked")]
+    Locked { name: String },
+    #[error("failed to get lock status of worktree `{name}`")]
+    LockStatus {
+        name: String,
+        #[source]
+        source: git2::Error,
+    },
+    #[error("failed to get status of worktree: {}", path.display())]
+    Status {
+        path: PathBuf,
+        #[source]
+        source: git2::Error,
+    },
+    #[error("failed to remove worktree `{name}`")]
+    Prune {
+        name: String,
+        #[source]
+        source: git2::Error,
+    },
+}
+
+fn open(path: &Path) -> Result<git2::Repository, Error> {
+    git2::Repository::open(path).map_err(|source| Error::Open {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+/// Returns the local branch, and whether it was created by this call.
+fn find_or_create_branch<'r>(
+    repo: &'r git2::Repository,
+    branch: &str,
+) -> Result<(git2::Branch<'r>, bool), Error> {
+    if let Ok(local) = repo.find_branch(branch, git2::BranchType::Local) {
+        return Ok((local, false));
+    }
+
+    let create = || -> Result<git2::Branch<'r>, git2::Error> {
+        let remote_name = format!("origin/{branch}");
+        if let Ok(remote) = repo.find_branch(&remote_name, git2::BranchType::Remote) {
+            let commit = remote.get().peel_to_commit()?;
+            let mut local = repo.branch(branch, &commit, false)?;
+            local.set_upstream(Some(&remote_name))?;
+            return Ok(local);
+        }
+        let commit = repo.head()?.peel_to_commit()?;
+        repo.branch(branch, &commit, false)
+    };
+    let local = create().map_err(|source| Error::CreateBranch {
+        branch: branch.to_owned(),
+        source,
+    })?;
+    Ok((local, true))
+}
+
+impl RepoWorktree for Git2RepoWorktree {
+    fn add_worktree(
+        &self,
+        repo_path: &dyn PathLike,
+        name: &str,
+        branch: &str,
+        worktree_path: &dyn PathLike,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let repo = open(repo_path.as_real_path())?;
+        let (mut branch, created) = find_or_create_branch(&repo, branch)?;
+        let mut opts = git2::WorktreeAddOptions::new();
+        opts.reference(Some(branch.get()));
+        if let Err(source) = repo.worktree(name, worktree_path.as_real_path(), Some(&opts)) {
+            // Do not leave behind a branch created only for the worktree.
+            if created {
+                let _ = branch.delete();
+            }
+            return Err(Error::Add {
+                name: name.to_owned(),
+                path: worktree_path.as_real_path().to_owned(),
+                source,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    fn list_worktrees(
+        &self,
+        repo_path: &dyn PathLike,
+    ) -> Result<Vec<WorktreeInfo>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let repo = open(repo_path.as_real_path())?;
+        let list_err = |source| Error::List {
+            path: repo_path.as_real_path().to_owned(),
+            source,
+        };
+        let names = repo.worktrees().map_err(list_err)?;
+
+        let mut worktrees = vec![];
+        for name in names.iter() {
+            let Some(name) = name.map_err(list_err)? else {
+                continue;
+            };
+            let worktree = repo.find_worktree(name).map_err(list_err)?;
+            let locked = !matches!(
+                worktree.is_locked().map_err(list_err)?,
+                git2::WorktreeLockStatus::Unlocked
+            );
+            let prunable = worktree.validate().is_err();
+            let branch = if prunable {
+                None
+            } else {
+                git2::Repository::open_from_worktree(&worktree)
+                    .ok()
+                    .and_then(|repo| {
+                        let head = repo.head().ok()?;
+                        if !head.is_branch() {
+                            return None;
+                        }
+                        head.shorthand().ok().map(str::to_owned)
+                    })
+            };
+            worktrees.push(WorktreeInfo {
+                name: name.to_owned(),
+                path: worktree.path().to_owned(),
+                branch,
+                locked,
+                prunable,
+            });
+        }
+        Ok(worktrees)
+    }
+
+    fn remove_worktree(
+        &self,
+        repo_path: &dyn PathLike,
+        name: &str,
+        force: bool,
+    ) -> Result<(), RemoveWorktreeError> {
+        let backend = |err: Error| RemoveWorktreeError::Backend(err.into());
+
+        let repo = open(repo_path.as_real_path()).map_err(backend)?;
+        let worktree = repo.find_worktree(name).map_err(|source| {
+            backend(Error::NotFound {
+                name: name.to_owned(),
+                source,
+            })
+        })?;
+
+        if !force {
+            let locked = worktree.is_locked().map_err(|source| {
+                backend(Error::LockStatus {
+                    name: name.to_owned(),
+                    source,
+                })
+            })?;
+            if !matches!(locked, git2::WorktreeLockStatus::Unlocked) {
+                return Err(backend(Error::Locked {
+                    name: name.to_owned(),
+                }));
+            }
+
+            if worktree.validate().is_ok() {
+                let path = worktree.path().to_owned();
+                let status_err = |source| {
+                    backend(Error::Status {
+                        path: path.clone(),
+                        source,
+                    })
+                };
+                let wt_repo =
+                    git2::Repository::open_from_worktree(&worktree).map_err(status_err)?;
+                let mut status_opts = git2::StatusOptions::new();
+                status_opts.include_untracked(true).include_ignored(false);
+                let statuses = wt_repo
+                    .statuses(Some(&mut status_opts))
+                    .map_err(status_err)?;
+                if !statuses.is_empty() {
+                    return Err(RemoveWorktreeError::Dirty { path });
+                }
+            }
+        }
+
+        let mut prune_opts = git2::WorktreePruneOptions::new();
+        prune_opts.valid(true).locked(force).working_tree(true);
+        worktree.prune(Some(&mut prune_opts)).map_err(|source| {
+            backend(Error::Prune {
+                name: name.to_owned(),
+                source,
+            })
+        })?;
+        Ok(())
+    }
+}
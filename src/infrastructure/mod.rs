@@ -3,9 +3,12 @@ use std::sync::Arc;
 use crate::{
     domain::port::Ports,
     infrastructure::{
-        fs::{FsDirEditor, FsDirWalker, FsPathCanonicalizer},
-        git2::{Git2RepoClone, Git2RepoProbe},
-        persistence::JsonRepoCache,
+        fs::{FsDirEditor, FsDirWalker, FsPathCanonicalizer, FsSshConfig},
+        git2::{
+            Git2GitBackend, Git2GitConfig, Git2RepoClone, Git2RepoFiles, Git2RepoProbe,
+            Git2RepoRemote, Git2RepoWorktree,
+        },
+        persistence::{JsonRepoCache, JsonVisitStore},
     },
 };
 
@@ -20,6 +23,13 @@ pub(crate) fn ports() -> Ports {
         dir_editor: Arc::new(FsDirEditor::new()),
         repo_clone: Arc::new(Git2RepoClone::new()),
         repo_probe: Arc::new(Git2RepoProbe::new()),
+        repo_files: Arc::new(Git2RepoFiles::new()),
+        repo_remote: Arc::new(Git2RepoRemote::new()),
         repo_cache: Arc::new(JsonRepoCache::new()),
+        repo_worktree: Arc::new(Git2RepoWorktree::new()),
+        git_config: Arc::new(Git2GitConfig::new()),
+        git_backend: Arc::new(Git2GitBackend::new()),
+        ssh_config: Arc::new(FsSshConfig::new()),
+        visit_store: Arc::new(JsonVisitStore::new()),
     }
 }
@@ -1,3 +1,4 @@
-pub(in crate::infrastructure) use self::repo_cache::*;
+pub(in crate::infrastructure) use self::{repo_cache::*, visit_store::*};
 
 mod repo_cache;
+mod visit_store;
@@ -11,7 +11,7 @@ use crate::{
     domain::{
         model::{
             path_like::PathLike,
-            repo::{CanonicalRepo, Repo},
+            repo::{CanonicalRepo, Repo, RepoKind},
             root::{CanonicalRoot, Root},
         },
         port::repo_cache::{RepoCache, RepoCacheEntry},
@@ -82,6 +82,25 @@ impl RepoCache for JsonRepoCache {
             entry,
         })
     }
+
+    fn repos(&self, root: &CanonicalRoot) -> Vec<CanonicalRepo> {
+        let this = self.0.lock().unwrap();
+        let Some(root_cache) = this.cache.roots.get(root.name()) else {
+            return vec![];
+        };
+        if !root_cache.is_valid(root) {
+            return vec![];
+        }
+        root_cache
+            .repos
+            .iter()
+            .filter_map(|(relative_path, entry)| {
+                let entry = entry.lock().unwrap();
+                let repo = entry.as_ref()?;
+                Some(repo.to_canonical_repo(root.as_root(), relative_path.clone()))
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Default)]
@@ -153,11 +172,52 @@ impl JsonRootEntry {
 }
 
 #[derive(Debug, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", from = "JsonRepoEntryRepr")]
 struct JsonRepoEntry {
     timestamp: DateTime<Utc>,
     canonical_path: PathBuf,
+    kind: RepoKind,
+    common_dir: PathBuf,
+}
+
+/// Stored form of [`JsonRepoEntry`].
+///
+/// Caches written by older versions only have `bare`, so the kind and the git
+/// directory are derived from it.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonRepoEntryRepr {
+    timestamp: DateTime<Utc>,
+    canonical_path: PathBuf,
+    #[serde(default)]
     bare: bool,
+    #[serde(default)]
+    kind: Option<RepoKind>,
+    #[serde(default)]
+    common_dir: Option<PathBuf>,
+}
+
+impl From<JsonRepoEntryRepr> for JsonRepoEntry {
+    fn from(repr: JsonRepoEntryRepr) -> Self {
+        let kind = repr.kind.unwrap_or(if repr.bare {
+            RepoKind::Bare
+        } else {
+            RepoKind::Normal
+        });
+        let common_dir = repr.common_dir.unwrap_or_else(|| {
+            if kind.is_bare() {
+                repr.canonical_path.clone()
+            } else {
+                repr.canonical_path.join(".git")
+            }
+        });
+        Self {
+            timestamp: repr.timestamp,
+            canonical_path: repr.canonical_path,
+            kind,
+            common_dir,
+        }
+    }
 }
 
 impl JsonRepoEntry {
@@ -166,12 +226,12 @@ impl JsonRepoEntry {
     }
 
     fn to_repo(&self, root: &Root, relative_path: PathBuf) -> Repo {
-        Repo::from_relative_path(root, relative_path, self.bare)
+        Repo::from_relative_path(root, relative_path, self.kind)
     }
 
     fn to_canonical_repo(&self, root: &Root, relative_path: PathBuf) -> CanonicalRepo {
         let repo = self.to_repo(root, relative_path);
-        CanonicalRepo::new(repo, self.canonical_path.clone())
+        CanonicalRepo::new(repo, self.canonical_path.clone(), self.common_dir.clone())
     }
 }
 
@@ -196,7 +256,62 @@ impl RepoCacheEntry for RepoCacheEntryHandler {
         *entry = Some(JsonRepoEntry {
             timestamp: self.now,
             canonical_path: repo.canonical_path().to_owned(),
-            bare: repo.bare(),
+            kind: repo.kind(),
+            common_dir: repo.common_dir().to_owned(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::domain::model::path_buf_pair::PathBufPair;
+
+    #[test]
+    fn load_cache_written_before_repo_kinds() {
+        let dir = TempDir::new().unwrap();
+        let root_path = dir.path().join("root");
+        let cache_path = dir.path().join("cache.json");
+        let now = Utc::now();
+        let cache = serde_json::json!({
+            "roots": {
+                "default": {
+                    "realPath": root_path,
+                    "displayPath": root_path,
+                    "canonicalPath": root_path,
+                    "repos": {
+                        "normal": {
+                            "timestamp": now,
+                            "canonicalPath": root_path.join("normal"),
+                            "bare": false,
+                        },
+                        "bare": {
+                            "timestamp": now,
+                            "canonicalPath": root_path.join("bare"),
+                            "bare": true,
+                        },
+                    },
+                },
+            },
         });
+        std::fs::write(&cache_path, cache.to_string()).unwrap();
+
+        let repo_cache = JsonRepoCache::new();
+        repo_cache
+            .load(&PathBufPair::new(&cache_path), now, Duration::days(1))
+            .unwrap();
+        let root = CanonicalRoot::new(
+            Root::new("default".to_owned(), PathBufPair::new(&root_path)),
+            root_path.clone(),
+        );
+
+        let normal = repo_cache.entry(&root, Path::new("normal")).get().unwrap();
+        assert_eq!(normal.kind(), RepoKind::Normal);
+        assert_eq!(normal.common_dir(), root_path.join("normal/.git"));
+        let bare = repo_cache.entry(&root, Path::new("bare")).get().unwrap();
+        assert_eq!(bare.kind(), RepoKind::Bare);
+        assert_eq!(bare.common_dir(), root_path.join("bare"));
     }
 }
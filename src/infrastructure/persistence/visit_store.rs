@@ -0,0 +1,84 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    domain::{
+        model::{path_like::PathLike, visit::RepoVisits},
+        port::visit_store::VisitStore,
+    },
+    util::file,
+};
+
+#[derive(Debug)]
+pub(in crate::infrastructure) struct JsonVisitStore(Mutex<JsonVisits>);
+
+impl JsonVisitStore {
+    pub(in crate::infrastructure) fn new() -> Self {
+        Self(Mutex::new(JsonVisits::default()))
+    }
+}
+
+impl VisitStore for JsonVisitStore {
+    fn load(
+        &self,
+        path: &dyn PathLike,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let mut this = self.0.lock().unwrap();
+        *this = file::load_json("visit history", &path.as_real_path())?.unwrap_or_default();
+        Ok(())
+    }
+
+    fn record(
+        &self,
+        path: &dyn PathLike,
+        canonical_path: &Path,
+        now: DateTime<Utc>,
+        expire_before: DateTime<Utc>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let path = path.as_real_path();
+        let _lock = file::lock("visit history", &path)?;
+
+        let mut this = self.0.lock().unwrap();
+        *this = file::load_json("visit history", &path)?.unwrap_or_default();
+        this.repos
+            .retain(|_, entry| entry.last_visited >= expire_before);
+        let entry = this
+            .repos
+            .entry(canonical_path.to_owned())
+            .or_insert(JsonVisitEntry {
+                count: 0,
+                last_visited: now,
+            });
+        entry.count += 1;
+        entry.last_visited = entry.last_visited.max(now);
+        file::store_json("visit history", &path, &*this)?;
+        Ok(())
+    }
+
+    fn visits(&self) -> Vec<RepoVisits> {
+        let this = self.0.lock().unwrap();
+        this.repos
+            .iter()
+            .map(|(path, entry)| RepoVisits::new(path.clone(), entry.count, entry.last_visited))
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct JsonVisits {
+    repos: HashMap<PathBuf, JsonVisitEntry>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonVisitEntry {
+    count: u64,
+    last_visited: DateTime<Utc>,
+}
@@ -1,11 +1,15 @@
-use std::env;
+use std::{env, process::ExitCode};
 
 use clap::Parser as _;
 use color_eyre::eyre::{self, WrapErr as _, eyre};
 use tracing::level_filters::LevelFilter;
 use tracing_subscriber::EnvFilter;
 
-use crate::{app_dirs::AppDirs, application::usecase::Usecases, cli::args::Args};
+use crate::{
+    app_dirs::AppDirs,
+    application::usecase::Usecases,
+    cli::{Outcome, args::Args},
+};
 
 #[macro_use]
 mod macros;
@@ -18,18 +22,18 @@ mod infrastructure;
 mod util;
 
 const BIN_NAME: &str = env!("CARGO_BIN_NAME");
+const COMPLETE_VAR: &str = "SOUKO_COMPLETE";
 
-fn main() -> eyre::Result<()> {
+fn main() -> eyre::Result<ExitCode> {
     color_eyre::install()?;
 
-    let env_prefix = BIN_NAME.to_uppercase().replace("-", "_");
-    if let Ok(shell) = env::var(format!("{env_prefix}_COMPLETE")) {
-        cli::print_completion(BIN_NAME, &shell)?;
-        return Ok(());
+    if cli::complete::complete(BIN_NAME, COMPLETE_VAR)? {
+        return Ok(ExitCode::SUCCESS);
     }
+    let env_prefix = BIN_NAME.to_uppercase().replace("-", "_");
     if let Ok(output_dir) = env::var(format!("{env_prefix}_GENERATE_MAN_TO")) {
         cli::generate_man(&output_dir)?;
-        return Ok(());
+        return Ok(ExitCode::SUCCESS);
     }
 
     let args = Args::parse();
@@ -56,5 +60,17 @@ fn main() -> eyre::Result<()> {
     let usecases = Usecases::new(&ports);
     let app_dirs =
         AppDirs::new(BIN_NAME).wrap_err("failed to initialize application directories")?;
-    cli::dispatch(&args, usecases, app_dirs)
+    let outcome = cli::dispatch(&args, usecases, app_dirs)?;
+    Ok(exit_code(outcome))
+}
+
+fn exit_code(outcome: Outcome) -> ExitCode {
+    match outcome {
+        Outcome::Success => ExitCode::SUCCESS,
+        // Following grep.
+        Outcome::NoMatch => ExitCode::from(1),
+        Outcome::SearchFailed => ExitCode::from(2),
+        // Following fzf and skim.
+        Outcome::Cancelled => ExitCode::from(130),
+    }
 }
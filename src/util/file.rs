@@ -1,6 +1,7 @@
 use std::{
     fs::{self, File},
-    io::{self, BufReader, BufWriter, Read},
+    io::{self, BufReader, BufWriter, Read, Write as _},
+    path::PathBuf,
 };
 
 use color_eyre::eyre::{Result, WrapErr, eyre};
@@ -21,6 +22,29 @@ pub(crate) fn open(name: &str, path: &impl PathLike) -> Result<Option<File>> {
     Ok(file)
 }
 
+/// Locks the file `<path>.lock` exclusively, blocking until the lock is acquired.
+///
+/// The lock is released when the returned file is dropped. `path` itself is not
+/// locked because [`store_json`] replaces it with a new file.
+pub(crate) fn lock(name: &str, path: &impl PathLike) -> Result<File> {
+    let mut lock_path = path.as_real_path().as_os_str().to_owned();
+    lock_path.push(".lock");
+    let lock_path = PathBuf::from(lock_path);
+    if let Some(dir) = lock_path.parent() {
+        fs::create_dir_all(dir)
+            .wrap_err_with(|| format!("failed to create directory: {}", dir.display()))?;
+    }
+    let file = File::create(&lock_path).wrap_err_with(|| {
+        format!(
+            "failed to create lock file of {name}: {}",
+            lock_path.display()
+        )
+    })?;
+    file.lock()
+        .wrap_err_with(|| format!("failed to lock {name}: {}", lock_path.display()))?;
+    Ok(file)
+}
+
 pub(crate) fn load_json<T>(name: &str, path: &impl PathLike) -> Result<Option<T>>
 where
     T: for<'de> Deserialize<'de>,
@@ -39,6 +63,26 @@ where
 pub(crate) fn store_json<T>(name: &str, path: &impl PathLike, value: &T) -> Result<()>
 where
     T: Serialize,
+{
+    store(name, path, |writer| {
+        Ok(serde_json::to_writer(writer, value)?)
+    })
+}
+
+pub(crate) fn store_toml_document(
+    name: &str,
+    path: &impl PathLike,
+    doc: &DocumentMut,
+) -> Result<()> {
+    store(name, path, |writer| {
+        Ok(writer.write_all(doc.to_string().as_bytes())?)
+    })
+}
+
+/// Writes a file atomically by writing to a temporary file and renaming it.
+fn store<F>(name: &str, path: &impl PathLike, write: F) -> Result<()>
+where
+    F: FnOnce(&mut BufWriter<NamedTempFile>) -> Result<()>,
 {
     let dir = path
         .as_real_path()
@@ -51,7 +95,7 @@ where
         .wrap_err_with(|| format!("failed to create temporary file in {}", dir.display(),))?;
     let temp_path = file.path().to_owned();
     let mut writer = BufWriter::new(file);
-    serde_json::to_writer(&mut writer, value).wrap_err_with(|| {
+    write(&mut writer).wrap_err_with(|| {
         format!(
             "failed to write {name} to temporary file: {}",
             temp_path.display()
@@ -80,16 +124,3 @@ pub(crate) fn load_toml_document(name: &str, path: &impl PathLike) -> Result<Opt
 
     Ok(Some(doc))
 }
-
-pub(crate) fn load_toml<T>(name: &str, path: &impl PathLike) -> Result<Option<T>>
-where
-    T: for<'de> Deserialize<'de>,
-{
-    let doc = load_toml_document(name, path)?;
-    let value = match doc {
-        Some(toml) => toml_edit::de::from_document(toml)
-            .wrap_err_with(|| format!("failed to parse {name}: {}", path.display()))?,
-        None => None,
-    };
-    Ok(value)
-}
@@ -0,0 +1,266 @@
+use std::path::Path;
+
+use assert_cmd::prelude::*;
+use assert_fs::{TempDir, prelude::*};
+use predicates::prelude::*;
+
+mod common;
+
+#[test]
+fn clone_explain_shows_matched_route() {
+    let home = TempDir::new().unwrap();
+    let config = home.child("config.toml");
+    config
+        .write_str(
+            r#"
+[[root]]
+name = "work"
+path = "work"
+
+[[route]]
+root = "work"
+host = "git.corp.example"
+"#,
+        )
+        .unwrap();
+    let config_path = config.path().to_str().unwrap();
+
+    common::souko_cmd(home.path())
+        .args([
+            "--config",
+            config_path,
+            "clone",
+            "--explain",
+            "https://git.corp.example/team/app.git",
+        ])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("root: work (").and(predicate::str::contains(
+                r#"reason: matched route #1 (host = "git.corp.example")"#,
+            )),
+        );
+
+    common::souko_cmd(home.path())
+        .args(["--config", config_path, "clone", "--explain", "owner/repo"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("root: default (")
+                .and(predicate::str::contains("no route matched")),
+        );
+
+    common::souko_cmd(home.path())
+        .args([
+            "--config",
+            config_path,
+            "clone",
+            "--explain",
+            "--root",
+            "default",
+            "https://git.corp.example/team/app.git",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("reason: specified by `--root`"));
+
+    home.child("work").assert(predicate::path::missing());
+}
+
+#[test]
+fn invalid_route_only_breaks_root_selection() {
+    let home = TempDir::new().unwrap();
+    let config = home.child("config.toml");
+    config
+        .write_str(
+            r#"
+[[route]]
+root = "missing"
+host = "example.com"
+"#,
+        )
+        .unwrap();
+    let config_path = config.path().to_str().unwrap();
+
+    common::souko_cmd(home.path())
+        .args(["--config", config_path, "list"])
+        .assert()
+        .success();
+
+    common::souko_cmd(home.path())
+        .args(["--config", config_path, "clone", "--explain", "owner/repo"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "root `missing` not found in config file",
+        ));
+}
+
+#[test]
+fn clone_explain_shows_remotes() {
+    let home = TempDir::new().unwrap();
+    let config = home.child("config.toml");
+    config
+        .write_str(
+            r#"
+[clone]
+push_url_template = "ssh://git@{host}/{path}.git"
+"#,
+        )
+        .unwrap();
+    let config_path = config.path().to_str().unwrap();
+
+    common::souko_cmd(home.path())
+        .args([
+            "--config",
+            config_path,
+            "clone",
+            "--explain",
+            "--upstream",
+            "gifnksm/souko",
+            "--remote",
+            "mirror=gl:gifnksm/souko",
+            "someone/souko",
+        ])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains(
+                "remote: origin https://github.com/someone/souko.git (push: ssh://git@github.com/someone/souko.git)\n",
+            )
+            .and(predicate::str::contains(
+                "remote: upstream https://github.com/gifnksm/souko.git\n",
+            ))
+            .and(predicate::str::contains(
+                "remote: mirror https://gitlab.com/gifnksm/souko.git\n",
+            )),
+        );
+
+    common::souko_cmd(home.path())
+        .args([
+            "clone",
+            "--explain",
+            "--upstream",
+            "gifnksm/souko",
+            "--remote",
+            "upstream=gl:gifnksm/souko",
+            "someone/souko",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "remote `upstream` is specified more than once",
+        ));
+}
+
+#[test]
+fn clone_explain_shows_custom_scheme_remotes() {
+    let home = TempDir::new().unwrap();
+    let config = home.child("config.toml");
+    config
+        .write_str(
+            r#"
+[query.custom_scheme]
+fork = "https://github.com/someone/{repo}.git"
+
+[query.custom_scheme_remote.fork]
+upstream = "gh:{path}"
+mirror = "gl:{path}"
+"#,
+        )
+        .unwrap();
+    let config_path = config.path().to_str().unwrap();
+
+    common::souko_cmd(home.path())
+        .args([
+            "--config",
+            config_path,
+            "clone",
+            "--explain",
+            "--remote",
+            "mirror=cb:gifnksm/souko",
+            "fork:gifnksm/souko",
+        ])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("remote: origin https://github.com/someone/souko.git\n")
+                .and(predicate::str::contains(
+                    "remote: upstream https://github.com/gifnksm/souko.git\n",
+                ))
+                .and(predicate::str::contains(
+                    "remote: mirror https://codeberg.org/gifnksm/souko.git\n",
+                )),
+        );
+}
+
+#[cfg(unix)]
+#[test]
+fn clone_local_path_places_repo_under_local_prefix() {
+    let home = TempDir::new().unwrap();
+    let home_path = dunce::canonicalize(home.path()).unwrap();
+
+    let upstream = home.child("src/upstream");
+    upstream.create_dir_all().unwrap();
+    common::init_repo_with_commit(upstream.path());
+    let fork = home.child("src/fork");
+    fork.create_dir_all().unwrap();
+    common::init_repo_with_commit(fork.path());
+
+    common::souko_cmd(home.path())
+        .current_dir(home.child("src").path())
+        .args(["clone", "--upstream", "./upstream", "./fork"])
+        .assert()
+        .success();
+
+    let relative = home_path.join("src/fork");
+    let relative = relative.strip_prefix("/").unwrap();
+    let cloned = common::data_local_dir(&home)
+        .child("root/local")
+        .child(relative);
+    cloned.child(".git").assert(predicate::path::is_dir());
+
+    let repo = git2::Repository::open(cloned.path()).unwrap();
+    let origin = repo.find_remote("origin").unwrap();
+    assert_eq!(
+        origin.url().unwrap(),
+        format!("file://{}", home_path.join("src/fork").display())
+    );
+    let upstream = repo.find_remote("upstream").unwrap();
+    assert_eq!(
+        upstream.url().unwrap(),
+        format!("file://{}", home_path.join("src/upstream").display())
+    );
+}
+
+#[test]
+fn clone_explain_resolves_ssh_config_host_alias_for_path() {
+    let home = TempDir::new().unwrap();
+    home.child(".ssh/config")
+        .write_str("Host work-gh\n    HostName github.com\n")
+        .unwrap();
+    let config = home.child("config.toml");
+    config
+        .write_str("[query]\nuse_ssh_config = true\n")
+        .unwrap();
+    let config_path = config.path().to_str().unwrap();
+
+    common::souko_cmd(home.path())
+        .args([
+            "--config",
+            config_path,
+            "clone",
+            "--explain",
+            "git@work-gh:corp/app.git",
+        ])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("url: ssh://git@work-gh/corp/app.git\n").and(
+                predicate::str::contains(format!(
+                    "path: {}\n",
+                    Path::new("github.com/corp/app").display()
+                )),
+            ),
+        );
+}
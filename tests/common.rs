@@ -1,6 +1,13 @@
-use std::{path::Path, process::Command};
+// Each integration test crate uses only some of these helpers.
+#![allow(dead_code)]
+
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 use assert_cmd::cargo::CommandCargoExt;
+use assert_fs::{TempDir, fixture::ChildPath, prelude::*};
 
 pub fn souko_cmd(home_dir: &Path) -> Command {
     let mut cmd = Command::cargo_bin("souko").unwrap();
@@ -15,3 +22,63 @@ pub fn souko_cmd(home_dir: &Path) -> Command {
     ]);
     cmd
 }
+
+pub fn data_local_dir(home_dir: &impl PathChild) -> ChildPath {
+    if cfg!(target_os = "linux") {
+        return home_dir.child(".local/share/souko");
+    }
+    if cfg!(target_os = "macos") {
+        return home_dir.child("Library/Application Support/souko");
+    }
+    if cfg!(target_os = "windows") {
+        return home_dir.child(r"AppData\Local\souko\data");
+    }
+    panic!("unsupported platform");
+}
+
+pub fn config_dir(home_dir: &impl PathChild) -> ChildPath {
+    if cfg!(target_os = "linux") {
+        return home_dir.child(".config/souko");
+    }
+    if cfg!(target_os = "macos") {
+        return home_dir.child("Library/Application Support/souko");
+    }
+    if cfg!(target_os = "windows") {
+        return home_dir.child(r"AppData\Roaming\souko\config");
+    }
+    panic!("unsupported platform");
+}
+
+/// Writes the configuration file at its default location.
+pub fn write_config(home: &TempDir, body: &str) -> ChildPath {
+    let config_dir = config_dir(home);
+    config_dir.create_dir_all().unwrap();
+    let config = config_dir.child("config.toml");
+    config.write_str(body).unwrap();
+    config
+}
+
+/// Creates empty repositories under the default root.
+pub fn init_repos(home: &TempDir, relative_paths: &[&str]) {
+    for relative_path in relative_paths {
+        let repo = data_local_dir(home).child("root").child(relative_path);
+        repo.create_dir_all().unwrap();
+        git2::Repository::init(repo.path()).unwrap();
+    }
+}
+
+/// Creates a repository with an empty initial commit.
+pub fn init_repo_with_commit(path: &Path) -> git2::Repository {
+    let repo = git2::Repository::init(path).unwrap();
+    let sig = git2::Signature::now("souko", "souko@example.com").unwrap();
+    let tree_id = repo.index().unwrap().write_tree().unwrap();
+    let tree = repo.find_tree(tree_id).unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "initial", &tree, &[])
+        .unwrap();
+    drop(tree);
+    repo
+}
+
+pub fn canonical(path: &Path) -> PathBuf {
+    dunce::canonicalize(path).unwrap()
+}
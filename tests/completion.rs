@@ -0,0 +1,151 @@
+use assert_cmd::prelude::*;
+use assert_fs::{TempDir, fixture::ChildPath, prelude::*};
+use predicates::prelude::*;
+
+mod common;
+
+/// Returns the candidates completing the last element of `args`.
+fn complete(home: &TempDir, args: &[&str]) -> Vec<String> {
+    complete_with_config(home, None, args)
+}
+
+fn complete_with_config(home: &TempDir, config: Option<&ChildPath>, args: &[&str]) -> Vec<String> {
+    let mut cmd = common::souko_cmd(home.path());
+    if let Some(config) = config {
+        cmd.env("SOUKO_CONFIG", config.path());
+    }
+    let output = cmd
+        .env("SOUKO_COMPLETE", "fish")
+        .args(["--", "souko"])
+        .args(args)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| line.split('\t').next().unwrap().to_owned())
+        .collect()
+}
+
+#[test]
+fn registration_script_is_printed_for_each_shell() {
+    let home = TempDir::new().unwrap();
+
+    for shell in ["bash", "elvish", "fish", "powershell", "zsh", "nushell"] {
+        common::souko_cmd(home.path())
+            .env("SOUKO_COMPLETE", shell)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("souko"));
+    }
+}
+
+#[test]
+fn complete_root_names_from_config() {
+    let home = TempDir::new().unwrap();
+    let config = home.child("config.toml");
+    config
+        .write_str(
+            r#"
+[[root]]
+name = "work"
+path = "~/work"
+
+[[root]]
+name = "oss"
+path = "~/oss"
+"#,
+        )
+        .unwrap();
+
+    let candidates = complete_with_config(&home, Some(&config), &["list", "--root", ""]);
+    for name in ["default", "oss", "work"] {
+        assert!(candidates.iter().any(|c| c == name), "{candidates:?}");
+    }
+    assert_eq!(
+        complete_with_config(&home, Some(&config), &["list", "--root", "w"]),
+        ["work"]
+    );
+
+    // `--config` on the command line being completed is used
+    let config_path = config.path().to_str().unwrap();
+    assert_eq!(
+        complete(&home, &["--config", config_path, "list", "--root", "w"]),
+        ["work"]
+    );
+    assert_eq!(
+        complete(
+            &home,
+            &[&format!("--config={config_path}"), "clone", "--root", "o"]
+        ),
+        ["oss"]
+    );
+}
+
+#[test]
+fn complete_repositories_with_default_scheme() {
+    let home = TempDir::new().unwrap();
+    common::init_repos(
+        &home,
+        &["github.com/owner/repo", "gitlab.com/group/project"],
+    );
+
+    let candidates = complete(&home, &["path", ""]);
+    assert!(
+        candidates.iter().any(|c| c == "owner/repo"),
+        "{candidates:?}"
+    );
+    assert!(candidates.iter().any(|c| c == "gh:"), "{candidates:?}");
+    // Not reachable without a scheme prefix.
+    assert!(!candidates.iter().any(|c| c.contains("project")));
+
+    assert_eq!(complete(&home, &["path", "own"]), ["owner/repo"]);
+}
+
+#[test]
+fn complete_owners_and_repositories_after_scheme() {
+    let home = TempDir::new().unwrap();
+    common::init_repos(
+        &home,
+        &[
+            "github.com/alice/one",
+            "github.com/alice/two",
+            "github.com/bob/three",
+            "gitlab.com/group/project",
+        ],
+    );
+
+    assert_eq!(complete(&home, &["clone", "gh:"]), ["gh:alice/", "gh:bob/"]);
+    assert_eq!(
+        complete(&home, &["clone", "gh:alice/"]),
+        ["gh:alice/one", "gh:alice/two"]
+    );
+    assert_eq!(complete(&home, &["worktree", "list", "gl:"]), ["gl:group/"]);
+    assert!(complete(&home, &["clone", "unknown:"]).is_empty());
+}
+
+#[test]
+fn complete_repositories_from_repo_cache() {
+    let home = TempDir::new().unwrap();
+    common::init_repos(&home, &["github.com/alice/one"]);
+    common::souko_cmd(home.path())
+        .arg("list")
+        .assert()
+        .success();
+
+    // Repositories cloned after the last listing are not walked.
+    common::init_repos(&home, &["github.com/alice/two"]);
+    assert_eq!(complete(&home, &["path", "alice/"]), ["alice/one"]);
+
+    common::souko_cmd(home.path())
+        .arg("list")
+        .assert()
+        .success();
+    assert_eq!(
+        complete(&home, &["path", "alice/"]),
+        ["alice/one", "alice/two"]
+    );
+}
@@ -0,0 +1,185 @@
+use assert_cmd::prelude::*;
+use assert_fs::{TempDir, prelude::*};
+use predicates::prelude::*;
+
+mod common;
+
+#[test]
+fn config_path_prints_default_location() {
+    let home = TempDir::new().unwrap();
+    let config = common::config_dir(&home).child("config.toml");
+
+    common::souko_cmd(home.path())
+        .args(["config", "path"])
+        .assert()
+        .success()
+        .stdout(format!("{}\n", config.path().display()));
+}
+
+#[test]
+fn config_init_writes_commented_template_once() {
+    let home = TempDir::new().unwrap();
+    let config = common::config_dir(&home).child("config.toml");
+
+    common::souko_cmd(home.path())
+        .args(["config", "init"])
+        .assert()
+        .success();
+    config.assert(
+        predicate::str::contains("# [[root]]")
+            .and(predicate::str::contains("# default_scheme = \"gh\"")),
+    );
+
+    common::souko_cmd(home.path())
+        .args(["config", "init"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+    common::souko_cmd(home.path())
+        .args(["config", "init", "--force"])
+        .assert()
+        .success();
+
+    // The template only has comments, so the defaults are used.
+    common::souko_cmd(home.path())
+        .args(["config", "get", "query.default_scheme"])
+        .assert()
+        .success()
+        .stdout("gh\n");
+}
+
+#[test]
+fn config_set_keeps_comments_and_formatting() {
+    let home = TempDir::new().unwrap();
+    let config = common::write_config(
+        &home,
+        r#"# my roots
+[[root]]
+name = "default"
+path = "~/src" # main
+
+[query]
+default_scheme = "gh"   # aligned
+"#,
+    );
+
+    for (key, value) in [
+        ("root[0].path", "~/repos"),
+        ("query.default_scheme", "gl"),
+        ("query.normalize.lowercase_host", "true"),
+    ] {
+        common::souko_cmd(home.path())
+            .args(["config", "set", key, value])
+            .assert()
+            .success();
+    }
+    config.assert(
+        r#"# my roots
+[[root]]
+name = "default"
+path = "~/repos" # main
+
+[query]
+default_scheme = "gl"   # aligned
+
+[query.normalize]
+lowercase_host = true
+"#,
+    );
+
+    common::souko_cmd(home.path())
+        .args(["config", "get", "query.normalize.lowercase_host"])
+        .assert()
+        .success()
+        .stdout("true\n");
+    common::souko_cmd(home.path())
+        .args(["config", "get", "query.normalize"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "query.normalize.lowercase_host = true\nquery.normalize.lowercase_path = false\n",
+        ));
+}
+
+#[test]
+fn config_set_rejects_unknown_keys_and_invalid_values() {
+    let home = TempDir::new().unwrap();
+    let body = "[query]\nuse_ssh_config = false\n";
+    let config = common::write_config(&home, body);
+
+    common::souko_cmd(home.path())
+        .args(["config", "set", "query.use_ssh", "true"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown key `query.use_ssh`"));
+    common::souko_cmd(home.path())
+        .args(["config", "set", "query.use_ssh_config", "yes"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected a boolean"));
+    common::souko_cmd(home.path())
+        .args(["config", "set", "query.default_scheme", "123"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid type: integer `123`"));
+    config.assert(body);
+}
+
+#[test]
+fn config_show_prints_effective_values_with_sources() {
+    let home = TempDir::new().unwrap();
+    common::write_config(&home, "[query]\ndefault_scheme = \"gl\"\n");
+
+    common::souko_cmd(home.path())
+        .args(["config", "show"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("# config: ")
+                .and(predicate::str::contains(
+                    "query.default_scheme = \"gl\"  # config file",
+                ))
+                .and(predicate::str::contains(
+                    "root[0].name = \"default\"  # default",
+                ))
+                .and(predicate::str::contains(
+                    "worktree.path_template = \"{repo_relative_path}@{branch_slug}\"  # default",
+                )),
+        );
+}
+
+#[test]
+fn config_root_add_and_remove() {
+    let home = TempDir::new().unwrap();
+    let body = "# query\n[query]\ndefault_scheme = \"gh\"\n";
+    let config = common::write_config(&home, body);
+
+    common::souko_cmd(home.path())
+        .args(["config", "root", "add", "work", "~/work"])
+        .assert()
+        .success();
+    config.assert(format!(
+        "{body}\n[[root]]\nname = \"work\"\npath = \"~/work\"\n"
+    ));
+    common::souko_cmd(home.path())
+        .args(["config", "root", "add", "work", "~/other"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("root `work` already exists"));
+
+    common::souko_cmd(home.path())
+        .args(["list", "--root", "work"])
+        .assert()
+        .success();
+
+    common::souko_cmd(home.path())
+        .args(["config", "root", "remove", "work"])
+        .assert()
+        .success();
+    config.assert(body);
+    common::souko_cmd(home.path())
+        .args(["config", "root", "remove", "work"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("root `work` not found"));
+}
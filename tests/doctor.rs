@@ -0,0 +1,220 @@
+use assert_cmd::prelude::*;
+use assert_fs::{TempDir, prelude::*};
+use predicates::prelude::*;
+
+mod common;
+
+#[test]
+fn doctor_succeeds_with_default_configuration() {
+    let home = TempDir::new().unwrap();
+
+    common::souko_cmd(home.path())
+        .arg("doctor")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("not found, the default configuration is used")
+                .and(predicate::str::contains("`default`"))
+                .and(predicate::str::contains("0 errors")),
+        );
+}
+
+#[test]
+fn doctor_reports_every_unknown_field_and_fix_keeps_them() {
+    let home = TempDir::new().unwrap();
+    let config = common::write_config(
+        &home,
+        r#"
+typo = 1
+
+[[root]]
+name = "default"
+nmae = "typo"
+
+[worktree]
+# kept
+path_template = "{repo_relative_path}"
+extra = true
+"#,
+    );
+
+    common::souko_cmd(home.path())
+        .arg("doctor")
+        .assert()
+        .failure()
+        .stdout(
+            predicate::str::contains("unknown field `typo`")
+                .and(predicate::str::contains("unknown field `root[0].nmae`"))
+                .and(predicate::str::contains("did you mean `root[0].name`?"))
+                .and(predicate::str::contains("unknown field `worktree.extra`"))
+                .and(predicate::str::contains("Repositories"))
+                .and(predicate::str::contains("skipped").not()),
+        );
+
+    let before = std::fs::read_to_string(config.path()).unwrap();
+    common::souko_cmd(home.path())
+        .args(["doctor", "--fix"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("unknown field `root[0].nmae`"));
+    assert_eq!(std::fs::read_to_string(config.path()).unwrap(), before);
+}
+
+#[test]
+fn doctor_reports_missing_and_nested_roots() {
+    let home = TempDir::new().unwrap();
+    let work = home.child("work");
+    common::write_config(
+        &home,
+        &format!(
+            r#"
+[[root]]
+name = "default"
+path = "{}"
+
+[[root]]
+name = "nested"
+path = "{}"
+"#,
+            work.path().display(),
+            work.child("nested").path().display(),
+        )
+        .replace('\\', "\\\\"),
+    );
+
+    common::souko_cmd(home.path())
+        .arg("doctor")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("does not exist"));
+
+    common::souko_cmd(home.path())
+        .args(["doctor", "--fix"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("`nested`: created"));
+    work.child("nested").assert(predicate::path::is_dir());
+
+    common::souko_cmd(home.path())
+        .arg("doctor")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "root `nested` is inside root `default`",
+        ));
+}
+
+#[test]
+fn doctor_reports_custom_schemes_failing_to_expand() {
+    let home = TempDir::new().unwrap();
+    common::write_config(
+        &home,
+        r#"
+[query.custom_scheme]
+good = "https://example.com/{path}.git"
+bad = "not a url {path}"
+"#,
+    );
+
+    common::souko_cmd(home.path())
+        .arg("doctor")
+        .assert()
+        .failure()
+        .stdout(
+            predicate::str::contains(
+                "custom scheme `good`: `good:owner/repo` -> example.com/owner/repo",
+            )
+            .and(predicate::str::contains(
+                "custom scheme `bad` fails to expand `bad:owner/repo`",
+            )),
+        );
+}
+
+#[test]
+fn doctor_resets_unreadable_repository_cache() {
+    let home = TempDir::new().unwrap();
+    let cache = home.child("repos.json");
+    cache.write_str("{broken").unwrap();
+    let cache_arg = format!("--repo-cache={}", cache.path().display());
+
+    common::souko_cmd(home.path())
+        .args([&cache_arg, "doctor"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("failed to read repository cache"));
+
+    cache.write_str("{broken").unwrap();
+    common::souko_cmd(home.path())
+        .args([&cache_arg, "doctor", "--fix"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("reset the unreadable cache"));
+
+    common::souko_cmd(home.path())
+        .args([&cache_arg, "doctor"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("failed to read").not());
+}
+
+#[test]
+fn doctor_reports_repositories_not_matching_origin() {
+    let home = TempDir::new().unwrap();
+    let root = common::data_local_dir(&home).child("root");
+    for (path, origin) in [
+        ("github.com/old/name", "https://github.com/new/name.git"),
+        ("github.com/same/name", "https://github.com/same/name.git"),
+    ] {
+        let dir = root.child(path);
+        dir.create_dir_all().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        repo.remote("origin", origin).unwrap();
+    }
+
+    common::souko_cmd(home.path())
+        .arg("doctor")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains(
+                "`github.com/old/name` in root `default` does not match its origin \
+                 `https://github.com/new/name.git`, expected `github.com/new/name`",
+            )
+            .and(predicate::str::contains("run `souko relocate` to move it"))
+            .and(predicate::str::contains("same/name").not())
+            .and(predicate::str::contains("2 repositories checked")),
+        );
+}
+
+#[test]
+fn doctor_checks_repositories_in_their_root_without_routes() {
+    let home = TempDir::new().unwrap();
+    let work = home.child("work");
+    let repo = work.child("github.com/same/name");
+    repo.create_dir_all().unwrap();
+    git2::Repository::init(repo.path())
+        .unwrap()
+        .remote("origin", "https://github.com/same/name.git")
+        .unwrap();
+    common::write_config(
+        &home,
+        &format!(
+            r#"
+[[root]]
+name = "work"
+path = {:?}
+"#,
+            work.path().to_str().unwrap()
+        ),
+    );
+
+    common::souko_cmd(home.path())
+        .arg("doctor")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("1 repositories checked")
+                .and(predicate::str::contains("does not match").not())
+                .and(predicate::str::contains("souko relocate").not()),
+        );
+}
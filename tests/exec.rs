@@ -0,0 +1,93 @@
+#![cfg(unix)]
+
+use assert_cmd::prelude::*;
+use assert_fs::TempDir;
+use predicates::prelude::*;
+
+mod common;
+
+#[test]
+fn exec_runs_command_in_each_repository_with_prefix() {
+    let home = TempDir::new().unwrap();
+    common::init_repos(&home, &["github.com/a/x", "github.com/b/y"]);
+
+    common::souko_cmd(home.path())
+        .args(["exec", "--", "sh", "-c", "basename \"$PWD\""])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("default:github.com/a/x: x\n")
+                .and(predicate::str::contains("default:github.com/b/y: y\n")),
+        )
+        .stderr(predicate::str::contains("2 succeeded, 0 failed"));
+}
+
+#[test]
+fn exec_exports_template_variables() {
+    let home = TempDir::new().unwrap();
+    common::init_repos(&home, &["github.com/a/x"]);
+
+    common::souko_cmd(home.path())
+        .args([
+            "foreach",
+            "--output",
+            "group",
+            "--",
+            "sh",
+            "-c",
+            "echo \"$SOUKO_ROOT_NAME $SOUKO_REPO_RELATIVE_PATH $SOUKO_REPO_KIND\"",
+        ])
+        .assert()
+        .success()
+        .stdout("==> default:github.com/a/x <==\ndefault github.com/a/x normal\n");
+}
+
+#[test]
+fn exec_reports_failures_and_continues() {
+    let home = TempDir::new().unwrap();
+    common::init_repos(&home, &["github.com/a/x", "github.com/b/y"]);
+
+    common::souko_cmd(home.path())
+        .args([
+            "exec",
+            "-j",
+            "2",
+            "--",
+            "sh",
+            "-c",
+            "test \"${PWD##*/}\" = y",
+        ])
+        .assert()
+        .failure()
+        .stderr(
+            predicate::str::contains("command failed in `default:github.com/a/x`")
+                .and(predicate::str::contains("1 succeeded, 1 failed"))
+                .and(predicate::str::contains("command failed in 1 repositories")),
+        );
+}
+
+#[test]
+fn exec_fail_fast_skips_remaining_repositories() {
+    let home = TempDir::new().unwrap();
+    common::init_repos(
+        &home,
+        &["github.com/a/x", "github.com/b/y", "github.com/c/z"],
+    );
+
+    common::souko_cmd(home.path())
+        .args(["exec", "--fail-fast", "--", "sh", "-c", "echo ran; exit 1"])
+        .assert()
+        .failure()
+        .stdout("default:github.com/a/x: ran\n")
+        .stderr(predicate::str::contains("0 succeeded, 1 failed"));
+}
+
+#[test]
+fn exec_requires_command() {
+    let home = TempDir::new().unwrap();
+
+    common::souko_cmd(home.path())
+        .arg("exec")
+        .assert()
+        .failure();
+}
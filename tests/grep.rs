@@ -0,0 +1,155 @@
+use std::path::Path;
+
+use assert_cmd::prelude::*;
+use assert_fs::{TempDir, fixture::ChildPath, prelude::*};
+use predicates::prelude::*;
+use serde_json::Value;
+
+mod common;
+
+/// Creates a repository with `committed` files committed and `untracked` files
+/// left in the working tree.
+fn init_repo(home: &TempDir, relative_path: &str, committed: &[(&str, &str)]) -> ChildPath {
+    let dir = common::data_local_dir(home)
+        .child("root")
+        .child(relative_path);
+    dir.create_dir_all().unwrap();
+    let repo = git2::Repository::init(dir.path()).unwrap();
+    let mut index = repo.index().unwrap();
+    for (path, content) in committed {
+        dir.child(path).write_str(content).unwrap();
+        index.add_path(Path::new(path)).unwrap();
+    }
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = git2::Signature::now("souko", "souko@example.com").unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+        .unwrap();
+    dir
+}
+
+fn stdout(home: &TempDir, args: &[&str]) -> String {
+    let output = common::souko_cmd(home.path())
+        .args(args)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn grep_prints_matching_lines_of_tracked_files() {
+    let home = TempDir::new().unwrap();
+    let repo = init_repo(
+        &home,
+        "github.com/a/x",
+        &[
+            ("src/lib.rs", "fn foo() {}\nfn bar() {}\n"),
+            ("README", "foo\n"),
+        ],
+    );
+    init_repo(&home, "github.com/b/y", &[("main.rs", "let foo = 1;\n")]);
+    repo.child("untracked.txt").write_str("foo\n").unwrap();
+
+    assert_eq!(
+        stdout(&home, &["grep", "fo+"]),
+        "github.com/a/x/README:1:foo\n\
+         github.com/a/x/src/lib.rs:1:fn foo() {}\n\
+         github.com/b/y/main.rs:1:let foo = 1;\n"
+    );
+}
+
+#[test]
+fn grep_searches_working_tree_or_head() {
+    let home = TempDir::new().unwrap();
+    let repo = init_repo(&home, "github.com/a/x", &[("a.txt", "old\n")]);
+    repo.child("a.txt").write_str("new\n").unwrap();
+
+    assert_eq!(
+        stdout(&home, &["grep", "new"]),
+        "github.com/a/x/a.txt:1:new\n"
+    );
+    assert_eq!(
+        stdout(&home, &["grep", "--head", "old"]),
+        "github.com/a/x/a.txt:1:old\n"
+    );
+}
+
+#[test]
+fn grep_untracked_respects_gitignore() {
+    let home = TempDir::new().unwrap();
+    let repo = init_repo(&home, "github.com/a/x", &[(".gitignore", "*.log\n")]);
+    repo.child("notes.txt").write_str("needle\n").unwrap();
+    repo.child("debug.log").write_str("needle\n").unwrap();
+
+    assert_eq!(
+        stdout(&home, &["grep", "--untracked", "needle"]),
+        "github.com/a/x/notes.txt:1:needle\n"
+    );
+}
+
+#[test]
+fn grep_group_and_json_output() {
+    let home = TempDir::new().unwrap();
+    init_repo(
+        &home,
+        "github.com/a/x",
+        &[("a.txt", "Foo\nbar\nfoo\n"), ("b.txt", "foo\n")],
+    );
+
+    assert_eq!(
+        stdout(&home, &["grep", "-i", "--format", "group", "foo"]),
+        "github.com/a/x/a.txt\n1:Foo\n3:foo\n\ngithub.com/a/x/b.txt\n1:foo\n"
+    );
+
+    let json: Value = serde_json::from_str(&stdout(&home, &["grep", "--json", "bar"])).unwrap();
+    let repo = &json["repos"][0];
+    assert_eq!(repo["rootName"], "default");
+    assert_eq!(repo["relativePath"], "github.com/a/x");
+    assert_eq!(repo["files"][0]["path"], "a.txt");
+    assert_eq!(repo["files"][0]["matches"][0]["lineNumber"], 2);
+    assert_eq!(repo["files"][0]["matches"][0]["line"], "bar");
+}
+
+#[test]
+fn grep_fails_without_matches() {
+    let home = TempDir::new().unwrap();
+    init_repo(&home, "github.com/a/x", &[("a.txt", "foo\n")]);
+
+    common::souko_cmd(home.path())
+        .args(["grep", "qux"])
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr("");
+}
+
+#[test]
+fn grep_fails_with_status_2_if_any_repo_cannot_be_searched() {
+    let home = TempDir::new().unwrap();
+    init_repo(&home, "github.com/a/x", &[("a.txt", "foo\n")]);
+    let broken = init_repo(&home, "github.com/a/y", &[("a.txt", "foo\n")]);
+    broken.child(".git/index").write_str("broken").unwrap();
+
+    common::souko_cmd(home.path())
+        .args(["grep", "foo"])
+        .assert()
+        .code(2)
+        .stdout("github.com/a/x/a.txt:1:foo\n")
+        .stderr(predicate::str::contains(
+            "failed to search `github.com/a/y`",
+        ));
+}
+
+#[test]
+fn grep_rejects_invalid_pattern() {
+    let home = TempDir::new().unwrap();
+
+    common::souko_cmd(home.path())
+        .args(["grep", "("])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid pattern"));
+}
@@ -0,0 +1,155 @@
+use assert_cmd::prelude::*;
+use assert_fs::{TempDir, prelude::*};
+use predicates::prelude::*;
+use serde_json::Value;
+
+mod common;
+
+fn canonical_path(home: &TempDir, relative_path: &str) -> String {
+    let path = common::data_local_dir(home)
+        .child("root")
+        .child(relative_path);
+    common::canonical(path.path()).to_str().unwrap().to_owned()
+}
+
+fn stdout_lines(home: &TempDir, args: &[&str]) -> Vec<String> {
+    let output = common::souko_cmd(home.path())
+        .args(args)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(str::to_owned)
+        .collect()
+}
+
+#[test]
+fn path_prints_repository_path_and_records_visit() {
+    let home = TempDir::new().unwrap();
+    common::init_repos(&home, &["github.com/owner/repo"]);
+
+    for _ in 0..2 {
+        assert_eq!(
+            stdout_lines(&home, &["path", "owner/repo"]),
+            [canonical_path(&home, "github.com/owner/repo")]
+        );
+    }
+
+    let visits: Value = serde_json::from_slice(
+        &std::fs::read(common::data_local_dir(&home).child("visits.json").path()).unwrap(),
+    )
+    .unwrap();
+    let entry = &visits["repos"][canonical_path(&home, "github.com/owner/repo")];
+    assert_eq!(entry["count"], 2);
+}
+
+#[test]
+fn path_fails_for_missing_repository() {
+    let home = TempDir::new().unwrap();
+
+    common::souko_cmd(home.path())
+        .args(["path", "owner/missing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("repository not found"));
+    common::data_local_dir(&home)
+        .child("visits.json")
+        .assert(predicate::path::missing());
+}
+
+#[test]
+fn jump_prefers_frequently_visited_repository() {
+    let home = TempDir::new().unwrap();
+    common::init_repos(
+        &home,
+        &["github.com/alice/souko", "github.com/bob/souko-fork"],
+    );
+
+    // Without any visit, the shortest matching path wins.
+    assert_eq!(
+        stdout_lines(&home, &["jump", "souko"]),
+        [canonical_path(&home, "github.com/alice/souko")]
+    );
+
+    stdout_lines(&home, &["path", "bob/souko-fork"]);
+    assert_eq!(
+        stdout_lines(&home, &["jump", "souko"]),
+        [canonical_path(&home, "github.com/bob/souko-fork")]
+    );
+    assert_eq!(
+        stdout_lines(&home, &["jump", "alice", "sou"]),
+        [canonical_path(&home, "github.com/alice/souko")]
+    );
+}
+
+#[test]
+fn jump_requires_last_keyword_in_last_component() {
+    let home = TempDir::new().unwrap();
+    common::init_repos(&home, &["github.com/alice/souko"]);
+
+    common::souko_cmd(home.path())
+        .args(["jump", "alice"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no repository matches `alice`"));
+}
+
+#[test]
+fn list_sort_frecency_orders_visited_repositories_first() {
+    let home = TempDir::new().unwrap();
+    common::init_repos(
+        &home,
+        &[
+            "github.com/owner/a",
+            "github.com/owner/b",
+            "github.com/owner/c",
+        ],
+    );
+
+    stdout_lines(&home, &["path", "owner/c"]);
+    stdout_lines(&home, &["path", "owner/c"]);
+    stdout_lines(&home, &["path", "owner/b"]);
+
+    assert_eq!(
+        stdout_lines(
+            &home,
+            &[
+                "list",
+                "--sort",
+                "frecency",
+                "--template",
+                "{repo_relative_path}"
+            ]
+        ),
+        [
+            "github.com/owner/c",
+            "github.com/owner/b",
+            "github.com/owner/a"
+        ]
+    );
+    assert_eq!(
+        stdout_lines(&home, &["list", "--template", "{repo_relative_path}"]),
+        [
+            "github.com/owner/a",
+            "github.com/owner/b",
+            "github.com/owner/c"
+        ]
+    );
+}
+
+#[test]
+fn list_sort_frecency_conflicts_with_group_worktrees() {
+    let home = TempDir::new().unwrap();
+
+    common::souko_cmd(home.path())
+        .args(["list", "--sort", "frecency", "--group-worktrees"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "`--group-worktrees` cannot be used with `--sort frecency`",
+        ));
+}
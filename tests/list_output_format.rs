@@ -81,3 +81,238 @@ fn list_json_output_is_valid_json_after_template_addition() {
     let value: Value = serde_json::from_slice(&output).unwrap();
     assert!(value.get("roots").is_some());
 }
+
+#[test]
+fn list_table_output_aligns_columns() {
+    let home = TempDir::new().unwrap();
+    common::init_repos(
+        &home,
+        &["example.com/a/short", "example.com/owner/long-repository"],
+    );
+
+    let output = common::souko_cmd(home.path())
+        .args(["list", "--format", "table"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let output = String::from_utf8(output).unwrap();
+    let lines = output.lines().collect::<Vec<_>>();
+    let sep = std::path::MAIN_SEPARATOR;
+    assert_eq!(
+        lines,
+        [
+            "root_name  repo_relative_path                 repo_kind".to_owned(),
+            format!("default    example.com{sep}a{sep}short                normal"),
+            format!("default    example.com{sep}owner{sep}long-repository  normal"),
+        ]
+    );
+
+    common::souko_cmd(home.path())
+        .args([
+            "list",
+            "--format",
+            "table",
+            "--columns",
+            "repo_kind,root_name",
+            "--no-header",
+        ])
+        .assert()
+        .success()
+        .stdout("normal  default\nnormal  default\n");
+}
+
+#[test]
+fn list_table_options_require_table_format() {
+    let home = TempDir::new().unwrap();
+
+    common::souko_cmd(home.path())
+        .args(["list", "--json", "--no-header"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--format table"));
+
+    common::souko_cmd(home.path())
+        .args(["list", "--format", "table", "--template", "{root_name}"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--template"));
+}
+
+#[test]
+fn list_null_terminated_output() {
+    let home = TempDir::new().unwrap();
+    common::init_repos(&home, &["example.com/a/one", "example.com/a/two"]);
+
+    let output = common::souko_cmd(home.path())
+        .args([
+            "list",
+            "--null",
+            "--template",
+            "{repo_relative_path | basename}",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    assert_eq!(output, b"one\0two\0");
+
+    common::souko_cmd(home.path())
+        .args(["list", "--null", "--json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("`--null`"));
+}
+
+#[test]
+fn list_ndjson_output_has_one_object_per_repo() {
+    let home = TempDir::new().unwrap();
+    common::init_repos(&home, &["example.com/a/one", "example.com/a/two"]);
+
+    let output = common::souko_cmd(home.path())
+        .args(["list", "--format", "ndjson"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let output = String::from_utf8(output).unwrap();
+    let records = output
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0]["root_name"], "default");
+    assert_eq!(records[0]["repo_kind"], "normal");
+    assert!(records[1]["repo_canonical_path"].is_string());
+
+    let output = common::souko_cmd(home.path())
+        .args(["list", "--format", "ndjson", "--columns", "repo_kind"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "{\"repo_kind\":\"normal\"}\n{\"repo_kind\":\"normal\"}\n"
+    );
+}
+
+#[test]
+fn list_json_output_uses_column_names() {
+    let home = TempDir::new().unwrap();
+    common::init_repos(&home, &["example.com/a/one"]);
+
+    let output = common::souko_cmd(home.path())
+        .args(["list", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: Value = serde_json::from_slice(&output).unwrap();
+    let root = &value["roots"][0];
+    assert_eq!(root["root_name"], "default");
+    assert!(root["root_canonical_path"].is_string());
+    let repo = &root["repos"][0];
+    assert_eq!(
+        repo["repo_relative_path"],
+        ["example.com", "a", "one"].join(std::path::MAIN_SEPARATOR_STR)
+    );
+    assert_eq!(repo["repo_kind"], "normal");
+    assert!(repo.get("root_name").is_none());
+}
+
+#[test]
+fn list_csv_and_tsv_output_share_columns() {
+    let home = TempDir::new().unwrap();
+    common::init_repos(&home, &["example.com/a/one"]);
+
+    common::souko_cmd(home.path())
+        .args([
+            "list",
+            "--format",
+            "csv",
+            "--columns",
+            "root_name,repo_kind",
+        ])
+        .assert()
+        .success()
+        .stdout("root_name,repo_kind\r\ndefault,normal\r\n");
+
+    common::souko_cmd(home.path())
+        .args([
+            "list",
+            "--format",
+            "tsv",
+            "--columns",
+            "root_name,repo_kind",
+            "--no-header",
+        ])
+        .assert()
+        .success()
+        .stdout("default\tnormal\n");
+
+    let output = common::souko_cmd(home.path())
+        .args(["list", "--format", "csv"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(
+        output.lines().next().unwrap(),
+        "root_name,root_display_path,root_real_path,root_canonical_path,repo_relative_path,repo_display_path,repo_real_path,repo_canonical_path,repo_kind,repo_common_dir"
+    );
+}
+
+#[test]
+fn list_color_output_follows_color_flag_and_environment() {
+    let home = TempDir::new().unwrap();
+    common::init_repos(&home, &["example.com/owner/repo"]);
+
+    let template = "{color.red}{repo_relative_path | basename}{color.reset}";
+
+    common::souko_cmd(home.path())
+        .args(["list", "--color", "always", "--template", template])
+        .assert()
+        .success()
+        .stdout("\x1b[31mrepo\x1b[0m\n");
+
+    common::souko_cmd(home.path())
+        .args(["list", "--color", "never", "--template", template])
+        .env("CLICOLOR_FORCE", "1")
+        .assert()
+        .success()
+        .stdout("repo\n");
+
+    common::souko_cmd(home.path())
+        .args(["list", "--template", template])
+        .env("CLICOLOR_FORCE", "1")
+        .env_remove("NO_COLOR")
+        .assert()
+        .success()
+        .stdout("\x1b[31mrepo\x1b[0m\n");
+
+    common::souko_cmd(home.path())
+        .args(["list", "--template", template])
+        .env("CLICOLOR_FORCE", "1")
+        .env("NO_COLOR", "1")
+        .assert()
+        .success()
+        .stdout("repo\n");
+
+    let output = common::souko_cmd(home.path())
+        .args(["list", "--color", "always"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("\x1b[1mrepo\x1b[0m"), "{output:?}");
+}
@@ -1,47 +1,9 @@
-use std::path::{Path, PathBuf};
-
 use assert_cmd::prelude::*;
-use assert_fs::{TempDir, fixture::ChildPath, prelude::*};
+use assert_fs::{TempDir, prelude::*};
 use predicates::prelude::*;
 
 mod common;
 
-fn data_local_dir(home_dir: &impl PathChild) -> ChildPath {
-    if cfg!(target_os = "linux") {
-        return home_dir.child(".local/share/souko");
-    }
-    if cfg!(target_os = "macos") {
-        return home_dir.child("Library/Application Support/souko");
-    }
-    if cfg!(target_os = "windows") {
-        return home_dir.child(r"AppData\Local\souko\data");
-    }
-    panic!("unsupported platform");
-}
-
-fn config_dir(home_dir: &impl PathChild) -> ChildPath {
-    if cfg!(target_os = "linux") {
-        return home_dir.child(".config/souko");
-    }
-    if cfg!(target_os = "macos") {
-        return home_dir.child("Library/Application Support/souko");
-    }
-    if cfg!(target_os = "windows") {
-        return home_dir.child(r"AppData\Roaming\souko\config");
-    }
-    panic!("unsupported platform");
-}
-
-fn write_config(home: &TempDir, body: &str) {
-    let config_dir = config_dir(home);
-    config_dir.create_dir_all().unwrap();
-    config_dir.child("config.toml").write_str(body).unwrap();
-}
-
-fn canonical(path: &Path) -> PathBuf {
-    dunce::canonicalize(path).unwrap()
-}
-
 #[test]
 fn list_excludes_bare_repo_by_default_and_includes_it_when_enabled() {
     let home = TempDir::new().unwrap();
@@ -52,16 +14,16 @@ fn list_excludes_bare_repo_by_default_and_includes_it_when_enabled() {
         .success()
         .stdout(predicate::str::is_empty());
 
-    let bare_repo = data_local_dir(&home).child("root/bare.git");
+    let bare_repo = common::data_local_dir(&home).child("root/bare.git");
     bare_repo.create_dir_all().unwrap();
     git2::Repository::init_bare(bare_repo.path()).unwrap();
 
-    let default_repo = canonical(
-        data_local_dir(&home)
+    let default_repo = common::canonical(
+        common::data_local_dir(&home)
             .child("root/github.com/gifnksm/souko")
             .path(),
     );
-    let bare_repo = canonical(bare_repo.path());
+    let bare_repo = common::canonical(bare_repo.path());
 
     common::souko_cmd(home.path())
         .args(["list"])
@@ -70,7 +32,7 @@ fn list_excludes_bare_repo_by_default_and_includes_it_when_enabled() {
         .stdout(predicate::str::contains(default_repo.display().to_string()))
         .stdout(predicate::str::contains(bare_repo.display().to_string()).not());
 
-    write_config(
+    common::write_config(
         &home,
         r#"
 [[root]]
@@ -91,11 +53,11 @@ include_bare_repo = true
 fn list_excludes_hidden_dirs_by_default_and_includes_them_when_enabled() {
     let home = TempDir::new().unwrap();
 
-    let hidden_repo = data_local_dir(&home).child("root/.hidden/repo");
+    let hidden_repo = common::data_local_dir(&home).child("root/.hidden/repo");
     hidden_repo.create_dir_all().unwrap();
     git2::Repository::init(hidden_repo.path()).unwrap();
 
-    let hidden_repo = canonical(hidden_repo.path());
+    let hidden_repo = common::canonical(hidden_repo.path());
 
     common::souko_cmd(home.path())
         .args(["list"])
@@ -103,7 +65,7 @@ fn list_excludes_hidden_dirs_by_default_and_includes_them_when_enabled() {
         .success()
         .stdout(predicate::str::contains(hidden_repo.display().to_string()).not());
 
-    write_config(
+    common::write_config(
         &home,
         r#"
 [[root]]
@@ -123,7 +85,7 @@ visit_hidden_dirs = true
 fn list_does_not_visit_repo_subdirs_by_default_and_visits_them_when_enabled() {
     let home = TempDir::new().unwrap();
 
-    let parent_repo = data_local_dir(&home).child("root/parent");
+    let parent_repo = common::data_local_dir(&home).child("root/parent");
     parent_repo.create_dir_all().unwrap();
     git2::Repository::init(parent_repo.path()).unwrap();
 
@@ -131,8 +93,8 @@ fn list_does_not_visit_repo_subdirs_by_default_and_visits_them_when_enabled() {
     child_repo.create_dir_all().unwrap();
     git2::Repository::init(child_repo.path()).unwrap();
 
-    let parent_repo = canonical(parent_repo.path());
-    let child_repo = canonical(child_repo.path());
+    let parent_repo = common::canonical(parent_repo.path());
+    let child_repo = common::canonical(child_repo.path());
 
     common::souko_cmd(home.path())
         .args(["list"])
@@ -141,7 +103,7 @@ fn list_does_not_visit_repo_subdirs_by_default_and_visits_them_when_enabled() {
         .stdout(predicate::str::contains(parent_repo.display().to_string()))
         .stdout(predicate::str::contains(child_repo.display().to_string()).not());
 
-    write_config(
+    common::write_config(
         &home,
         r#"
 [[root]]
@@ -181,7 +143,7 @@ path = "../repos"
         )
         .unwrap();
 
-    let repo = canonical(repo.path());
+    let repo = common::canonical(repo.path());
 
     common::souko_cmd(home.path())
         .args([
@@ -193,3 +155,138 @@ path = "../repos"
         .success()
         .stdout(predicate::str::contains(repo.display().to_string()));
 }
+
+#[cfg(unix)]
+#[test]
+fn list_does_not_follow_symlinks_by_default_and_follows_them_when_enabled() {
+    let home = TempDir::new().unwrap();
+
+    let external_repo = home.child("external/repo");
+    external_repo.create_dir_all().unwrap();
+    git2::Repository::init(external_repo.path()).unwrap();
+
+    let root = common::data_local_dir(&home).child("root");
+    root.create_dir_all().unwrap();
+    std::os::unix::fs::symlink(external_repo.path(), root.child("linked").path()).unwrap();
+    // a symlink pointing to its ancestor must not be traversed endlessly
+    std::os::unix::fs::symlink(root.path(), root.child("loop").path()).unwrap();
+
+    let external_repo = common::canonical(external_repo.path());
+
+    common::souko_cmd(home.path())
+        .args(["list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(external_repo.display().to_string()).not());
+
+    common::write_config(
+        &home,
+        r#"
+[[root]]
+name = "default"
+follow_symlinks = true
+"#,
+    );
+
+    common::souko_cmd(home.path())
+        .args(["list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            external_repo.display().to_string(),
+        ));
+}
+
+#[test]
+fn list_warns_about_duplicate_repos_and_shows_them_when_requested() {
+    let home = TempDir::new().unwrap();
+
+    let repo = home.child("repos/example");
+    repo.create_dir_all().unwrap();
+    git2::Repository::init(repo.path()).unwrap();
+
+    common::write_config(
+        &home,
+        &format!(
+            r#"
+[[root]]
+name = "default"
+path = '{0}'
+
+[[root]]
+name = "other"
+path = '{0}'
+"#,
+            home.child("repos").path().display()
+        ),
+    );
+
+    let repo = common::canonical(repo.path()).display().to_string();
+
+    common::souko_cmd(home.path())
+        .args(["list"])
+        .assert()
+        .success()
+        .stdout(format!("{repo}\n"))
+        .stderr(predicate::str::contains("duplicate repository"));
+
+    common::souko_cmd(home.path())
+        .args(["list", "--duplicates", "show"])
+        .assert()
+        .success()
+        .stdout(format!("{repo}\n{repo}\n"))
+        .stderr(predicate::str::contains("duplicate repository").not());
+}
+
+#[test]
+fn list_groups_linked_worktrees_and_excludes_them_when_disabled() {
+    let home = TempDir::new().unwrap();
+
+    let root = common::data_local_dir(&home).child("root");
+    let main_repo = root.child("b/main");
+    main_repo.create_dir_all().unwrap();
+    let repo = common::init_repo_with_commit(main_repo.path());
+    let other_repo = root.child("c/other");
+    other_repo.create_dir_all().unwrap();
+    git2::Repository::init(other_repo.path()).unwrap();
+    let worktree = root.child("a/worktree");
+    root.child("a").create_dir_all().unwrap();
+    repo.worktree("worktree", worktree.path(), None).unwrap();
+
+    let main_repo = common::canonical(main_repo.path()).display().to_string();
+    let other_repo = common::canonical(other_repo.path()).display().to_string();
+    let worktree = common::canonical(worktree.path()).display().to_string();
+
+    common::souko_cmd(home.path())
+        .args(["list"])
+        .assert()
+        .success()
+        .stdout(format!("{worktree}\n{main_repo}\n{other_repo}\n"));
+
+    common::souko_cmd(home.path())
+        .args(["list", "--group-worktrees"])
+        .assert()
+        .success()
+        .stdout(format!("{main_repo}\n{worktree}\n{other_repo}\n"));
+
+    common::souko_cmd(home.path())
+        .args(["list", "--template", "{repo_kind}"])
+        .assert()
+        .success()
+        .stdout("linked-worktree\nnormal\nnormal\n");
+
+    common::write_config(
+        &home,
+        r#"
+[[root]]
+name = "default"
+include_linked_worktrees = false
+"#,
+    );
+
+    common::souko_cmd(home.path())
+        .args(["list"])
+        .assert()
+        .success()
+        .stdout(format!("{main_repo}\n{other_repo}\n"));
+}
@@ -0,0 +1,133 @@
+#![cfg(unix)]
+
+use std::{
+    io::{Read as _, Write as _},
+    path::Path,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use assert_fs::{TempDir, prelude::*};
+use portable_pty::{CommandBuilder, PtySize, native_pty_system};
+
+mod common;
+
+struct PickSession {
+    output: mpsc::Receiver<Vec<u8>>,
+    writer: Box<dyn std::io::Write + Send>,
+    child: Box<dyn portable_pty::Child + Send + Sync>,
+    received: Vec<u8>,
+}
+
+impl PickSession {
+    fn spawn(home: &Path, args: &[&str]) -> Self {
+        let pair = native_pty_system()
+            .openpty(PtySize {
+                rows: 24,
+                cols: 120,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .unwrap();
+        let mut cmd = CommandBuilder::new(env!("CARGO_BIN_EXE_souko"));
+        cmd.arg("pick");
+        cmd.args(args);
+        cmd.env("HOME", home);
+        cmd.env("SOUKO_INTEGRATION_TEST_HOME", home);
+        cmd.env("TERM", "xterm-256color");
+        let child = pair.slave.spawn_command(cmd).unwrap();
+        drop(pair.slave);
+
+        let mut reader = pair.master.try_clone_reader().unwrap();
+        let writer = pair.master.take_writer().unwrap();
+        let (tx, output) = mpsc::channel();
+        thread::spawn(move || {
+            // Keep the master side open until the reader is done.
+            let _master = pair.master;
+            let mut buf = [0; 4096];
+            while let Ok(n @ 1..) = reader.read(&mut buf) {
+                if tx.send(buf[..n].to_vec()).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            output,
+            writer,
+            child,
+            received: vec![],
+        }
+    }
+
+    fn wait_for(&mut self, needle: &str) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !String::from_utf8_lossy(&self.received).contains(needle) {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            match self.output.recv_timeout(timeout) {
+                Ok(chunk) => self.received.extend(chunk),
+                Err(_) => panic!(
+                    "`{needle}` not found in output: {:?}",
+                    String::from_utf8_lossy(&self.received)
+                ),
+            }
+        }
+    }
+
+    fn send(&mut self, input: &str) {
+        self.writer.write_all(input.as_bytes()).unwrap();
+        self.writer.flush().unwrap();
+    }
+
+    fn finish(mut self) -> (u32, String) {
+        let status = self.child.wait().unwrap();
+        while let Ok(chunk) = self.output.recv_timeout(Duration::from_secs(1)) {
+            self.received.extend(chunk);
+        }
+        (
+            status.exit_code(),
+            String::from_utf8_lossy(&self.received).into_owned(),
+        )
+    }
+}
+
+#[test]
+fn pick_prints_selected_repo() {
+    let home = TempDir::new().unwrap();
+    common::init_repos(
+        &home,
+        &["example.com/owner/apple", "example.com/owner/banana"],
+    );
+    let banana = dunce::canonicalize(
+        common::data_local_dir(&home)
+            .child("root/example.com/owner/banana")
+            .path(),
+    )
+    .unwrap();
+
+    let mut session = PickSession::spawn(home.path(), &[]);
+    session.wait_for("2/2");
+    session.send("banana");
+    session.wait_for("1/2");
+    session.send("\r");
+    let (status, output) = session.finish();
+
+    assert_eq!(status, 0, "{output:?}");
+    assert!(
+        output.contains(&format!("{}\r\n", banana.display())),
+        "{output:?}"
+    );
+}
+
+#[test]
+fn pick_exits_with_130_when_cancelled() {
+    let home = TempDir::new().unwrap();
+    common::init_repos(&home, &["example.com/owner/apple"]);
+
+    let mut session = PickSession::spawn(home.path(), &["--query", "apple"]);
+    session.wait_for("1/1");
+    session.send("\x03");
+    let (status, output) = session.finish();
+
+    assert_eq!(status, 130, "{output:?}");
+}
@@ -0,0 +1,87 @@
+use assert_cmd::prelude::*;
+use assert_fs::{TempDir, prelude::*};
+use predicates::prelude::*;
+
+mod common;
+
+#[test]
+fn query_explain_shows_steps_and_paths() {
+    let home = TempDir::new().unwrap();
+    let config = home.child("config.toml");
+    config
+        .write_str(
+            r#"
+[[root]]
+name = "flat"
+path = "flat"
+layout = "{repo}"
+
+[query]
+default_scheme = "corp"
+
+[query.scheme_alias]
+corp = "work"
+
+[query.custom_scheme]
+work = "ssh://git@git.corp.example:22/{path}.git"
+"#,
+        )
+        .unwrap();
+    let config_path = config.path().to_str().unwrap();
+
+    common::souko_cmd(home.path())
+        .args(["--config", config_path, "query", "explain", "team/app"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("step #1: default scheme `corp`\n  -> corp:team/app\n")
+                .and(predicate::str::contains(
+                    "step #2: scheme alias `corp` -> `work`\n  -> work:team/app\n",
+                ))
+                .and(predicate::str::contains(
+                    "step #3: custom scheme `work` (template `ssh://git@git.corp.example:22/{path}.git`)\n",
+                ))
+                .and(predicate::str::contains(
+                    "step #4: default port 22 removed\n  -> ssh://git@git.corp.example/team/app.git\n",
+                ))
+                .and(predicate::str::contains(
+                    "url: ssh://git@git.corp.example/team/app.git\n",
+                ))
+                .and(predicate::str::contains("root flat: app\n"))
+                .and(predicate::str::contains(
+                    "root default (selected): git.corp.example/team/app\n",
+                )),
+        );
+
+    let output = common::souko_cmd(home.path())
+        .args([
+            "--config",
+            config_path,
+            "query",
+            "explain",
+            "--json",
+            "git@example.com:owner/repo",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["url"], "ssh://git@example.com/owner/repo");
+    assert_eq!(json["steps"][0]["kind"], "scp-like");
+    assert_eq!(
+        json["steps"][0]["result"],
+        "ssh://git@example.com/owner/repo"
+    );
+    let root = |name: &str| {
+        json["roots"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|root| root["name"] == name)
+            .unwrap()
+    };
+    assert_eq!(root("flat")["relativePath"], "repo");
+    assert_eq!(root("flat")["selected"], false);
+    assert_eq!(root("default")["relativePath"], "example.com/owner/repo");
+    assert_eq!(root("default")["selected"], true);
+}
@@ -0,0 +1,201 @@
+use assert_cmd::prelude::*;
+use assert_fs::{TempDir, fixture::ChildPath, prelude::*};
+use predicates::prelude::*;
+
+mod common;
+
+fn init_repos(root: &ChildPath) {
+    for (path, origin) in [
+        ("github.com/old/name", "https://github.com/new/name.git"),
+        ("github.com/same/name", "https://github.com/same/name.git"),
+    ] {
+        let dir = root.child(path);
+        dir.create_dir_all().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        repo.remote("origin", origin).unwrap();
+    }
+}
+
+fn origin_url(dir: &ChildPath) -> String {
+    let repo = git2::Repository::open(dir.path()).unwrap();
+    repo.find_remote("origin")
+        .unwrap()
+        .url()
+        .unwrap()
+        .to_owned()
+}
+
+#[test]
+fn relocate_without_apply_only_reports_misplaced_repositories() {
+    let home = TempDir::new().unwrap();
+    let root = common::data_local_dir(&home).child("root");
+    init_repos(&root);
+
+    common::souko_cmd(home.path())
+        .arg("relocate")
+        .assert()
+        .success()
+        .stdout("default: github.com/old/name -> github.com/new/name\n")
+        .stderr(predicate::str::contains(
+            "1 misplaced repositories found, pass `--apply` to move them",
+        ));
+
+    root.child("github.com/old/name/.git")
+        .assert(predicate::path::is_dir());
+    root.child("github.com/new")
+        .assert(predicate::path::missing());
+}
+
+#[test]
+fn relocate_apply_moves_repositories_and_removes_empty_directories() {
+    let home = TempDir::new().unwrap();
+    let root = common::data_local_dir(&home).child("root");
+    init_repos(&root);
+
+    common::souko_cmd(home.path())
+        .args(["relocate", "--apply"])
+        .assert()
+        .success()
+        .stdout("default: github.com/old/name -> github.com/new/name\n");
+
+    root.child("github.com/new/name/.git")
+        .assert(predicate::path::is_dir());
+    root.child("github.com/old")
+        .assert(predicate::path::missing());
+    root.child("github.com/same/name/.git")
+        .assert(predicate::path::is_dir());
+
+    common::souko_cmd(home.path())
+        .arg("relocate")
+        .assert()
+        .success()
+        .stdout("")
+        .stderr(predicate::str::contains("no misplaced repositories found"));
+}
+
+#[test]
+fn relocate_refuses_to_overwrite_existing_directory() {
+    let home = TempDir::new().unwrap();
+    let root = common::data_local_dir(&home).child("root");
+    init_repos(&root);
+    root.child("github.com/new/name/file").touch().unwrap();
+
+    common::souko_cmd(home.path())
+        .args(["relocate", "--apply"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "failed to relocate 1 repositories",
+        ));
+
+    root.child("github.com/old/name/.git")
+        .assert(predicate::path::is_dir());
+    root.child("github.com/new/name/file")
+        .assert(predicate::path::is_file());
+}
+
+#[test]
+fn relocate_update_remote_rewrites_origin_to_match_path() {
+    let home = TempDir::new().unwrap();
+    let root = common::data_local_dir(&home).child("root");
+    init_repos(&root);
+    let repo = root.child("github.com/old/name");
+
+    common::souko_cmd(home.path())
+        .args(["relocate", "--update-remote"])
+        .assert()
+        .success()
+        .stdout(
+            "default: github.com/old/name: \
+             https://github.com/new/name.git -> https://github.com/old/name.git\n",
+        );
+    assert_eq!(origin_url(&repo), "https://github.com/new/name.git");
+
+    common::souko_cmd(home.path())
+        .args(["relocate", "--update-remote", "--apply"])
+        .assert()
+        .success();
+    assert_eq!(origin_url(&repo), "https://github.com/old/name.git");
+    repo.child(".git").assert(predicate::path::is_dir());
+}
+
+#[test]
+fn relocate_moves_repositories_to_the_root_selected_by_routes() {
+    let home = TempDir::new().unwrap();
+    let root = common::data_local_dir(&home).child("root");
+    init_repos(&root);
+    let work = home.child("work");
+    common::write_config(
+        &home,
+        &format!(
+            r#"
+[[root]]
+name = "work"
+path = {:?}
+
+[[route]]
+root = "work"
+url = "/same/"
+"#,
+            work.path().to_str().unwrap()
+        ),
+    );
+
+    common::souko_cmd(home.path())
+        .args(["relocate", "--update-remote"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "belongs to root `work` by its origin URL",
+        ));
+
+    common::souko_cmd(home.path())
+        .args(["relocate", "--apply"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("default: github.com/old/name -> github.com/new/name\n").and(
+                predicate::str::contains(
+                    "default: github.com/same/name -> work: github.com/same/name\n",
+                ),
+            ),
+        );
+    work.child("github.com/same/name/.git")
+        .assert(predicate::path::is_dir());
+    root.child("github.com/same")
+        .assert(predicate::path::missing());
+}
+
+#[test]
+fn relocate_keeps_repositories_in_their_root_without_routes() {
+    let home = TempDir::new().unwrap();
+    let work = home.child("work");
+    let repo = work.child("github.com/same/name");
+    repo.create_dir_all().unwrap();
+    git2::Repository::init(repo.path())
+        .unwrap()
+        .remote("origin", "https://github.com/same/name.git")
+        .unwrap();
+    common::write_config(
+        &home,
+        &format!(
+            r#"
+[[root]]
+name = "work"
+path = {:?}
+"#,
+            work.path().to_str().unwrap()
+        ),
+    );
+
+    common::souko_cmd(home.path())
+        .args(["relocate", "--apply"])
+        .assert()
+        .success()
+        .stdout("")
+        .stderr(predicate::str::contains("no misplaced repositories found"));
+    repo.child(".git").assert(predicate::path::is_dir());
+    common::data_local_dir(&home)
+        .child("root/github.com")
+        .assert(predicate::path::missing());
+}
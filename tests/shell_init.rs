@@ -0,0 +1,201 @@
+use assert_cmd::prelude::*;
+use assert_fs::{TempDir, fixture::ChildPath, prelude::*};
+use predicates::prelude::*;
+use serde_json::Value;
+
+mod common;
+
+fn init_repo(home: &TempDir, relative_path: &str) -> ChildPath {
+    let repo = common::data_local_dir(home)
+        .child("root")
+        .child(relative_path);
+    repo.create_dir_all().unwrap();
+    git2::Repository::init(repo.path()).unwrap();
+    repo
+}
+
+fn visits(home: &TempDir) -> Value {
+    let path = common::data_local_dir(home).child("visits.json");
+    serde_json::from_slice(&std::fs::read(path.path()).unwrap()).unwrap()
+}
+
+/// Waits for a visit recorded in the background by the shell hook.
+#[cfg(unix)]
+fn wait_for_visits(home: &TempDir) -> Value {
+    let path = common::data_local_dir(home).child("visits.json");
+    for _ in 0..100 {
+        if path.exists() {
+            return visits(home);
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    panic!("visit is not recorded: {}", path.display());
+}
+
+#[test]
+fn shell_init_prints_integration_code_for_each_shell() {
+    let home = TempDir::new().unwrap();
+
+    for shell in ["bash", "zsh", "fish", "nushell", "elvish", "powershell"] {
+        common::souko_cmd(home.path())
+            .args(["shell-init", shell])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("souko-cd"))
+            .stdout(predicate::str::contains("souko visit --"));
+    }
+}
+
+#[test]
+fn shell_init_does_not_load_config() {
+    let home = TempDir::new().unwrap();
+    let config = home.child("config.toml");
+    config.write_str("invalid = [").unwrap();
+
+    common::souko_cmd(home.path())
+        .args(["--config", config.path().to_str().unwrap()])
+        .args(["shell-init", "bash"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn visit_records_repository_containing_directory() {
+    let home = TempDir::new().unwrap();
+    let repo = init_repo(&home, "github.com/owner/repo");
+    let subdir = repo.child("src");
+    subdir.create_dir_all().unwrap();
+
+    for path in [repo.path(), subdir.path()] {
+        common::souko_cmd(home.path())
+            .args(["visit", "--"])
+            .arg(path)
+            .assert()
+            .success()
+            .stdout(predicate::str::is_empty());
+    }
+
+    let repo_path = repo.path().canonicalize().unwrap();
+    let visits = visits(&home);
+    let repos = visits["repos"].as_object().unwrap();
+    assert_eq!(repos.len(), 1);
+    let entry = &repos[repo_path.to_str().unwrap()];
+    assert_eq!(entry["count"], 2);
+    assert!(entry["lastVisited"].is_string());
+}
+
+#[test]
+fn visit_forgets_repositories_not_visited_for_long() {
+    let home = TempDir::new().unwrap();
+    let repo = init_repo(&home, "github.com/owner/repo");
+    common::data_local_dir(&home)
+        .child("visits.json")
+        .write_str(r#"{"repos":{"/old/repo":{"count":10,"lastVisited":"2000-01-01T00:00:00Z"}}}"#)
+        .unwrap();
+
+    common::souko_cmd(home.path())
+        .args(["visit", "--"])
+        .arg(repo.path())
+        .assert()
+        .success();
+
+    let repo_path = repo.path().canonicalize().unwrap();
+    let visits = visits(&home);
+    let repos = visits["repos"].as_object().unwrap();
+    assert_eq!(
+        repos.keys().collect::<Vec<_>>(),
+        [repo_path.to_str().unwrap()]
+    );
+}
+
+#[test]
+fn visit_does_not_read_ssh_config() {
+    let home = TempDir::new().unwrap();
+    let repo = init_repo(&home, "github.com/owner/repo");
+    // reading a directory as the ssh config fails
+    home.child(".ssh/config").create_dir_all().unwrap();
+    let config = home.child("config.toml");
+    config
+        .write_str("[query]\nuse_ssh_config = true\n")
+        .unwrap();
+
+    common::souko_cmd(home.path())
+        .args(["--config", config.path().to_str().unwrap()])
+        .args(["visit", "--"])
+        .arg(repo.path())
+        .assert()
+        .success();
+}
+
+#[test]
+fn visit_ignores_directories_outside_repositories() {
+    let home = TempDir::new().unwrap();
+    init_repo(&home, "github.com/owner/repo");
+    let root_subdir = common::data_local_dir(&home).child("root/github.com");
+    let outside = home.child("outside");
+    outside.create_dir_all().unwrap();
+
+    for path in [root_subdir.path(), outside.path()] {
+        common::souko_cmd(home.path())
+            .args(["visit", "--"])
+            .arg(path)
+            .assert()
+            .success();
+    }
+
+    common::data_local_dir(&home)
+        .child("visits.json")
+        .assert(predicate::path::missing());
+}
+
+#[test]
+fn visit_fails_for_missing_directory() {
+    let home = TempDir::new().unwrap();
+
+    common::souko_cmd(home.path())
+        .args(["visit", "--"])
+        .arg(home.child("missing").path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("failed to get canonical path"));
+}
+
+#[cfg(unix)]
+#[test]
+fn bash_hook_records_visit_on_directory_change() {
+    use std::{env, path::PathBuf, process::Command};
+
+    if Command::new("bash").arg("--version").output().is_err() {
+        return;
+    }
+
+    let home = TempDir::new().unwrap();
+    let repo = init_repo(&home, "github.com/owner/repo");
+    let souko_dir = PathBuf::from(env!("CARGO_BIN_EXE_souko"))
+        .parent()
+        .unwrap()
+        .to_owned();
+    let path = env::join_paths(
+        std::iter::once(souko_dir).chain(env::split_paths(&env::var_os("PATH").unwrap())),
+    )
+    .unwrap();
+
+    let script = r#"
+        eval "$(souko shell-init bash)"
+        cd "$1"
+        eval "$PROMPT_COMMAND"
+        eval "$PROMPT_COMMAND"
+    "#;
+    Command::new("bash")
+        .env("HOME", home.path())
+        .env("SOUKO_INTEGRATION_TEST_HOME", home.path())
+        .env("PATH", path)
+        .args(["--norc", "--noprofile", "-c", script, "bash"])
+        .arg(repo.path())
+        .assert()
+        .success();
+
+    let repo_path = repo.path().canonicalize().unwrap();
+    let visits = wait_for_visits(&home);
+    assert_eq!(visits["repos"][repo_path.to_str().unwrap()]["count"], 1);
+}
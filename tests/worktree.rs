@@ -0,0 +1,164 @@
+use assert_cmd::prelude::*;
+use assert_fs::{TempDir, prelude::*};
+use predicates::prelude::*;
+
+mod common;
+
+#[test]
+fn worktree_add_list_and_remove() {
+    let home = TempDir::new().unwrap();
+
+    let repo = common::data_local_dir(&home).child("root/github.com/owner/repo");
+    repo.create_dir_all().unwrap();
+    common::init_repo_with_commit(repo.path());
+
+    common::souko_cmd(home.path())
+        .args(["worktree", "add", "owner/repo", "feature/foo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    let worktree = common::data_local_dir(&home).child("root/github.com/owner/repo@feature-foo");
+    worktree.child(".git").assert(predicate::path::is_file());
+    let worktree_path = common::canonical(worktree.path()).display().to_string();
+
+    common::souko_cmd(home.path())
+        .args(["worktree", "list", "owner/repo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\tfeature/foo\n"));
+
+    common::souko_cmd(home.path())
+        .args(["list", "--template", "{repo_kind}\t{repo_canonical_path}"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "linked-worktree\t{worktree_path}\n"
+        )));
+
+    // adding the worktree of the same branch again fails without removing the existing one
+    common::souko_cmd(home.path())
+        .args(["worktree", "add", "owner/repo", "feature/foo"])
+        .assert()
+        .failure();
+    worktree.child(".git").assert(predicate::path::is_file());
+
+    worktree.child("untracked").touch().unwrap();
+    common::souko_cmd(home.path())
+        .args(["worktree", "remove", "owner/repo", "feature/foo"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("uncommitted changes"));
+    worktree.assert(predicate::path::is_dir());
+
+    common::souko_cmd(home.path())
+        .args(["worktree", "remove", "--force", "owner/repo", "feature/foo"])
+        .assert()
+        .success();
+    worktree.assert(predicate::path::missing());
+
+    common::souko_cmd(home.path())
+        .args(["worktree", "list", "owner/repo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn worktree_add_uses_configured_path_template_and_cleans_up_on_failure() {
+    let home = TempDir::new().unwrap();
+
+    let config_dir = home.child(if cfg!(target_os = "linux") {
+        ".config/souko"
+    } else if cfg!(target_os = "macos") {
+        "Library/Application Support/souko"
+    } else {
+        r"AppData\Roaming\souko\config"
+    });
+    config_dir.create_dir_all().unwrap();
+    config_dir
+        .child("config.toml")
+        .write_str(
+            r#"
+[worktree]
+path_template = "worktrees/{repo_name}/{branch_slug}"
+"#,
+        )
+        .unwrap();
+
+    // the repository does not exist, so no directory should be left behind
+    common::souko_cmd(home.path())
+        .args(["worktree", "add", "owner/missing", "main"])
+        .assert()
+        .failure();
+    common::data_local_dir(&home)
+        .child("root/worktrees")
+        .assert(predicate::path::missing());
+
+    let repo = common::data_local_dir(&home).child("root/github.com/owner/repo");
+    repo.create_dir_all().unwrap();
+    common::init_repo_with_commit(repo.path());
+
+    common::souko_cmd(home.path())
+        .args(["worktree", "add", "owner/repo", "topic"])
+        .assert()
+        .success();
+    common::data_local_dir(&home)
+        .child("root/worktrees/repo/topic/.git")
+        .assert(predicate::path::is_file());
+}
+
+#[test]
+fn worktree_add_failure_deletes_created_branch() {
+    let home = TempDir::new().unwrap();
+    let repo = common::data_local_dir(&home).child("root/github.com/owner/repo");
+    repo.create_dir_all().unwrap();
+    common::init_repo_with_commit(repo.path());
+
+    // git refuses to add a worktree to a non-empty directory, so adding it
+    // fails after the branch is created.
+    common::data_local_dir(&home)
+        .child("root/github.com/owner/repo@topic/file")
+        .touch()
+        .unwrap();
+    common::souko_cmd(home.path())
+        .args(["worktree", "add", "owner/repo", "topic"])
+        .assert()
+        .failure();
+
+    let git_repo = git2::Repository::open(repo.path()).unwrap();
+    assert!(
+        git_repo
+            .find_branch("topic", git2::BranchType::Local)
+            .is_err()
+    );
+}
+
+#[test]
+fn worktree_add_separates_branches_with_the_same_slug() {
+    let home = TempDir::new().unwrap();
+    let root = common::data_local_dir(&home).child("root");
+    let repo = root.child("github.com/owner/repo");
+    repo.create_dir_all().unwrap();
+    common::init_repo_with_commit(repo.path());
+
+    for branch in ["a-b", "a/b"] {
+        common::souko_cmd(home.path())
+            .args(["worktree", "add", "owner/repo", branch])
+            .assert()
+            .success();
+    }
+    root.child("github.com/owner/repo@a-b/.git")
+        .assert(predicate::path::is_file());
+    root.child("github.com/owner/repo@a-b-2/.git")
+        .assert(predicate::path::is_file());
+
+    common::souko_cmd(home.path())
+        .args(["worktree", "remove", "owner/repo", "a/b"])
+        .assert()
+        .success();
+    root.child("github.com/owner/repo@a-b-2")
+        .assert(predicate::path::missing());
+    root.child("github.com/owner/repo@a-b/.git")
+        .assert(predicate::path::is_file());
+}